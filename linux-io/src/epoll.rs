@@ -0,0 +1,259 @@
+//! An epoll instance monitors a set of registered files for readiness, and
+//! then reports the events that occurred on them along with a caller-chosen
+//! token for each file, so that a program can wait for many files at once
+//! without passing the full set to the kernel on every call.
+
+use core::time::Duration;
+
+use crate::fd::ioctl::IoDevice;
+use crate::result::Result;
+use crate::signal::sigset_t;
+use crate::File;
+
+const SIGSET_SIZE: linux_unsafe::size_t = core::mem::size_of::<sigset_t>();
+
+/// A marker type for [`File`] objects that represent epoll instances.
+#[derive(Clone, Copy, Debug)]
+pub struct EpollDevice;
+
+impl IoDevice for EpollDevice {}
+
+/// An epoll instance.
+pub type Epoll = File<EpollDevice>;
+
+impl File<EpollDevice> {
+    /// Create a new epoll instance.
+    ///
+    /// The new file descriptor has the close-on-exec flag set.
+    #[inline]
+    pub fn epoll_create() -> Result<Self> {
+        let result = unsafe { linux_unsafe::epoll_create1(linux_unsafe::EPOLL_CLOEXEC) };
+        result
+            .map(|fd| unsafe { File::from_raw_fd(fd) })
+            .map_err(|e| e.into())
+    }
+
+    /// Start monitoring the given file for the given events, reporting
+    /// them with the given token.
+    ///
+    /// Fails with `EEXIST` if the file is already registered.
+    #[inline]
+    pub fn add<Device>(&self, file: &File<Device>, events: EpollEvents, token: u64) -> Result<()> {
+        let ev = EpollEvent::new(events, token);
+        unsafe { self.ctl(linux_unsafe::EPOLL_CTL_ADD, file.fd, &ev.raw) }
+    }
+
+    /// Change the events and token for a file that is already registered.
+    ///
+    /// This also re-arms a file that was registered with
+    /// [`EpollEvents::ONESHOT`] after it has reported an event.
+    /// [`EpollEvents::EXCLUSIVE`] cannot be used with this operation.
+    #[inline]
+    pub fn modify<Device>(
+        &self,
+        file: &File<Device>,
+        events: EpollEvents,
+        token: u64,
+    ) -> Result<()> {
+        let ev = EpollEvent::new(events, token);
+        unsafe { self.ctl(linux_unsafe::EPOLL_CTL_MOD, file.fd, &ev.raw) }
+    }
+
+    /// Stop monitoring the given file.
+    ///
+    /// The kernel automatically removes a file once all descriptors
+    /// referring to its open file description are closed, so this is needed
+    /// only to stop monitoring a file that remains open.
+    #[inline]
+    pub fn delete<Device>(&self, file: &File<Device>) -> Result<()> {
+        unsafe { self.ctl(linux_unsafe::EPOLL_CTL_DEL, file.fd, core::ptr::null()) }
+    }
+
+    unsafe fn ctl(
+        &self,
+        op: linux_unsafe::int,
+        fd: linux_unsafe::int,
+        event: *const linux_unsafe::epoll_event,
+    ) -> Result<()> {
+        let result = unsafe { linux_unsafe::epoll_ctl(self.fd, op, fd, event) };
+        result.map(|_| ()).map_err(|e| e.into())
+    }
+
+    /// Wait for events on any of the registered files, returning the part
+    /// of the given buffer that was populated.
+    ///
+    /// If `timeout` is `None` then this waits indefinitely. Otherwise the
+    /// timeout is rounded up to the nearest millisecond, and the result is
+    /// empty if it expires before any events occur. A timeout of zero
+    /// returns immediately.
+    #[inline]
+    pub fn wait<'a>(
+        &self,
+        buf: &'a mut [EpollEvent],
+        timeout: Option<Duration>,
+    ) -> Result<&'a [EpollEvent]> {
+        let timeout = match timeout {
+            Some(d) => timeout_millis(d),
+            None => -1,
+        };
+        let result = unsafe {
+            linux_unsafe::epoll_pwait(
+                self.fd,
+                EpollEvent::as_raw_mut_ptr(buf),
+                max_events(buf.len()),
+                timeout,
+                core::ptr::null(),
+                SIGSET_SIZE,
+            )
+        };
+        result.map(|n| &buf[..(n as usize)]).map_err(|e| e.into())
+    }
+
+    /// Like [`Self::wait`], but uses the `epoll_pwait2` system call to
+    /// support a timeout with nanosecond precision and to optionally replace
+    /// the calling thread's signal mask for the duration of the wait.
+    ///
+    /// Replacing the signal mask allows safely waiting for both file events
+    /// and signals that are otherwise blocked, without the race that would
+    /// occur if unblocking the signals before the call.
+    #[inline]
+    pub fn wait_with_signal_mask<'a>(
+        &self,
+        buf: &'a mut [EpollEvent],
+        timeout: Option<Duration>,
+        sigmask: Option<&sigset_t>,
+    ) -> Result<&'a [EpollEvent]> {
+        let ts = match timeout {
+            Some(d) => Some(crate::time::kernel_timespec_from_duration(d)?),
+            None => None,
+        };
+        let ts_ptr = match &ts {
+            Some(ts) => ts as *const linux_unsafe::kernel_timespec,
+            None => core::ptr::null(),
+        };
+        let mask_ptr = match sigmask {
+            Some(mask) => mask as *const sigset_t,
+            None => core::ptr::null(),
+        };
+        let result = unsafe {
+            linux_unsafe::epoll_pwait2(
+                self.fd,
+                EpollEvent::as_raw_mut_ptr(buf),
+                max_events(buf.len()),
+                ts_ptr,
+                mask_ptr,
+                SIGSET_SIZE,
+            )
+        };
+        result.map(|n| &buf[..(n as usize)]).map_err(|e| e.into())
+    }
+}
+
+/// An event reported by [`File::wait`], or the registration details for
+/// a file.
+///
+/// This has the same memory layout as the kernel's `epoll_event` type, and
+/// so a slice of these can be passed directly to the kernel.
+#[derive(Clone, Copy, Debug)]
+#[repr(transparent)]
+pub struct EpollEvent {
+    raw: linux_unsafe::epoll_event,
+}
+
+impl EpollEvent {
+    /// Construct an event with the given event bits and token.
+    ///
+    /// Buffers passed to [`File::wait`] can be initialized using
+    /// `EpollEvent::new(EpollEvents::NONE, 0)`, since the kernel overwrites
+    /// them.
+    #[inline(always)]
+    pub const fn new(events: EpollEvents, token: u64) -> Self {
+        Self {
+            raw: linux_unsafe::epoll_event {
+                events: events.0,
+                data: linux_unsafe::epoll_data { u64: token },
+            },
+        }
+    }
+
+    /// Returns the events that occurred.
+    #[inline(always)]
+    pub const fn events(&self) -> EpollEvents {
+        EpollEvents(self.raw.events)
+    }
+
+    /// Returns the token that was given when registering the file.
+    #[inline(always)]
+    pub const fn token(&self) -> u64 {
+        let data = self.raw.data;
+        unsafe { data.u64 }
+    }
+
+    #[inline(always)]
+    fn as_raw_mut_ptr(bufs: &mut [Self]) -> *mut linux_unsafe::epoll_event {
+        bufs.as_mut_ptr() as *mut linux_unsafe::epoll_event
+    }
+}
+
+impl Default for EpollEvent {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new(EpollEvents::NONE, 0)
+    }
+}
+
+flags_type! {
+    /// Event types and registration flags for use with [`File::add`] and
+    /// [`File::modify`], and the event types reported by [`File::wait`].
+    pub struct EpollEvents(u32);
+
+    /// No events.
+    const NONE = 0;
+    /// The file is ready for reading.
+    const IN = linux_unsafe::EPOLLIN;
+    /// The file is ready for writing.
+    const OUT = linux_unsafe::EPOLLOUT;
+    /// An exceptional condition, such as out-of-band data on a TCP socket.
+    const PRI = linux_unsafe::EPOLLPRI;
+    /// An error condition. This is always reported when it occurs, and
+    /// need not be requested.
+    const ERR = linux_unsafe::EPOLLERR;
+    /// Hang up. This is always reported when it occurs, and need not be
+    /// requested.
+    const HUP = linux_unsafe::EPOLLHUP;
+    /// The peer of a stream socket closed its connection or shut down
+    /// writing.
+    const RDHUP = linux_unsafe::EPOLLRDHUP;
+    /// Registration flag requesting edge-triggered notification, where an
+    /// event is reported only when the file's readiness changes.
+    const EDGE_TRIGGERED = linux_unsafe::EPOLLET;
+    /// Registration flag that disables the file after one event is
+    /// reported, until it is re-armed using [`File::modify`].
+    const ONESHOT = linux_unsafe::EPOLLONESHOT;
+    /// Registration flag that avoids waking all of the epoll instances that
+    /// monitor the same file when an event occurs. Only valid with
+    /// [`File::add`].
+    const EXCLUSIVE = linux_unsafe::EPOLLEXCLUSIVE;
+    /// Registration flag that prevents the system from suspending while an
+    /// event is pending. Requires the `CAP_BLOCK_SUSPEND` capability.
+    const WAKEUP = linux_unsafe::EPOLLWAKEUP;
+}
+
+/// Converts a timeout to milliseconds for `epoll_pwait`, rounding up so that
+/// a nonzero timeout never becomes a non-blocking poll.
+fn timeout_millis(timeout: Duration) -> linux_unsafe::int {
+    let ms = timeout.as_nanos().div_ceil(1_000_000);
+    if ms > (linux_unsafe::int::MAX as u128) {
+        linux_unsafe::int::MAX
+    } else {
+        ms as linux_unsafe::int
+    }
+}
+
+fn max_events(len: usize) -> linux_unsafe::int {
+    if len > (linux_unsafe::int::MAX as usize) {
+        linux_unsafe::int::MAX
+    } else {
+        len as linux_unsafe::int
+    }
+}
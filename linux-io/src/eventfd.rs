@@ -0,0 +1,78 @@
+//! An eventfd is a kernel-maintained 64-bit counter that can be used as a
+//! lightweight event notification mechanism, either between threads or
+//! between userspace and the kernel.
+//!
+//! Writing to an eventfd adds to its counter, while reading from it waits
+//! until the counter is nonzero and then either resets it to zero or, in
+//! semaphore mode, decrements it by one. An eventfd is readable whenever
+//! its counter is nonzero, and so it can be used with [`crate::poll`] and
+//! [`crate::epoll`] along with other files.
+
+use crate::fd::ioctl::IoDevice;
+use crate::result::Result;
+use crate::File;
+
+/// A marker type for [`File`] objects that represent eventfd instances.
+#[derive(Clone, Copy, Debug)]
+pub struct EventFdDevice;
+
+impl IoDevice for EventFdDevice {}
+
+/// An eventfd instance.
+pub type EventFd = File<EventFdDevice>;
+
+impl File<EventFdDevice> {
+    /// Create a new eventfd whose counter starts at `initval`.
+    ///
+    /// Most callers should include [`EventFdFlags::CLOEXEC`] in `flags`.
+    #[inline]
+    pub fn new(initval: u32, flags: EventFdFlags) -> Result<Self> {
+        let result = unsafe { linux_unsafe::eventfd2(initval, flags.raw()) };
+        result
+            .map(|fd| unsafe { File::from_raw_fd(fd) })
+            .map_err(|e| e.into())
+    }
+
+    /// Add `n` to the counter, waking any waiters.
+    ///
+    /// If adding `n` would make the counter exceed `u64::MAX - 1` then this
+    /// blocks until the counter is read, unless the eventfd is in
+    /// non-blocking mode, in which case it fails with
+    /// [`EAGAIN`](crate::result::EAGAIN). Signalling `u64::MAX` fails with
+    /// [`EINVAL`](crate::result::EINVAL).
+    #[inline]
+    pub fn signal(&self, n: u64) -> Result<()> {
+        let buf = n.to_ne_bytes();
+        self.write(&buf).map(|_| ())
+    }
+
+    /// Wait until the counter is nonzero and then consume it.
+    ///
+    /// Normally this returns the counter's value and resets it to zero. If
+    /// the eventfd was created with [`EventFdFlags::SEMAPHORE`] then this
+    /// instead decrements the counter and returns 1.
+    ///
+    /// If the eventfd is in non-blocking mode and the counter is zero then
+    /// this fails with [`EAGAIN`](crate::result::EAGAIN).
+    #[inline]
+    pub fn wait(&self) -> Result<u64> {
+        let mut buf = [0_u8; 8];
+        self.read(&mut buf)?;
+        Ok(u64::from_ne_bytes(buf))
+    }
+}
+
+flags_type! {
+    /// Flags for [`EventFd::new`].
+    pub struct EventFdFlags(linux_unsafe::int);
+
+    /// No flags.
+    const NONE = 0;
+    /// Set the close-on-exec flag on the new file descriptor.
+    const CLOEXEC = linux_unsafe::EFD_CLOEXEC;
+    /// Put the new file descriptor in non-blocking mode.
+    const NONBLOCK = linux_unsafe::EFD_NONBLOCK;
+    /// Use semaphore semantics for reads, so that each read decrements the
+    /// counter by one instead of resetting it to zero.
+    const SEMAPHORE = linux_unsafe::EFD_SEMAPHORE;
+}
@@ -0,0 +1,503 @@
+//! A fanotify group reports events for whole mounts, filesystems, or
+//! individual files and directories, and can also intercept attempts to
+//! open or access files so that the program can allow or deny them.
+//!
+//! Creating a fanotify group typically requires the `CAP_SYS_ADMIN`
+//! capability.
+
+use core::ffi::CStr;
+
+use crate::fd::ioctl::IoDevice;
+use crate::result::Result;
+use crate::File;
+
+/// A marker type for [`File`] objects that represent fanotify groups.
+#[derive(Clone, Copy, Debug)]
+pub struct FanotifyDevice;
+
+impl IoDevice for FanotifyDevice {}
+
+/// A fanotify group.
+pub type Fanotify = File<FanotifyDevice>;
+
+impl File<FanotifyDevice> {
+    /// Create a new fanotify group with no marks.
+    ///
+    /// `event_file_flags` are the file status flags, such as `O_RDONLY`
+    /// and `O_CLOEXEC`, for the file descriptors that the kernel opens for
+    /// the subject of each event. These are not used for groups that report
+    /// file identifiers instead of file descriptors.
+    ///
+    /// Most callers should include [`FanotifyFlags::CLOEXEC`] in `flags`.
+    #[inline]
+    pub fn new(flags: FanotifyFlags, event_file_flags: linux_unsafe::int) -> Result<Self> {
+        let result = unsafe {
+            linux_unsafe::fanotify_init(flags.raw(), event_file_flags as linux_unsafe::uint)
+        };
+        result
+            .map(|fd| unsafe { File::from_raw_fd(fd) })
+            .map_err(|e| e.into())
+    }
+
+    /// Add, remove, or change a mark on the filesystem object at the given
+    /// path, which is relative to the current working directory if not
+    /// absolute.
+    #[inline]
+    pub fn mark(&self, flags: MarkFlags, mask: FanotifyMask, path: &CStr) -> Result<()> {
+        unsafe { self.mark_raw(flags, mask, linux_unsafe::AT_FDCWD, path.as_ptr()) }
+    }
+
+    /// Add, remove, or change a mark on the filesystem object at the given
+    /// path relative to the given directory.
+    #[inline]
+    pub fn mark_relative<Device>(
+        &self,
+        flags: MarkFlags,
+        mask: FanotifyMask,
+        dir: &File<Device>,
+        path: &CStr,
+    ) -> Result<()> {
+        unsafe { self.mark_raw(flags, mask, dir.fd, path.as_ptr()) }
+    }
+
+    /// Add, remove, or change a mark on the filesystem object that the given
+    /// file refers to.
+    #[inline]
+    pub fn mark_file<Device>(
+        &self,
+        flags: MarkFlags,
+        mask: FanotifyMask,
+        file: &File<Device>,
+    ) -> Result<()> {
+        unsafe { self.mark_raw(flags, mask, file.fd, core::ptr::null()) }
+    }
+
+    unsafe fn mark_raw(
+        &self,
+        flags: MarkFlags,
+        mask: FanotifyMask,
+        dirfd: linux_unsafe::int,
+        path: *const linux_unsafe::char,
+    ) -> Result<()> {
+        let result =
+            unsafe { linux_unsafe::fanotify_mark(self.fd, flags.raw(), mask.raw(), dirfd, path) };
+        result.map(|_| ()).map_err(|e| e.into())
+    }
+
+    /// Read one or more pending events into the given buffer, returning an
+    /// iterator over the events that were read.
+    ///
+    /// Blocks until at least one event is pending unless the group is in
+    /// non-blocking mode, in which case it returns
+    /// [`EAGAIN`](crate::result::EAGAIN) if no events are pending.
+    ///
+    /// Each event that carries a file descriptor must be closed by the
+    /// caller, such as by using [`FanotifyEvent::take_file`], or else the
+    /// descriptors will leak.
+    #[inline]
+    pub fn read_events<'a>(&self, buf: &'a mut [u8]) -> Result<FanotifyEvents<'a>> {
+        let len = self.read(buf)?;
+        Ok(FanotifyEvents::from_buffer(&buf[..len]))
+    }
+
+    /// Respond to a permission event, allowing or denying the operation
+    /// that caused it.
+    ///
+    /// This must be called before closing the event's file descriptor.
+    #[inline]
+    pub fn respond(&self, event: &FanotifyEvent<'_>, response: FanotifyResponse) -> Result<()> {
+        let raw = linux_unsafe::fanotify_response {
+            fd: event.metadata.fd,
+            response: response.raw(),
+        };
+        let len = core::mem::size_of::<linux_unsafe::fanotify_response>();
+        unsafe { self.write_raw(&raw as *const _ as *const linux_unsafe::void, len) }.map(|_| ())
+    }
+}
+
+/// A single event extracted from a buffer populated by reading from a
+/// fanotify group.
+#[derive(Debug, Clone)]
+pub struct FanotifyEvent<'a> {
+    metadata: linux_unsafe::fanotify_event_metadata,
+    info: &'a [u8],
+}
+
+impl<'a> FanotifyEvent<'a> {
+    /// Returns the raw event metadata.
+    #[inline(always)]
+    pub const fn raw_metadata(&self) -> &linux_unsafe::fanotify_event_metadata {
+        &self.metadata
+    }
+
+    /// Returns the type of event, along with any event flags.
+    #[inline(always)]
+    pub const fn mask(&self) -> FanotifyMask {
+        FanotifyMask(self.metadata.mask)
+    }
+
+    /// Returns the process that caused the event, or the thread if the
+    /// group was created with [`FanotifyFlags::REPORT_TID`].
+    #[inline(always)]
+    pub const fn pid(&self) -> linux_unsafe::pid_t {
+        self.metadata.pid
+    }
+
+    /// Returns the file descriptor that the kernel opened for the subject
+    /// of the event, or `None` if the event doesn't have one, such as
+    /// when the group reports file identifiers or the queue overflowed.
+    #[inline(always)]
+    pub const fn raw_fd(&self) -> Option<linux_unsafe::int> {
+        if self.metadata.fd < 0 {
+            None
+        } else {
+            Some(self.metadata.fd)
+        }
+    }
+
+    /// Wraps the event's file descriptor in a [`File`], so that it will be
+    /// closed when dropped.
+    ///
+    /// Safety: Callers must call this at most once for each event that was
+    /// read from the kernel, and must not otherwise close the file
+    /// descriptor.
+    #[inline]
+    pub unsafe fn take_file(&self) -> Option<File> {
+        self.raw_fd().map(|fd| unsafe { File::from_raw_fd(fd) })
+    }
+
+    /// Returns an iterator over the information records that follow the
+    /// event metadata.
+    #[inline]
+    pub fn info_records(&self) -> FanotifyInfoRecords<'a> {
+        FanotifyInfoRecords { remain: self.info }
+    }
+}
+
+/// An iterator over events in an already-populated fanotify read buffer.
+pub struct FanotifyEvents<'a> {
+    remain: &'a [u8],
+}
+
+impl<'a> FanotifyEvents<'a> {
+    pub fn from_buffer(buf: &'a [u8]) -> Self {
+        Self { remain: buf }
+    }
+
+    /// Consume the iterator object and obtain the remaining bytes that it
+    /// hasn't yet transformed into `FanotifyEvent` values.
+    pub fn to_remaining_bytes(self) -> &'a [u8] {
+        self.remain
+    }
+}
+
+impl<'a> Iterator for FanotifyEvents<'a> {
+    type Item = FanotifyEvent<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        const HEADER_SIZE: usize = core::mem::size_of::<linux_unsafe::fanotify_event_metadata>();
+
+        if self.remain.len() < HEADER_SIZE {
+            return None;
+        }
+        // The caller's buffer might not be suitably aligned for the header.
+        let metadata = unsafe {
+            core::ptr::read_unaligned(
+                self.remain.as_ptr() as *const linux_unsafe::fanotify_event_metadata
+            )
+        };
+        let event_len = metadata.event_len as usize;
+        let metadata_len = metadata.metadata_len as usize;
+        if metadata.vers != linux_unsafe::FANOTIFY_METADATA_VERSION
+            || metadata_len < HEADER_SIZE
+            || event_len < metadata_len
+            || event_len > self.remain.len()
+        {
+            return None;
+        }
+        let info = &self.remain[metadata_len..event_len];
+        self.remain = &self.remain[event_len..];
+        Some(FanotifyEvent { metadata, info })
+    }
+}
+
+/// A file identifier reported in a fanotify information record.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct FanotifyFid<'a> {
+    /// The raw record type, such as `FAN_EVENT_INFO_TYPE_DFID`.
+    pub info_type: u8,
+    /// Identifies the filesystem containing the object.
+    pub fsid: [linux_unsafe::int; 2],
+    /// The type of the file handle, as used with `open_by_handle_at`.
+    pub handle_type: linux_unsafe::int,
+    /// The opaque file handle bytes.
+    pub handle: &'a [u8],
+}
+
+/// An information record that follows the metadata of a [`FanotifyEvent`].
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum FanotifyInfo<'a> {
+    /// Identifies either the object of the event or its parent directory,
+    /// for records of type `FAN_EVENT_INFO_TYPE_FID` or
+    /// `FAN_EVENT_INFO_TYPE_DFID`.
+    Fid(FanotifyFid<'a>),
+    /// Identifies a directory and the name of an entry within it, for
+    /// records of type `FAN_EVENT_INFO_TYPE_DFID_NAME`,
+    /// `FAN_EVENT_INFO_TYPE_OLD_DFID_NAME`, or
+    /// `FAN_EVENT_INFO_TYPE_NEW_DFID_NAME`.
+    FidName(FanotifyFid<'a>, &'a CStr),
+    /// A pidfd for the process that caused the event, or a negative value
+    /// if the kernel could not provide one.
+    Pidfd(linux_unsafe::int),
+    /// A record of a type that this crate does not decode, including its
+    /// header.
+    Other { info_type: u8, data: &'a [u8] },
+}
+
+/// An iterator over the information records of a [`FanotifyEvent`].
+pub struct FanotifyInfoRecords<'a> {
+    remain: &'a [u8],
+}
+
+impl<'a> Iterator for FanotifyInfoRecords<'a> {
+    type Item = FanotifyInfo<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        const HEADER_SIZE: usize = core::mem::size_of::<linux_unsafe::fanotify_event_info_header>();
+
+        if self.remain.len() < HEADER_SIZE {
+            return None;
+        }
+        let hdr = unsafe {
+            core::ptr::read_unaligned(
+                self.remain.as_ptr() as *const linux_unsafe::fanotify_event_info_header
+            )
+        };
+        let len = hdr.len as usize;
+        if len < HEADER_SIZE || len > self.remain.len() {
+            return None;
+        }
+        let data = &self.remain[..len];
+        self.remain = &self.remain[len..];
+        Some(fanotify_info_from_record(hdr.info_type, data))
+    }
+}
+
+fn fanotify_info_from_record(info_type: u8, data: &[u8]) -> FanotifyInfo<'_> {
+    const FID_SIZE: usize = core::mem::size_of::<linux_unsafe::fanotify_event_info_fid>();
+    // struct file_handle begins with a u32 length and an int type.
+    const HANDLE_HEADER_SIZE: usize = 8;
+    let other = FanotifyInfo::Other { info_type, data };
+
+    match info_type {
+        linux_unsafe::FAN_EVENT_INFO_TYPE_FID
+        | linux_unsafe::FAN_EVENT_INFO_TYPE_DFID
+        | linux_unsafe::FAN_EVENT_INFO_TYPE_DFID_NAME
+        | linux_unsafe::FAN_EVENT_INFO_TYPE_OLD_DFID_NAME
+        | linux_unsafe::FAN_EVENT_INFO_TYPE_NEW_DFID_NAME => {
+            if data.len() < FID_SIZE + HANDLE_HEADER_SIZE {
+                return other;
+            }
+            let fid = unsafe {
+                core::ptr::read_unaligned(
+                    data.as_ptr() as *const linux_unsafe::fanotify_event_info_fid
+                )
+            };
+            let handle_hdr = &data[FID_SIZE..FID_SIZE + HANDLE_HEADER_SIZE];
+            let handle_bytes = u32::from_ne_bytes(handle_hdr[0..4].try_into().unwrap()) as usize;
+            let handle_type =
+                linux_unsafe::int::from_ne_bytes(handle_hdr[4..8].try_into().unwrap());
+            let handle_start = FID_SIZE + HANDLE_HEADER_SIZE;
+            if data.len() - handle_start < handle_bytes {
+                return other;
+            }
+            let fid = FanotifyFid {
+                info_type,
+                fsid: fid.fsid,
+                handle_type,
+                handle: &data[handle_start..handle_start + handle_bytes],
+            };
+            match info_type {
+                linux_unsafe::FAN_EVENT_INFO_TYPE_FID | linux_unsafe::FAN_EVENT_INFO_TYPE_DFID => {
+                    FanotifyInfo::Fid(fid)
+                }
+                _ => match CStr::from_bytes_until_nul(&data[handle_start + handle_bytes..]) {
+                    Ok(name) => FanotifyInfo::FidName(fid, name),
+                    Err(_) => other,
+                },
+            }
+        }
+        linux_unsafe::FAN_EVENT_INFO_TYPE_PIDFD => {
+            if data.len() < core::mem::size_of::<linux_unsafe::fanotify_event_info_pidfd>() {
+                return other;
+            }
+            let raw = unsafe {
+                core::ptr::read_unaligned(
+                    data.as_ptr() as *const linux_unsafe::fanotify_event_info_pidfd
+                )
+            };
+            FanotifyInfo::Pidfd(raw.pidfd)
+        }
+        _ => other,
+    }
+}
+
+flags_type! {
+    /// Flags for [`Fanotify::new`].
+    pub struct FanotifyFlags(linux_unsafe::uint);
+
+    /// No flags, creating a notification-only group that reports file
+    /// descriptors.
+    const NONE = 0;
+    /// Set the close-on-exec flag on the new file descriptor.
+    const CLOEXEC = linux_unsafe::FAN_CLOEXEC;
+    /// Put the new file descriptor in non-blocking mode.
+    const NONBLOCK = linux_unsafe::FAN_NONBLOCK;
+    /// Create a group that can receive permission events for decisions
+    /// based on file contents.
+    const CLASS_CONTENT = linux_unsafe::FAN_CLASS_CONTENT;
+    /// Create a group that can receive permission events before file
+    /// contents are available, such as for hierarchical storage managers.
+    const CLASS_PRE_CONTENT = linux_unsafe::FAN_CLASS_PRE_CONTENT;
+    /// Remove the limit on the number of queued events.
+    const UNLIMITED_QUEUE = linux_unsafe::FAN_UNLIMITED_QUEUE;
+    /// Remove the limit on the number of marks.
+    const UNLIMITED_MARKS = linux_unsafe::FAN_UNLIMITED_MARKS;
+    /// Report thread IDs instead of process IDs.
+    const REPORT_TID = linux_unsafe::FAN_REPORT_TID;
+    /// Report a pidfd for the process that caused each event.
+    const REPORT_PIDFD = linux_unsafe::FAN_REPORT_PIDFD;
+    /// Report file identifiers instead of file descriptors.
+    const REPORT_FID = linux_unsafe::FAN_REPORT_FID;
+    /// Report the file identifier of the parent directory.
+    const REPORT_DIR_FID = linux_unsafe::FAN_REPORT_DIR_FID;
+    /// Report the name of the directory entry, along with the file
+    /// identifier of the parent directory.
+    const REPORT_DFID_NAME = linux_unsafe::FAN_REPORT_DFID_NAME;
+    /// Like [`Self::REPORT_DFID_NAME`], but also report the file identifier
+    /// of the child for directory entry events.
+    const REPORT_DFID_NAME_TARGET = linux_unsafe::FAN_REPORT_DFID_NAME_TARGET;
+}
+
+flags_type! {
+    /// Flags for [`Fanotify::mark`] and related methods.
+    ///
+    /// Each operation must include exactly one of [`Self::ADD`],
+    /// [`Self::REMOVE`], or [`Self::FLUSH`].
+    pub struct MarkFlags(linux_unsafe::uint);
+
+    /// Add the events in the mask to the mark.
+    const ADD = linux_unsafe::FAN_MARK_ADD;
+    /// Remove the events in the mask from the mark.
+    const REMOVE = linux_unsafe::FAN_MARK_REMOVE;
+    /// Remove all marks of the selected kind from the group.
+    const FLUSH = linux_unsafe::FAN_MARK_FLUSH;
+    /// Don't follow a symbolic link at the end of the path.
+    const DONT_FOLLOW = linux_unsafe::FAN_MARK_DONT_FOLLOW;
+    /// Fail unless the path is a directory.
+    const ONLYDIR = linux_unsafe::FAN_MARK_ONLYDIR;
+    /// Mark the whole mount containing the path, rather than just the
+    /// object at the path.
+    const MOUNT = linux_unsafe::FAN_MARK_MOUNT;
+    /// Mark the whole filesystem containing the path, rather than just the
+    /// object at the path.
+    const FILESYSTEM = linux_unsafe::FAN_MARK_FILESYSTEM;
+    /// Allow the kernel to evict the marked inode from its cache, which
+    /// silently removes the mark.
+    const EVICTABLE = linux_unsafe::FAN_MARK_EVICTABLE;
+    /// Treat the mask as a set of events to ignore, rather than to report.
+    const IGNORE = linux_unsafe::FAN_MARK_IGNORE;
+}
+
+flags_type! {
+    /// Event types and flags for use with [`Fanotify::mark`], and the event
+    /// types reported in each [`FanotifyEvent`].
+    pub struct FanotifyMask(u64);
+
+    /// No events.
+    const NONE = 0;
+    /// A file was accessed.
+    const ACCESS = linux_unsafe::FAN_ACCESS;
+    /// A file was modified.
+    const MODIFY = linux_unsafe::FAN_MODIFY;
+    /// Metadata changed. Requires a group that reports file identifiers.
+    const ATTRIB = linux_unsafe::FAN_ATTRIB;
+    /// A file opened for writing was closed.
+    const CLOSE_WRITE = linux_unsafe::FAN_CLOSE_WRITE;
+    /// A file not opened for writing was closed.
+    const CLOSE_NOWRITE = linux_unsafe::FAN_CLOSE_NOWRITE;
+    /// A file was opened.
+    const OPEN = linux_unsafe::FAN_OPEN;
+    /// A file was opened with the intent to execute it.
+    const OPEN_EXEC = linux_unsafe::FAN_OPEN_EXEC;
+    /// A file was renamed out of a marked directory. Requires a group that
+    /// reports file identifiers.
+    const MOVED_FROM = linux_unsafe::FAN_MOVED_FROM;
+    /// A file was renamed into a marked directory. Requires a group that
+    /// reports file identifiers.
+    const MOVED_TO = linux_unsafe::FAN_MOVED_TO;
+    /// A file was renamed within or between marked directories, reported
+    /// with both the old and new names. Requires a group that reports
+    /// directory file identifiers and names.
+    const RENAME = linux_unsafe::FAN_RENAME;
+    /// A file was created in a marked directory. Requires a group that
+    /// reports file identifiers.
+    const CREATE = linux_unsafe::FAN_CREATE;
+    /// A file was deleted from a marked directory. Requires a group that
+    /// reports file identifiers.
+    const DELETE = linux_unsafe::FAN_DELETE;
+    /// The marked object itself was deleted. Requires a group that reports
+    /// file identifiers.
+    const DELETE_SELF = linux_unsafe::FAN_DELETE_SELF;
+    /// The marked object itself was moved. Requires a group that reports
+    /// file identifiers.
+    const MOVE_SELF = linux_unsafe::FAN_MOVE_SELF;
+    /// Permission event: a process wants to open a file.
+    const OPEN_PERM = linux_unsafe::FAN_OPEN_PERM;
+    /// Permission event: a process wants to open a file for execution.
+    const OPEN_EXEC_PERM = linux_unsafe::FAN_OPEN_EXEC_PERM;
+    /// Permission event: a process wants to read from a file.
+    const ACCESS_PERM = linux_unsafe::FAN_ACCESS_PERM;
+    /// A filesystem error was detected. Requires a filesystem mark.
+    const FS_ERROR = linux_unsafe::FAN_FS_ERROR;
+    /// Both [`Self::CLOSE_WRITE`] and [`Self::CLOSE_NOWRITE`].
+    const CLOSE = linux_unsafe::FAN_CLOSE;
+    /// Both [`Self::MOVED_FROM`] and [`Self::MOVED_TO`].
+    const MOVE = linux_unsafe::FAN_MOVE;
+
+    /// Mark flag: also report events for the immediate children of a
+    /// marked directory.
+    const EVENT_ON_CHILD = linux_unsafe::FAN_EVENT_ON_CHILD;
+    /// Mark flag: also report events whose subject is a directory. Also
+    /// reported on such events.
+    const ONDIR = linux_unsafe::FAN_ONDIR;
+    /// Reported flag: the event queue overflowed, and so some events were
+    /// lost.
+    const Q_OVERFLOW = linux_unsafe::FAN_Q_OVERFLOW;
+}
+
+/// A response to a fanotify permission event, for use with
+/// [`Fanotify::respond`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct FanotifyResponse(u32);
+
+impl FanotifyResponse {
+    /// Allow the operation.
+    pub const ALLOW: Self = Self(linux_unsafe::FAN_ALLOW);
+    /// Deny the operation, making it fail with `EPERM`.
+    pub const DENY: Self = Self(linux_unsafe::FAN_DENY);
+
+    /// Returns a response that also asks the kernel to record the decision
+    /// in the audit log. Requires a group created with `FAN_ENABLE_AUDIT`.
+    #[inline(always)]
+    pub const fn with_audit(self) -> Self {
+        Self(self.0 | linux_unsafe::FAN_AUDIT)
+    }
+
+    /// Returns the raw response value.
+    #[inline(always)]
+    pub const fn raw(self) -> u32 {
+        self.0
+    }
+}
@@ -17,6 +17,18 @@ pub mod sockopt;
 mod direntry;
 pub use direntry::*;
 
+mod iov;
+pub use iov::*;
+
+mod lock;
+pub use lock::*;
+
+mod metadata;
+pub use metadata::*;
+
+mod splice;
+pub use splice::*;
+
 /// An encapsulated Linux file descriptor.
 ///
 /// The methods of `File` are largely just thin wrappers around Linux system
@@ -105,6 +117,21 @@ impl File<()> {
             .map_err(|e| e.into())
     }
 
+    /// Create a new anonymous memory-backed file using the `memfd_create`
+    /// system call.
+    ///
+    /// The name is used only for debugging purposes, such as in the target
+    /// of the symlink in `/proc/self/fd`. `flags` is a combination of the
+    /// `MFD_*` constants from [`linux_unsafe`].
+    #[inline]
+    pub fn memfd_create(name: &CStr, flags: linux_unsafe::uint) -> Result<Self> {
+        let name_raw = name.as_ptr() as *const linux_unsafe::char;
+        let result = unsafe { linux_unsafe::memfd_create(name_raw, flags) };
+        result
+            .map(|fd| unsafe { Self::from_raw_fd(fd as linux_unsafe::int) })
+            .map_err(|e| e.into())
+    }
+
     /// Create a new socket using the `socket` system call.
     ///
     /// The protocol is specifed as a special typed constant which carries
@@ -291,6 +318,52 @@ impl<Device> File<Device> {
         result.map(|v| v as _).map_err(|e| e.into())
     }
 
+    /// Read bytes from the file at the given offset into the given buffer,
+    /// without changing the file's current read/write position.
+    ///
+    /// This allows multiple readers to share a single file descriptor without
+    /// coordinating on its current position.
+    #[inline]
+    pub fn read_at(&self, buf: &mut [u8], offset: u64) -> Result<usize> {
+        let offset = raw_offset(offset)?;
+        let buf_ptr = buf.as_mut_ptr() as *mut linux_unsafe::void;
+        let result = unsafe { linux_unsafe::pread64(self.fd, buf_ptr, buf.len(), offset) };
+        result.map(|v| v as _).map_err(|e| e.into())
+    }
+
+    /// Read bytes from the file into multiple buffers in turn, using the
+    /// `readv` system call.
+    #[inline]
+    pub fn read_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        let count = raw_iov_count(bufs.len())?;
+        let iov = IoSliceMut::as_raw_mut_ptr(bufs);
+        let result = unsafe { linux_unsafe::readv(self.fd, iov, count) };
+        result.map(|v| v as _).map_err(|e| e.into())
+    }
+
+    /// Read bytes from the file into multiple buffers in turn, with
+    /// additional flags, using the `preadv2` system call.
+    ///
+    /// If `offset` is `None` then this reads from and updates the file's
+    /// current read/write position, and otherwise it reads from the given
+    /// offset without changing the current position.
+    #[inline]
+    pub fn read_vectored_at(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+        offset: Option<u64>,
+        flags: RwFlags,
+    ) -> Result<usize> {
+        let offset = match offset {
+            Some(offset) => raw_offset(offset)?,
+            None => -1,
+        };
+        let count = raw_iov_count(bufs.len())?;
+        let iov = IoSliceMut::as_raw_mut_ptr(bufs);
+        let result = unsafe { linux_unsafe::preadv2(self.fd, iov, count, offset, flags.raw()) };
+        result.map(|v| v as _).map_err(|e| e.into())
+    }
+
     /// Read some directory entries from the directory into the given buffer,
     /// and obtain an iterator over those directory entries.
     ///
@@ -411,6 +484,180 @@ impl<Device> File<Device> {
         }
     }
 
+    /// Retrieve metadata about the file using the `statx` system call.
+    #[inline]
+    pub fn metadata(&self) -> Result<Metadata> {
+        self.metadata_relative_raw(c"", linux_unsafe::AT_EMPTY_PATH, METADATA_DEFAULT_MASK)
+    }
+
+    /// Retrieve metadata about a file relative to the file, which must
+    /// represent a directory, using the `statx` system call.
+    ///
+    /// `flags` can include `AT_SYMLINK_NOFOLLOW` to retrieve metadata about
+    /// a symbolic link itself, and the `AT_STATX_*` synchronization flags.
+    #[inline]
+    pub fn metadata_relative(&self, path: &CStr, flags: linux_unsafe::int) -> Result<Metadata> {
+        self.metadata_relative_raw(path, flags, METADATA_DEFAULT_MASK)
+    }
+
+    /// Retrieve metadata about a file relative to the file using the `statx`
+    /// system call, requesting only the fields represented by the given
+    /// `STATX_*` mask.
+    #[inline]
+    pub fn metadata_relative_raw(
+        &self,
+        path: &CStr,
+        flags: linux_unsafe::int,
+        mask: u32,
+    ) -> Result<Metadata> {
+        let path_raw = path.as_ptr() as *const linux_unsafe::char;
+        let mut buf = MaybeUninit::<linux_unsafe::statx>::zeroed();
+        let result = unsafe {
+            linux_unsafe::statx(
+                self.fd,
+                path_raw,
+                flags,
+                mask as linux_unsafe::uint,
+                buf.as_mut_ptr(),
+            )
+        };
+        result
+            .map(|_| Metadata::from_raw(unsafe { buf.assume_init() }))
+            .map_err(|e| e.into())
+    }
+
+    /// Create a new directory relative to the file, which must represent
+    /// a directory.
+    #[inline]
+    pub fn mkdir_relative(&self, path: &CStr, mode: linux_unsafe::mode_t) -> Result<()> {
+        let path_raw = path.as_ptr() as *const linux_unsafe::char;
+        let result = unsafe { linux_unsafe::mkdirat(self.fd, path_raw, mode) };
+        result.map(|_| ()).map_err(|e| e.into())
+    }
+
+    /// Create a filesystem node, such as a named pipe or device special file,
+    /// relative to the file, which must represent a directory.
+    ///
+    /// `mode` must include one of the `S_IF*` file type constants from
+    /// [`linux_unsafe`]. `dev` is used only for device special files, and
+    /// can be constructed using [`linux_unsafe::makedev`].
+    #[inline]
+    pub fn mknod_relative(
+        &self,
+        path: &CStr,
+        mode: linux_unsafe::mode_t,
+        dev: linux_unsafe::dev_t,
+    ) -> Result<()> {
+        let path_raw = path.as_ptr() as *const linux_unsafe::char;
+        let result = unsafe { linux_unsafe::mknodat(self.fd, path_raw, mode, dev) };
+        result.map(|_| ()).map_err(|e| e.into())
+    }
+
+    /// Remove a non-directory file relative to the file, which must represent
+    /// a directory.
+    #[inline]
+    pub fn unlink_relative(&self, path: &CStr) -> Result<()> {
+        self.unlink_relative_raw(path, 0)
+    }
+
+    /// Remove an empty directory relative to the file, which must represent
+    /// a directory.
+    #[inline]
+    pub fn rmdir_relative(&self, path: &CStr) -> Result<()> {
+        self.unlink_relative_raw(path, linux_unsafe::AT_REMOVEDIR)
+    }
+
+    /// Remove a directory entry using the `unlinkat` system call.
+    ///
+    /// This function exposes the raw `flags` argument from the underlying
+    /// system call, which the caller must populate appropriately.
+    #[inline]
+    pub fn unlink_relative_raw(&self, path: &CStr, flags: linux_unsafe::int) -> Result<()> {
+        let path_raw = path.as_ptr() as *const linux_unsafe::char;
+        let result = unsafe { linux_unsafe::unlinkat(self.fd, path_raw, flags) };
+        result.map(|_| ()).map_err(|e| e.into())
+    }
+
+    /// Rename a file relative to the file, which must represent a directory,
+    /// to a new path relative to another directory, which may be the same.
+    ///
+    /// `flags` is a combination of [`linux_unsafe::RENAME_NOREPLACE`],
+    /// [`linux_unsafe::RENAME_EXCHANGE`] and [`linux_unsafe::RENAME_WHITEOUT`],
+    /// or zero to replace any existing file at the new path.
+    #[inline]
+    pub fn rename_relative<NewDevice>(
+        &self,
+        old_path: &CStr,
+        new_dir: &File<NewDevice>,
+        new_path: &CStr,
+        flags: linux_unsafe::uint,
+    ) -> Result<()> {
+        let old_raw = old_path.as_ptr() as *const linux_unsafe::char;
+        let new_raw = new_path.as_ptr() as *const linux_unsafe::char;
+        let result =
+            unsafe { linux_unsafe::renameat2(self.fd, old_raw, new_dir.fd, new_raw, flags) };
+        result.map(|_| ()).map_err(|e| e.into())
+    }
+
+    /// Create a symbolic link relative to the file, which must represent
+    /// a directory.
+    ///
+    /// The new link at `link_path` will refer to `target`, which the kernel
+    /// does not interpret until the link is followed.
+    #[inline]
+    pub fn symlink_relative(&self, target: &CStr, link_path: &CStr) -> Result<()> {
+        let target_raw = target.as_ptr() as *const linux_unsafe::char;
+        let link_raw = link_path.as_ptr() as *const linux_unsafe::char;
+        let result = unsafe { linux_unsafe::symlinkat(target_raw, self.fd, link_raw) };
+        result.map(|_| ()).map_err(|e| e.into())
+    }
+
+    /// Change the permissions of a file relative to the file, which must
+    /// represent a directory.
+    ///
+    /// `flags` can include `AT_SYMLINK_NOFOLLOW` and `AT_EMPTY_PATH`, but
+    /// nonzero flags require the `fchmodat2` system call from Linux 6.6 or
+    /// later.
+    #[inline]
+    pub fn chmod_relative(
+        &self,
+        path: &CStr,
+        mode: linux_unsafe::mode_t,
+        flags: linux_unsafe::int,
+    ) -> Result<()> {
+        let path_raw = path.as_ptr() as *const linux_unsafe::char;
+        let result = if flags == 0 {
+            unsafe { linux_unsafe::fchmodat(self.fd, path_raw, mode) }
+        } else {
+            unsafe { linux_unsafe::fchmodat2(self.fd, path_raw, mode, flags) }
+        };
+        result.map(|_| ()).map_err(|e| e.into())
+    }
+
+    /// Change the access and modification timestamps of a file relative
+    /// to the file, which must represent a directory.
+    ///
+    /// `times` gives the access and modification times respectively, or
+    /// `None` to set both to the current time. The `tv_nsec` field of
+    /// either can be [`linux_unsafe::UTIME_NOW`] or [`linux_unsafe::UTIME_OMIT`]
+    /// to set that timestamp to the current time or to leave it unchanged.
+    /// `flags` can include `AT_SYMLINK_NOFOLLOW` and `AT_EMPTY_PATH`.
+    #[inline]
+    pub fn set_times_relative(
+        &self,
+        path: &CStr,
+        times: Option<&[linux_unsafe::timespec; 2]>,
+        flags: linux_unsafe::int,
+    ) -> Result<()> {
+        let path_raw = path.as_ptr() as *const linux_unsafe::char;
+        let times_raw = match times {
+            Some(times) => times.as_ptr(),
+            None => core::ptr::null(),
+        };
+        let result = unsafe { linux_unsafe::utimensat(self.fd, path_raw, times_raw, flags) };
+        result.map(|_| ()).map_err(|e| e.into())
+    }
+
     /// Change the current read/write position of the file.
     #[inline]
     pub fn seek(&self, pos: impl Into<SeekFrom>) -> Result<u64> {
@@ -479,6 +726,105 @@ impl<Device> File<Device> {
         result.map(|v| v as _).map_err(|e| e.into())
     }
 
+    /// Write bytes from the given buffer to the file at the given offset,
+    /// without changing the file's current read/write position.
+    ///
+    /// If the file was opened with `O_APPEND` then Linux appends the data to
+    /// the end of the file regardless of the given offset.
+    #[inline]
+    pub fn write_at(&self, buf: &[u8], offset: u64) -> Result<usize> {
+        let offset = raw_offset(offset)?;
+        let buf_ptr = buf.as_ptr() as *const linux_unsafe::void;
+        let result = unsafe { linux_unsafe::pwrite64(self.fd, buf_ptr, buf.len(), offset) };
+        result.map(|v| v as _).map_err(|e| e.into())
+    }
+
+    /// Write bytes from multiple buffers in turn to the file, using the
+    /// `writev` system call.
+    #[inline]
+    pub fn write_vectored(&self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        let count = raw_iov_count(bufs.len())?;
+        let iov = IoSlice::as_raw_ptr(bufs);
+        let result = unsafe { linux_unsafe::writev(self.fd, iov, count) };
+        result.map(|v| v as _).map_err(|e| e.into())
+    }
+
+    /// Write bytes from multiple buffers in turn to the file, with
+    /// additional flags, using the `pwritev2` system call.
+    ///
+    /// If `offset` is `None` then this writes at and updates the file's
+    /// current read/write position, and otherwise it writes at the given
+    /// offset without changing the current position.
+    #[inline]
+    pub fn write_vectored_at(
+        &self,
+        bufs: &[IoSlice<'_>],
+        offset: Option<u64>,
+        flags: RwFlags,
+    ) -> Result<usize> {
+        let offset = match offset {
+            Some(offset) => raw_offset(offset)?,
+            None => -1,
+        };
+        let count = raw_iov_count(bufs.len())?;
+        let iov = IoSlice::as_raw_ptr(bufs);
+        let result = unsafe { linux_unsafe::pwritev2(self.fd, iov, count, offset, flags.raw()) };
+        result.map(|v| v as _).map_err(|e| e.into())
+    }
+
+    /// Copy up to `len` bytes from this file to another file using the
+    /// `copy_file_range` system call, without passing the data through
+    /// userspace.
+    ///
+    /// If an offset is `None` then the corresponding file's current
+    /// read/write position is used and updated. Otherwise the given offset is
+    /// used and updated, and the file's current position is unchanged.
+    #[inline]
+    pub fn copy_file_range_to<OutDevice>(
+        &self,
+        off_in: Option<&mut u64>,
+        out: &File<OutDevice>,
+        off_out: Option<&mut u64>,
+        len: usize,
+    ) -> Result<usize> {
+        let result = unsafe {
+            linux_unsafe::copy_file_range(
+                self.fd,
+                raw_offset_ptr(off_in),
+                out.fd,
+                raw_offset_ptr(off_out),
+                len,
+                0,
+            )
+        };
+        result.map(|v| v as _).map_err(|e| e.into())
+    }
+
+    /// Copy up to `count` bytes from this file to another file using the
+    /// `sendfile` system call, without passing the data through userspace.
+    ///
+    /// If `offset` is `None` then this file's current read/write position is
+    /// used and updated. Otherwise the given offset is used and updated, and
+    /// the file's current position is unchanged.
+    #[inline]
+    pub fn sendfile_to<OutDevice>(
+        &self,
+        out: &File<OutDevice>,
+        offset: Option<&mut u64>,
+        count: usize,
+    ) -> Result<usize> {
+        #[cfg(not(target_pointer_width = "32"))]
+        let result =
+            unsafe { linux_unsafe::sendfile(out.fd, self.fd, raw_offset_ptr(offset), count) };
+        #[cfg(target_pointer_width = "32")]
+        let result = unsafe {
+            // On 32-bit platforms sendfile has only a 32-bit offset, so
+            // we use sendfile64 instead.
+            linux_unsafe::sendfile64(out.fd, self.fd, raw_offset_ptr(offset), count)
+        };
+        result.map(|v| v as _).map_err(|e| e.into())
+    }
+
     /// Safe wrapper for the `fcntl` system call.
     ///
     /// The safety of this wrapper relies on being passed only correct
@@ -593,6 +939,362 @@ impl<Device> File<Device> {
         result.map(|_| ()).map_err(|e| e.into())
     }
 
+    /// Accept a connection on a listening socket, returning a new socket
+    /// for the connection along with the address of its peer.
+    ///
+    /// The new socket has the same device type as `self`. The peer address
+    /// is decoded as `Addr`, such as [`crate::socket::ip::SockAddrIp`], or
+    /// is `None` if it belongs to a family that `Addr` cannot represent,
+    /// in which case the connection is still returned for the caller to use
+    /// or close.
+    #[inline]
+    pub fn accept<Addr: crate::socket::SockAddrFromRaw>(
+        &self,
+        flags: crate::socket::SocketFlags,
+    ) -> Result<(File<Device>, Option<Addr>)> {
+        let mut buf: MaybeUninit<linux_unsafe::sockaddr_storage> = MaybeUninit::zeroed();
+        let mut len =
+            core::mem::size_of::<linux_unsafe::sockaddr_storage>() as linux_unsafe::socklen_t;
+        let file = unsafe {
+            self.accept_raw(
+                buf.as_mut_ptr() as *mut linux_unsafe::sockaddr,
+                &mut len,
+                flags.raw(),
+            )
+        }?;
+        let raw = unsafe { buf.assume_init_ref() };
+        let addr = Addr::from_sockaddr_raw(raw, len);
+        Ok((file, addr))
+    }
+
+    /// Accept a connection on a listening socket using the `accept4` system
+    /// call, writing the peer address to a raw pointer.
+    ///
+    /// `addrlen` must initially contain the size of the buffer at `addr`,
+    /// and on return contains the size of the peer address, which might be
+    /// larger than the buffer if the address was truncated. Both pointers
+    /// can be null to discard the peer address.
+    #[inline]
+    pub unsafe fn accept_raw(
+        &self,
+        addr: *mut linux_unsafe::sockaddr,
+        addrlen: *mut linux_unsafe::socklen_t,
+        flags: linux_unsafe::int,
+    ) -> Result<File<Device>> {
+        let result = unsafe { linux_unsafe::accept4(self.fd, addr, addrlen, flags) };
+        result
+            .map(|fd| unsafe { File::from_raw_fd(fd) })
+            .map_err(|e| e.into())
+    }
+
+    /// Returns the address that the socket is bound to, for an IPv4 or IPv6
+    /// socket.
+    #[inline]
+    pub fn local_addr(&self) -> Result<crate::socket::ip::SockAddrIp> {
+        self.local_addr_as()
+    }
+
+    /// Returns the address that the socket is bound to, decoded as `Addr`.
+    ///
+    /// Fails with `EAFNOSUPPORT` if the address belongs to a family that
+    /// `Addr` cannot represent.
+    #[inline]
+    pub fn local_addr_as<Addr: crate::socket::SockAddrFromRaw>(&self) -> Result<Addr> {
+        self.get_addr(linux_unsafe::getsockname)
+    }
+
+    /// Returns the address of the peer that the socket is connected to, for
+    /// an IPv4 or IPv6 socket.
+    #[inline]
+    pub fn peer_addr(&self) -> Result<crate::socket::ip::SockAddrIp> {
+        self.peer_addr_as()
+    }
+
+    /// Returns the address of the peer that the socket is connected to,
+    /// decoded as `Addr`.
+    ///
+    /// Fails with `EAFNOSUPPORT` if the address belongs to a family that
+    /// `Addr` cannot represent.
+    #[inline]
+    pub fn peer_addr_as<Addr: crate::socket::SockAddrFromRaw>(&self) -> Result<Addr> {
+        self.get_addr(linux_unsafe::getpeername)
+    }
+
+    #[inline(always)]
+    fn get_addr<Addr: crate::socket::SockAddrFromRaw>(
+        &self,
+        f: unsafe fn(
+            linux_unsafe::int,
+            *mut linux_unsafe::sockaddr,
+            *mut linux_unsafe::socklen_t,
+        ) -> linux_unsafe::result::Result<linux_unsafe::int>,
+    ) -> Result<Addr> {
+        let mut buf: MaybeUninit<linux_unsafe::sockaddr_storage> = MaybeUninit::zeroed();
+        let mut len =
+            core::mem::size_of::<linux_unsafe::sockaddr_storage>() as linux_unsafe::socklen_t;
+        let result = unsafe {
+            f(
+                self.fd,
+                buf.as_mut_ptr() as *mut linux_unsafe::sockaddr,
+                &mut len,
+            )
+        };
+        result.map_err(crate::result::Error::from)?;
+        let raw = unsafe { buf.assume_init_ref() };
+        Addr::from_sockaddr_raw(raw, len).ok_or(crate::result::EAFNOSUPPORT)
+    }
+
+    /// Shut down part or all of a full-duplex socket connection.
+    ///
+    /// Unlike closing the file, this affects all file descriptors that
+    /// refer to the same socket.
+    #[inline]
+    pub fn shutdown(&self, how: crate::socket::Shutdown) -> Result<()> {
+        let result = unsafe { linux_unsafe::shutdown(self.fd, how.raw()) };
+        result.map(|_| ()).map_err(|e| e.into())
+    }
+
+    /// Send a datagram containing the bytes of `buf` to the given address,
+    /// using the `sendto` system call.
+    ///
+    /// Returns the number of bytes sent.
+    #[inline]
+    pub fn send_to(
+        &self,
+        buf: &[u8],
+        addr: impl crate::socket::SockAddr,
+        flags: crate::socket::msg::MsgFlags,
+    ) -> Result<usize> {
+        let (raw_ptr, raw_len) = unsafe { addr.sockaddr_raw_const() };
+        unsafe {
+            self.sendto_raw(
+                buf.as_ptr() as *const void,
+                buf.len(),
+                flags.raw(),
+                raw_ptr,
+                raw_len,
+            )
+        }
+    }
+
+    /// Send data on a socket using the `sendto` system call with raw
+    /// pointers.
+    ///
+    /// `addr` can be null with `addrlen` zero for a connected socket.
+    #[inline]
+    pub unsafe fn sendto_raw(
+        &self,
+        buf: *const void,
+        len: usize,
+        flags: linux_unsafe::int,
+        addr: *const linux_unsafe::sockaddr,
+        addrlen: linux_unsafe::socklen_t,
+    ) -> Result<usize> {
+        let result = unsafe { linux_unsafe::sendto(self.fd, buf, len, flags, addr, addrlen) };
+        result.map(|v| v as _).map_err(|e| e.into())
+    }
+
+    /// Receive a datagram into `buf`, returning the number of bytes received
+    /// along with the sender's address, for an IPv4 or IPv6 socket.
+    ///
+    /// If the datagram is larger than `buf` then the remainder is discarded,
+    /// unless `flags` includes
+    /// [`MsgFlags::TRUNC`](crate::socket::msg::MsgFlags::TRUNC), in which
+    /// case the result is the full length of the datagram.
+    ///
+    /// The sender's address is `None` if it isn't an IP address, as
+    /// described for [`Self::recv_from_as`].
+    #[inline]
+    pub fn recv_from(
+        &self,
+        buf: &mut [u8],
+        flags: crate::socket::msg::MsgFlags,
+    ) -> Result<(usize, Option<crate::socket::ip::SockAddrIp>)> {
+        self.recv_from_as(buf, flags)
+    }
+
+    /// Receive a datagram into `buf`, returning the number of bytes received
+    /// along with the sender's address decoded as `Addr`.
+    ///
+    /// The sender's address is `None` if the kernel reports no address or
+    /// one that belongs to a family that `Addr` cannot represent. The
+    /// datagram is still received in that case.
+    #[inline]
+    pub fn recv_from_as<Addr: crate::socket::SockAddrFromRaw>(
+        &self,
+        buf: &mut [u8],
+        flags: crate::socket::msg::MsgFlags,
+    ) -> Result<(usize, Option<Addr>)> {
+        let mut addr: MaybeUninit<linux_unsafe::sockaddr_storage> = MaybeUninit::zeroed();
+        let mut len =
+            core::mem::size_of::<linux_unsafe::sockaddr_storage>() as linux_unsafe::socklen_t;
+        let count = unsafe {
+            self.recvfrom_raw(
+                buf.as_mut_ptr() as *mut void,
+                buf.len(),
+                flags.raw(),
+                addr.as_mut_ptr() as *mut linux_unsafe::sockaddr,
+                &mut len,
+            )
+        }?;
+        let raw = unsafe { addr.assume_init_ref() };
+        let addr = Addr::from_sockaddr_raw(raw, len);
+        Ok((count, addr))
+    }
+
+    /// Receive data from a socket using the `recvfrom` system call with raw
+    /// pointers.
+    ///
+    /// `addrlen` must initially contain the size of the buffer at `addr`,
+    /// and on return contains the size of the sender's address. Both
+    /// pointers can be null to discard the sender's address.
+    #[inline]
+    pub unsafe fn recvfrom_raw(
+        &self,
+        buf: *mut void,
+        len: usize,
+        flags: linux_unsafe::int,
+        addr: *mut linux_unsafe::sockaddr,
+        addrlen: *mut linux_unsafe::socklen_t,
+    ) -> Result<usize> {
+        let result = unsafe { linux_unsafe::recvfrom(self.fd, buf, len, flags, addr, addrlen) };
+        result.map(|v| v as _).map_err(|e| e.into())
+    }
+
+    /// Send a message on a connected socket, gathering the data from
+    /// multiple buffers and including the given ancillary data, using the
+    /// `sendmsg` system call.
+    ///
+    /// Build `control` using [`crate::socket::msg::ControlMessageWriter`],
+    /// or pass an empty slice to send no ancillary data. Returns the number
+    /// of bytes of data sent.
+    #[inline]
+    pub fn send_msg(
+        &self,
+        bufs: &[IoSlice<'_>],
+        control: &[u8],
+        flags: crate::socket::msg::MsgFlags,
+    ) -> Result<usize> {
+        self.send_msg_inner(core::ptr::null(), 0, bufs, control, flags)
+    }
+
+    /// Send a message to the given address, gathering the data from
+    /// multiple buffers and including the given ancillary data, using the
+    /// `sendmsg` system call.
+    #[inline]
+    pub fn send_msg_to(
+        &self,
+        addr: impl crate::socket::SockAddr,
+        bufs: &[IoSlice<'_>],
+        control: &[u8],
+        flags: crate::socket::msg::MsgFlags,
+    ) -> Result<usize> {
+        let (raw_ptr, raw_len) = unsafe { addr.sockaddr_raw_const() };
+        self.send_msg_inner(raw_ptr, raw_len, bufs, control, flags)
+    }
+
+    #[inline(always)]
+    fn send_msg_inner(
+        &self,
+        addr: *const linux_unsafe::sockaddr,
+        addrlen: linux_unsafe::socklen_t,
+        bufs: &[IoSlice<'_>],
+        control: &[u8],
+        flags: crate::socket::msg::MsgFlags,
+    ) -> Result<usize> {
+        let msg = linux_unsafe::msghdr {
+            msg_name: addr as *mut void,
+            msg_namelen: addrlen,
+            msg_iov: IoSlice::as_raw_ptr(bufs) as *mut linux_unsafe::iovec,
+            msg_iovlen: bufs.len(),
+            msg_control: if control.is_empty() {
+                core::ptr::null_mut()
+            } else {
+                control.as_ptr() as *mut void
+            },
+            msg_controllen: control.len(),
+            msg_flags: 0,
+        };
+        unsafe { self.sendmsg_raw(&msg, flags.raw()) }
+    }
+
+    /// Send a message on a socket using a raw `msghdr`.
+    #[inline]
+    pub unsafe fn sendmsg_raw(
+        &self,
+        msg: *const linux_unsafe::msghdr,
+        flags: linux_unsafe::int,
+    ) -> Result<usize> {
+        let result = unsafe { linux_unsafe::sendmsg(self.fd, msg, flags) };
+        result.map(|v| v as _).map_err(|e| e.into())
+    }
+
+    /// Receive a message from a socket, scattering the data into multiple
+    /// buffers and collecting any ancillary data into `control`, using the
+    /// `recvmsg` system call.
+    ///
+    /// The result reports the amount of data received and the sender's
+    /// address, and allows iterating over the received ancillary data.
+    /// Ancillary data that doesn't fit in `control` is discarded, and the
+    /// result's flags then include
+    /// [`MsgFlags::CTRUNC`](crate::socket::msg::MsgFlags::CTRUNC). Any files
+    /// received as `SCM_RIGHTS` ancillary data are owned by the result, and
+    /// are closed when it's dropped unless the caller takes them.
+    #[inline]
+    pub fn recv_msg<'c>(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+        control: &'c mut [u8],
+        flags: crate::socket::msg::MsgFlags,
+    ) -> Result<crate::socket::msg::RecvMsg<'c>> {
+        let control = crate::socket::msg::align_buffer(control);
+        let mut addr: MaybeUninit<linux_unsafe::sockaddr_storage> = MaybeUninit::zeroed();
+        let mut msg = linux_unsafe::msghdr {
+            msg_name: addr.as_mut_ptr() as *mut void,
+            msg_namelen: core::mem::size_of::<linux_unsafe::sockaddr_storage>()
+                as linux_unsafe::socklen_t,
+            msg_iov: IoSliceMut::as_raw_mut_ptr(bufs),
+            msg_iovlen: bufs.len(),
+            msg_control: if control.is_empty() {
+                core::ptr::null_mut()
+            } else {
+                control.as_mut_ptr() as *mut void
+            },
+            msg_controllen: control.len(),
+            msg_flags: 0,
+        };
+        let len = unsafe { self.recvmsg_raw(&mut msg, flags.raw()) }?;
+        let control_len = if msg.msg_controllen < control.len() {
+            msg.msg_controllen
+        } else {
+            control.len()
+        };
+        Ok(crate::socket::msg::RecvMsg {
+            len,
+            flags: crate::socket::msg::MsgFlags::from_raw(msg.msg_flags),
+            addr: unsafe { addr.assume_init() },
+            addr_len: msg.msg_namelen,
+            // Safe because the kernel just populated this ancillary data.
+            control: unsafe {
+                crate::socket::msg::ControlMessages::from_received(&mut control[..control_len])
+            },
+        })
+    }
+
+    /// Receive a message from a socket using a raw `msghdr`.
+    ///
+    /// The caller is responsible for closing any files received as
+    /// `SCM_RIGHTS` ancillary data.
+    #[inline]
+    pub unsafe fn recvmsg_raw(
+        &self,
+        msg: *mut linux_unsafe::msghdr,
+        flags: linux_unsafe::int,
+    ) -> Result<usize> {
+        let result = unsafe { linux_unsafe::recvmsg(self.fd, msg, flags) };
+        result.map(|v| v as _).map_err(|e| e.into())
+    }
+
     /// Get a socket option for a file descriptor representing a socket.
     ///
     /// The value for `opt` is typically a constant defined elsewhere in this
@@ -687,6 +1389,36 @@ impl<Device> File<Device> {
     }
 }
 
+/// Converts a file offset to the signed type the kernel expects, rejecting
+/// offsets that the kernel would misinterpret as negative.
+#[inline(always)]
+fn raw_offset(offset: u64) -> Result<linux_unsafe::loff_t> {
+    if offset > (linux_unsafe::loff_t::MAX as u64) {
+        return Err(result::EINVAL);
+    }
+    Ok(offset as linux_unsafe::loff_t)
+}
+
+/// Converts an optional mutable file offset into the pointer form expected
+/// by system calls that can update an offset in place.
+#[inline(always)]
+pub(crate) fn raw_offset_ptr(offset: Option<&mut u64>) -> *mut linux_unsafe::loff_t {
+    match offset {
+        Some(offset) => offset as *mut u64 as *mut linux_unsafe::loff_t,
+        None => core::ptr::null_mut(),
+    }
+}
+
+/// Converts a number of buffers to the type the kernel expects for the
+/// `iovcnt` argument of the vectored I/O system calls.
+#[inline(always)]
+fn raw_iov_count(count: usize) -> Result<linux_unsafe::int> {
+    if count > (linux_unsafe::int::MAX as usize) {
+        return Err(result::EINVAL);
+    }
+    Ok(count as linux_unsafe::int)
+}
+
 /// Files that have been marked as representing a particular device type using
 /// [`File::to_device`] can support `ioctl` requests that are designated for
 /// that device.
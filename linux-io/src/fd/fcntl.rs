@@ -16,11 +16,11 @@ use linux_unsafe::int;
 /// available file descriptor greater than or equal to `arg`.
 pub const F_DUPFD: DirectFcntlCmd<int, super::File> = unsafe { fcntl_cmd(0) };
 
-/// Retrieve the function descriptor flags.
-pub const F_GETFD: DirectFcntlCmd<(), int> = unsafe { fcntl_cmd(1) };
+/// Retrieve the file descriptor flags.
+pub const F_GETFD: DirectFcntlCmd<(), FdFlags> = unsafe { fcntl_cmd(1) };
 
-/// Set the function descriptor flags.
-pub const F_SETFD: DirectFcntlCmd<int, ()> = unsafe { fcntl_cmd(2) };
+/// Set the file descriptor flags.
+pub const F_SETFD: DirectFcntlCmd<FdFlags, ()> = unsafe { fcntl_cmd(2) };
 
 /// Retrieve the file access mode and the file status flags.
 pub const F_GETFL: DirectFcntlCmd<(), int> = unsafe { fcntl_cmd(3) };
@@ -38,6 +38,41 @@ pub const F_SETLK: MutPtrFcntlCmd<linux_unsafe::flock, ()> = unsafe { fcntl_cmd_
 /// lock to be released.
 pub const F_SETLKW: MutPtrFcntlCmd<linux_unsafe::flock, ()> = unsafe { fcntl_cmd_mut_ptr(7) };
 
+/// Set the process or process group that will receive `SIGIO` and `SIGURG`
+/// signals for events on the file.
+///
+/// A positive value is a process ID, while a negative value is the negation
+/// of a process group ID.
+pub const F_SETOWN: DirectFcntlCmd<int, ()> = unsafe { fcntl_cmd(8) };
+
+/// Retrieve the process or process group that will receive `SIGIO` and
+/// `SIGURG` signals for events on the file, using the same representation
+/// as [`F_SETOWN`].
+///
+/// The kernel returns the negated process group ID through the same return
+/// value it uses for errors, so a process group whose ID is less than 4096
+/// is indistinguishable from an error and will be reported as one. Use
+/// [`F_GETOWN_EX`] instead to retrieve the owner unambiguously.
+pub const F_GETOWN: DirectFcntlCmd<(), int> = unsafe { fcntl_cmd(9) };
+
+/// Set the signal to send instead of `SIGIO` when input or output becomes
+/// possible on the file. Zero restores the default of `SIGIO`.
+pub const F_SETSIG: DirectFcntlCmd<int, ()> = unsafe { fcntl_cmd(10) };
+
+/// Retrieve the signal sent when input or output becomes possible on the
+/// file, or zero if the default of `SIGIO` is in use.
+pub const F_GETSIG: DirectFcntlCmd<(), int> = unsafe { fcntl_cmd(11) };
+
+/// Set the thread, process, or process group that will receive signals for
+/// events on the file.
+pub const F_SETOWN_EX: ConstPtrFcntlCmd<linux_unsafe::f_owner_ex, ()> =
+    unsafe { fcntl_cmd_const_ptr(15) };
+
+/// Retrieve the thread, process, or process group that will receive signals
+/// for events on the file.
+pub const F_GETOWN_EX: MutPtrFcntlCmd<linux_unsafe::f_owner_ex, ()> =
+    unsafe { fcntl_cmd_mut_ptr(16) };
+
 /// Place a lock on the file description of the file.
 pub const F_OFD_GETLK: MutPtrFcntlCmd<linux_unsafe::flock, ()> = unsafe { fcntl_cmd_mut_ptr(36) };
 
@@ -56,6 +91,135 @@ const F_LINUX_SPECIFIC_BASE: int = 1024;
 pub const F_DUPFD_CLOEXEC: DirectFcntlCmd<int, int> =
     unsafe { fcntl_cmd(F_LINUX_SPECIFIC_BASE + 6) };
 
+/// Change the capacity of a pipe, returning the actual new capacity.
+pub const F_SETPIPE_SZ: DirectFcntlCmd<int, int> = unsafe { fcntl_cmd(F_LINUX_SPECIFIC_BASE + 7) };
+
+/// Retrieve the capacity of a pipe.
+pub const F_GETPIPE_SZ: DirectFcntlCmd<(), int> = unsafe { fcntl_cmd(F_LINUX_SPECIFIC_BASE + 8) };
+
+/// Set or remove a lease on the file.
+///
+/// The argument is one of [`linux_unsafe::F_RDLCK`],
+/// [`linux_unsafe::F_WRLCK`], or [`linux_unsafe::F_UNLCK`], converted to
+/// [`int`].
+pub const F_SETLEASE: DirectFcntlCmd<int, ()> = unsafe { fcntl_cmd(F_LINUX_SPECIFIC_BASE) };
+
+/// Retrieve the type of lease currently held on the file, as one of
+/// [`linux_unsafe::F_RDLCK`], [`linux_unsafe::F_WRLCK`], or
+/// [`linux_unsafe::F_UNLCK`].
+pub const F_GETLEASE: DirectFcntlCmd<(), int> = unsafe { fcntl_cmd(F_LINUX_SPECIFIC_BASE + 1) };
+
+/// Request notification of changes to a directory, using a combination of
+/// the `DN_*` constants from [`linux_unsafe`].
+///
+/// Notifications are delivered as signals, so this is an obsolete mechanism
+/// that is included only for completeness. Prefer inotify or fanotify.
+pub const F_NOTIFY: DirectFcntlCmd<int, ()> = unsafe { fcntl_cmd(F_LINUX_SPECIFIC_BASE + 2) };
+
+/// Add seals to the file, which must have been created with sealing
+/// enabled, such as by using `MFD_ALLOW_SEALING` with
+/// [`super::File::memfd_create`].
+pub const F_ADD_SEALS: DirectFcntlCmd<SealFlags, ()> =
+    unsafe { fcntl_cmd(F_LINUX_SPECIFIC_BASE + 9) };
+
+/// Retrieve the seals currently applied to the file.
+pub const F_GET_SEALS: DirectFcntlCmd<(), SealFlags> =
+    unsafe { fcntl_cmd(F_LINUX_SPECIFIC_BASE + 10) };
+
+/// Retrieve the write lifetime hint for the inode of the file, as one of
+/// the `RWH_WRITE_LIFE_*` constants from [`linux_unsafe`].
+pub const F_GET_RW_HINT: MutPtrFcntlCmd<u64, ()> =
+    unsafe { fcntl_cmd_mut_ptr(F_LINUX_SPECIFIC_BASE + 11) };
+
+/// Set the write lifetime hint for the inode of the file, using one of the
+/// `RWH_WRITE_LIFE_*` constants from [`linux_unsafe`].
+pub const F_SET_RW_HINT: ConstPtrFcntlCmd<u64, ()> =
+    unsafe { fcntl_cmd_const_ptr(F_LINUX_SPECIFIC_BASE + 12) };
+
+/// Retrieve the write lifetime hint for the open file description.
+///
+/// Linux 5.18 removed support for per-file-description hints, so this
+/// fails with `EINVAL` on newer kernels.
+pub const F_GET_FILE_RW_HINT: MutPtrFcntlCmd<u64, ()> =
+    unsafe { fcntl_cmd_mut_ptr(F_LINUX_SPECIFIC_BASE + 13) };
+
+/// Set the write lifetime hint for the open file description.
+///
+/// Linux 5.18 removed support for per-file-description hints, so this
+/// fails with `EINVAL` on newer kernels.
+pub const F_SET_FILE_RW_HINT: ConstPtrFcntlCmd<u64, ()> =
+    unsafe { fcntl_cmd_const_ptr(F_LINUX_SPECIFIC_BASE + 14) };
+
+flags_type! {
+    /// File descriptor flags, for use with [`F_GETFD`] and [`F_SETFD`].
+    pub struct FdFlags(int);
+
+    /// No flags.
+    const NONE = 0;
+    /// Close the file descriptor automatically when calling `execve`.
+    const CLOEXEC = linux_unsafe::FD_CLOEXEC;
+}
+
+impl AsRawV for FdFlags {
+    #[inline(always)]
+    fn from_raw_result(raw: linux_unsafe::raw::V) -> Self {
+        Self(int::from_raw_result(raw))
+    }
+
+    #[inline(always)]
+    fn to_raw_arg(self) -> linux_unsafe::raw::V {
+        self.0.to_raw_arg()
+    }
+}
+
+impl FromFcntlResult for FdFlags {
+    #[inline(always)]
+    unsafe fn prepare_result(raw: int) -> Self {
+        Self(raw)
+    }
+}
+
+flags_type! {
+    /// File seals, for use with [`F_ADD_SEALS`] and [`F_GET_SEALS`].
+    pub struct SealFlags(int);
+
+    /// No seals.
+    const NONE = 0;
+    /// Prevent any further seals from being added.
+    const SEAL = linux_unsafe::F_SEAL_SEAL;
+    /// Prevent the file from being made smaller.
+    const SHRINK = linux_unsafe::F_SEAL_SHRINK;
+    /// Prevent the file from being made larger.
+    const GROW = linux_unsafe::F_SEAL_GROW;
+    /// Prevent any modification of the file's contents. This can only be
+    /// added while there are no writable shared mappings of the file.
+    const WRITE = linux_unsafe::F_SEAL_WRITE;
+    /// Like [`Self::WRITE`], but allows existing writable shared mappings
+    /// to continue to modify the file's contents.
+    const FUTURE_WRITE = linux_unsafe::F_SEAL_FUTURE_WRITE;
+    /// Prevent any change to the file's execute permission bits.
+    const EXEC = linux_unsafe::F_SEAL_EXEC;
+}
+
+impl AsRawV for SealFlags {
+    #[inline(always)]
+    fn from_raw_result(raw: linux_unsafe::raw::V) -> Self {
+        Self(int::from_raw_result(raw))
+    }
+
+    #[inline(always)]
+    fn to_raw_arg(self) -> linux_unsafe::raw::V {
+        self.0.to_raw_arg()
+    }
+}
+
+impl FromFcntlResult for SealFlags {
+    #[inline(always)]
+    unsafe fn prepare_result(raw: int) -> Self {
+        Self(raw)
+    }
+}
+
 /// Represents a particular command that can be used with the `fcntl` system call.
 ///
 /// Safety: Implementers must ensure that they only generate valid combinations
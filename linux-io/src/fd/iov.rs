@@ -0,0 +1,110 @@
+use core::marker::PhantomData;
+
+use linux_unsafe::iovec;
+
+/// A buffer to write from as part of a vectored write, such as with
+/// [`super::File::write_vectored`].
+///
+/// This has the same memory layout as the kernel's `iovec` type, and so a
+/// slice of these can be passed directly to the kernel.
+#[derive(Clone, Copy, Debug)]
+#[repr(transparent)]
+pub struct IoSlice<'a> {
+    raw: iovec,
+    _phantom: PhantomData<&'a [u8]>,
+}
+
+impl<'a> IoSlice<'a> {
+    /// Wraps the given byte slice.
+    #[inline(always)]
+    pub const fn new(buf: &'a [u8]) -> Self {
+        Self {
+            raw: iovec {
+                iov_base: buf.as_ptr() as *mut linux_unsafe::void,
+                iov_len: buf.len(),
+            },
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the wrapped byte slice.
+    #[inline(always)]
+    pub const fn as_slice(&self) -> &'a [u8] {
+        unsafe { core::slice::from_raw_parts(self.raw.iov_base as *const u8, self.raw.iov_len) }
+    }
+
+    /// Returns a pointer to the raw `iovec` representation of the given
+    /// buffers.
+    #[inline(always)]
+    pub(crate) fn as_raw_ptr(bufs: &[Self]) -> *const iovec {
+        bufs.as_ptr() as *const iovec
+    }
+}
+
+/// A buffer to read into as part of a vectored read, such as with
+/// [`super::File::read_vectored`].
+///
+/// This has the same memory layout as the kernel's `iovec` type, and so a
+/// slice of these can be passed directly to the kernel.
+#[derive(Debug)]
+#[repr(transparent)]
+pub struct IoSliceMut<'a> {
+    raw: iovec,
+    _phantom: PhantomData<&'a mut [u8]>,
+}
+
+impl<'a> IoSliceMut<'a> {
+    /// Wraps the given mutable byte slice.
+    #[inline(always)]
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self {
+            raw: iovec {
+                iov_base: buf.as_mut_ptr() as *mut linux_unsafe::void,
+                iov_len: buf.len(),
+            },
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the wrapped byte slice.
+    #[inline(always)]
+    pub fn as_slice(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self.raw.iov_base as *const u8, self.raw.iov_len) }
+    }
+
+    /// Returns the wrapped byte slice for modification.
+    #[inline(always)]
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        unsafe { core::slice::from_raw_parts_mut(self.raw.iov_base as *mut u8, self.raw.iov_len) }
+    }
+
+    /// Returns a pointer to the raw `iovec` representation of the given
+    /// buffers.
+    #[inline(always)]
+    pub(crate) fn as_raw_mut_ptr(bufs: &mut [Self]) -> *mut iovec {
+        bufs.as_mut_ptr() as *mut iovec
+    }
+}
+
+flags_type! {
+    /// Flags for [`super::File::read_vectored_at`] and
+    /// [`super::File::write_vectored_at`].
+    pub struct RwFlags(linux_unsafe::rwf_t);
+
+    /// No flags, giving the same behavior as the equivalent operation
+    /// without flags.
+    const NONE = 0;
+    /// High priority I/O, which allows block-based filesystems to use
+    /// polling of the device.
+    const HIPRI = linux_unsafe::RWF_HIPRI;
+    /// Per-operation equivalent of `O_DSYNC`. Only meaningful for writes.
+    const DSYNC = linux_unsafe::RWF_DSYNC;
+    /// Per-operation equivalent of `O_SYNC`. Only meaningful for writes.
+    const SYNC = linux_unsafe::RWF_SYNC;
+    /// Don't wait if the I/O would block, such as for data that is not
+    /// immediately available in the page cache, failing with `EAGAIN`
+    /// instead. Only meaningful for reads.
+    const NOWAIT = linux_unsafe::RWF_NOWAIT;
+    /// Per-operation equivalent of `O_APPEND`. Only meaningful for writes.
+    const APPEND = linux_unsafe::RWF_APPEND;
+}
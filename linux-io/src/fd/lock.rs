@@ -0,0 +1,358 @@
+use core::ops::{Bound, RangeBounds};
+
+use linux_unsafe::{int, loff_t, pid_t};
+
+use super::File;
+use crate::result::{self, Result};
+
+/// The kind of an advisory lock.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LockKind {
+    /// A shared ("read") lock, which can be held by multiple holders at once.
+    Shared,
+    /// An exclusive ("write") lock, which can be held only when there are no
+    /// other locks on the same range.
+    Exclusive,
+}
+
+impl LockKind {
+    #[inline(always)]
+    const fn raw_fcntl(self) -> linux_unsafe::short {
+        match self {
+            LockKind::Shared => linux_unsafe::F_RDLCK,
+            LockKind::Exclusive => linux_unsafe::F_WRLCK,
+        }
+    }
+
+    #[inline(always)]
+    const fn raw_flock(self) -> linux_unsafe::int {
+        match self {
+            LockKind::Shared => linux_unsafe::LOCK_SH,
+            LockKind::Exclusive => linux_unsafe::LOCK_EX,
+        }
+    }
+}
+
+/// Whether a locking operation should wait for conflicting locks to be
+/// released.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Wait {
+    /// Block until the lock can be acquired.
+    Yes,
+    /// Fail immediately if the lock cannot be acquired.
+    No,
+}
+
+/// Describes an existing lock that conflicts with a requested lock, as
+/// returned by [`File::query_lock`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LockConflict {
+    /// The kind of the conflicting lock.
+    pub kind: LockKind,
+    /// The offset of the first byte covered by the conflicting lock.
+    pub start: u64,
+    /// The number of bytes covered by the conflicting lock, or `None` if
+    /// the lock extends to the end of the file however large it grows.
+    pub len: Option<u64>,
+    /// The process that holds the conflicting lock, or `None` if it is an
+    /// open file description lock, which is not owned by any process.
+    pub pid: Option<pid_t>,
+}
+
+/// An advisory record lock on a range of a file, acquired using
+/// [`File::lock_range`] or [`File::lock_range_process`].
+///
+/// The lock is released when this object is dropped. Use [`Self::unlock`]
+/// instead to detect errors when releasing the lock.
+///
+/// The kernel doesn't track individual locks: it merges and converts
+/// overlapping ranges held by the same owner, and releasing a range releases
+/// every part of it that the owner holds. The guard therefore mutably
+/// borrows the file so that it can't be used to acquire other locks that
+/// the guard would then release. That doesn't prevent other descriptors
+/// sharing the same owner from doing so, though: descriptors duplicated from
+/// this one share its open file description locks, and every descriptor for
+/// the same file in this process shares its POSIX record locks.
+#[derive(Debug)]
+#[must_use = "the lock is released immediately if the guard is dropped"]
+pub struct RangeLock<'a, Device = ()> {
+    file: &'a mut File<Device>,
+    kind: LockKind,
+    start: loff_t,
+    len: loff_t,
+    ofd: bool,
+}
+
+impl<'a, Device> RangeLock<'a, Device> {
+    /// Returns the file that the lock belongs to.
+    #[inline(always)]
+    pub fn file(&self) -> &File<Device> {
+        self.file
+    }
+
+    /// Returns the kind of lock that is held.
+    #[inline(always)]
+    pub fn kind(&self) -> LockKind {
+        self.kind
+    }
+
+    /// Release the lock, returning any error from the kernel.
+    #[inline]
+    pub fn unlock(self) -> Result<()> {
+        let result = self.release();
+        core::mem::forget(self);
+        result
+    }
+
+    fn release(&self) -> Result<()> {
+        let mut raw = raw_flock(linux_unsafe::F_UNLCK, self.start, self.len);
+        let cmd = if self.ofd { F_OFD_SETLK } else { F_SETLK };
+        self.file.fcntl_lock(cmd, &mut raw)
+    }
+}
+
+impl<'a, Device> Drop for RangeLock<'a, Device> {
+    fn drop(&mut self) {
+        let _ = self.release();
+    }
+}
+
+/// An advisory lock on a whole file, acquired using [`File::flock`].
+///
+/// The lock is released when this object is dropped. Use [`Self::unlock`]
+/// instead to detect errors when releasing the lock.
+///
+/// An open file description holds at most one such lock, which the kernel
+/// converts in place when locking again, and so the guard mutably borrows
+/// the file to prevent acquiring a second guard for the same lock. Dropping
+/// this guard also releases the lock for any descriptors duplicated from
+/// this one, because they share the same open file description.
+#[derive(Debug)]
+#[must_use = "the lock is released immediately if the guard is dropped"]
+pub struct FlockGuard<'a, Device = ()> {
+    file: &'a mut File<Device>,
+    kind: LockKind,
+}
+
+impl<'a, Device> FlockGuard<'a, Device> {
+    /// Returns the file that the lock belongs to.
+    #[inline(always)]
+    pub fn file(&self) -> &File<Device> {
+        self.file
+    }
+
+    /// Returns the kind of lock that is held.
+    #[inline(always)]
+    pub fn kind(&self) -> LockKind {
+        self.kind
+    }
+
+    /// Release the lock, returning any error from the kernel.
+    #[inline]
+    pub fn unlock(self) -> Result<()> {
+        let result = self.release();
+        core::mem::forget(self);
+        result
+    }
+
+    fn release(&self) -> Result<()> {
+        let result = unsafe { linux_unsafe::flock(self.file.fd, linux_unsafe::LOCK_UN) };
+        result.map(|_| ()).map_err(|e| e.into())
+    }
+}
+
+impl<'a, Device> Drop for FlockGuard<'a, Device> {
+    fn drop(&mut self) {
+        let _ = self.release();
+    }
+}
+
+impl<Device> File<Device> {
+    /// Acquire an open file description lock on a range of the file.
+    ///
+    /// Open file description locks belong to the open file description
+    /// rather than to the calling process, and so they conflict with other
+    /// locks taken through separately-opened descriptors for the same file
+    /// even within a single process. They are released only when the
+    /// returned guard is dropped or when all descriptors sharing the open
+    /// file description are closed. This is usually the better choice
+    /// compared to [`Self::lock_range_process`].
+    ///
+    /// A range with no upper bound extends to the end of the file however
+    /// large it grows, so `..` locks the whole file.
+    ///
+    /// If `wait` is [`Wait::No`] and a conflicting lock is held then this
+    /// fails with `EAGAIN`.
+    #[inline]
+    pub fn lock_range(
+        &mut self,
+        range: impl RangeBounds<u64>,
+        kind: LockKind,
+        wait: Wait,
+    ) -> Result<RangeLock<'_, Device>> {
+        let (start, len) = raw_lock_range(range)?;
+        let mut raw = raw_flock(kind.raw_fcntl(), start, len);
+        match wait {
+            Wait::Yes => self.fcntl_lock(F_OFD_SETLKW, &mut raw)?,
+            Wait::No => self.fcntl_lock(F_OFD_SETLK, &mut raw)?,
+        }
+        Ok(RangeLock {
+            file: self,
+            kind,
+            start,
+            len,
+            ofd: true,
+        })
+    }
+
+    /// Acquire a traditional POSIX record lock on a range of the file.
+    ///
+    /// POSIX record locks belong to the calling process, and so they never
+    /// conflict with other locks held by the same process. The kernel
+    /// releases _all_ of the process's locks on a file as soon as the
+    /// process closes _any_ descriptor for that file, even if it was opened
+    /// separately. Prefer [`Self::lock_range`] unless compatibility with
+    /// other software using POSIX locks is required.
+    ///
+    /// If `wait` is [`Wait::No`] and a conflicting lock is held then this
+    /// fails with either `EAGAIN` or `EACCES`.
+    #[inline]
+    pub fn lock_range_process(
+        &mut self,
+        range: impl RangeBounds<u64>,
+        kind: LockKind,
+        wait: Wait,
+    ) -> Result<RangeLock<'_, Device>> {
+        let (start, len) = raw_lock_range(range)?;
+        let mut raw = raw_flock(kind.raw_fcntl(), start, len);
+        match wait {
+            Wait::Yes => self.fcntl_lock(F_SETLKW, &mut raw)?,
+            Wait::No => self.fcntl_lock(F_SETLK, &mut raw)?,
+        }
+        Ok(RangeLock {
+            file: self,
+            kind,
+            start,
+            len,
+            ofd: false,
+        })
+    }
+
+    /// Test whether a lock of the given kind could be placed on a range of
+    /// the file, returning a description of one conflicting lock if not.
+    ///
+    /// This considers both open file description locks and POSIX record
+    /// locks, including those held by the calling process through other
+    /// open file descriptions.
+    #[inline]
+    pub fn query_lock(
+        &self,
+        range: impl RangeBounds<u64>,
+        kind: LockKind,
+    ) -> Result<Option<LockConflict>> {
+        let (start, len) = raw_lock_range(range)?;
+        let mut raw = raw_flock(kind.raw_fcntl(), start, len);
+        self.fcntl_lock(F_OFD_GETLK, &mut raw)?;
+        let kind = match raw.l_type {
+            linux_unsafe::F_UNLCK => return Ok(None),
+            linux_unsafe::F_RDLCK => LockKind::Shared,
+            _ => LockKind::Exclusive,
+        };
+        Ok(Some(LockConflict {
+            kind,
+            start: raw.l_start as u64,
+            len: if raw.l_len == 0 {
+                None
+            } else {
+                Some(raw.l_len as u64)
+            },
+            pid: if raw.l_pid > 0 { Some(raw.l_pid) } else { None },
+        }))
+    }
+
+    /// Acquire an advisory lock on the whole file using the `flock` system
+    /// call.
+    ///
+    /// Locks acquired this way belong to the open file description, but
+    /// are independent of the range locks acquired using
+    /// [`Self::lock_range`] and [`Self::lock_range_process`]. Calling this
+    /// while already holding a lock converts the existing lock to the given
+    /// kind.
+    ///
+    /// If `wait` is [`Wait::No`] and a conflicting lock is held then this
+    /// fails with `EAGAIN`.
+    #[inline]
+    pub fn flock(&mut self, kind: LockKind, wait: Wait) -> Result<FlockGuard<'_, Device>> {
+        let mut op = kind.raw_flock();
+        if wait == Wait::No {
+            op |= linux_unsafe::LOCK_NB;
+        }
+        let result = unsafe { linux_unsafe::flock(self.fd, op) };
+        result
+            .map(|_| FlockGuard { file: self, kind })
+            .map_err(|e| e.into())
+    }
+
+    fn fcntl_lock(&self, cmd: int, raw: &mut RawFlock) -> Result<()> {
+        #[cfg(not(target_pointer_width = "32"))]
+        let result = unsafe { linux_unsafe::fcntl(self.fd, cmd, raw as *mut RawFlock) };
+        #[cfg(target_pointer_width = "32")]
+        let result = unsafe { linux_unsafe::fcntl64(self.fd, cmd, raw as *mut RawFlock) };
+        result.map(|_| ()).map_err(|e| e.into())
+    }
+}
+
+// On 32-bit platforms the kernel supports open file description locks only
+// through fcntl64, which takes the larger flock64 structure and uses separate
+// command numbers for its versions of the POSIX record lock commands. Using
+// it for all locks also allows offsets beyond the range of the 32-bit off_t.
+#[cfg(not(target_pointer_width = "32"))]
+type RawFlock = linux_unsafe::flock;
+#[cfg(target_pointer_width = "32")]
+type RawFlock = linux_unsafe::flock64;
+
+#[cfg(not(target_pointer_width = "32"))]
+const F_SETLK: int = 6;
+#[cfg(not(target_pointer_width = "32"))]
+const F_SETLKW: int = 7;
+#[cfg(target_pointer_width = "32")]
+const F_SETLK: int = 13; // F_SETLK64
+#[cfg(target_pointer_width = "32")]
+const F_SETLKW: int = 14; // F_SETLKW64
+const F_OFD_GETLK: int = 36;
+const F_OFD_SETLK: int = 37;
+const F_OFD_SETLKW: int = 38;
+
+#[inline(always)]
+fn raw_flock(l_type: linux_unsafe::short, start: loff_t, len: loff_t) -> RawFlock {
+    RawFlock {
+        l_type,
+        l_whence: linux_unsafe::SEEK_SET as linux_unsafe::short,
+        l_start: start,
+        l_len: len,
+        l_pid: 0,
+    }
+}
+
+/// Converts a range of byte offsets into the start and length values used
+/// in `struct flock`, where a length of zero represents an unbounded range.
+fn raw_lock_range(range: impl RangeBounds<u64>) -> Result<(loff_t, loff_t)> {
+    let start = match range.start_bound() {
+        Bound::Included(v) => *v,
+        Bound::Excluded(v) => v.checked_add(1).ok_or(result::EINVAL)?,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(v) => Some(v.checked_add(1).ok_or(result::EINVAL)?),
+        Bound::Excluded(v) => Some(*v),
+        Bound::Unbounded => None,
+    };
+    let len = match end {
+        Some(end) if end <= start => return Err(result::EINVAL),
+        Some(end) => end - start,
+        None => 0,
+    };
+    let start = loff_t::try_from(start).map_err(|_| result::EINVAL)?;
+    let len = loff_t::try_from(len).map_err(|_| result::EINVAL)?;
+    Ok((start, len))
+}
@@ -0,0 +1,262 @@
+use linux_unsafe::{statx, statx_timestamp};
+
+use super::DirEntryType;
+
+/// The mask of fields that [`super::File::metadata`] and
+/// [`super::File::metadata_relative`] request from the kernel.
+pub const METADATA_DEFAULT_MASK: u32 = linux_unsafe::STATX_BASIC_STATS
+    | linux_unsafe::STATX_BTIME
+    | linux_unsafe::STATX_MNT_ID
+    | linux_unsafe::STATX_DIOALIGN;
+
+/// Metadata about a file, as returned by the `statx` system call.
+///
+/// The kernel may not populate all of the fields that were requested, such
+/// as when the underlying filesystem doesn't support a particular field.
+/// Accessors for fields that are reported in the `stx_mask` field of the
+/// raw result therefore return `None` when the kernel didn't fill them.
+#[derive(Clone, Copy, Debug)]
+#[repr(transparent)]
+pub struct Metadata {
+    raw: statx,
+}
+
+impl Metadata {
+    /// Wraps a raw `statx` result.
+    #[inline(always)]
+    pub const fn from_raw(raw: statx) -> Self {
+        Self { raw }
+    }
+
+    /// Returns the raw `statx` result.
+    #[inline(always)]
+    pub const fn raw(&self) -> &statx {
+        &self.raw
+    }
+
+    /// Returns the mask of `STATX_*` flags representing the fields that the
+    /// kernel populated.
+    #[inline(always)]
+    pub const fn mask(&self) -> u32 {
+        self.raw.stx_mask
+    }
+
+    /// Returns true if the kernel populated all of the fields represented
+    /// by the given `STATX_*` mask.
+    #[inline(always)]
+    pub const fn has(&self, mask: u32) -> bool {
+        (self.raw.stx_mask & mask) == mask
+    }
+
+    #[inline(always)]
+    const fn field<T: Copy>(&self, mask: u32, v: T) -> Option<T> {
+        if self.has(mask) {
+            Some(v)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the type of the file.
+    #[inline]
+    pub fn file_type(&self) -> Option<DirEntryType> {
+        // The file type bits of a mode are the same values used for
+        // directory entry types, just shifted 12 bits to the left.
+        self.field(linux_unsafe::STATX_TYPE, self.raw.stx_mode)
+            .map(|mode| DirEntryType::from((mode >> 12) as linux_unsafe::uchar))
+    }
+
+    /// Returns true if the file is a directory.
+    #[inline]
+    pub fn is_dir(&self) -> bool {
+        self.file_type() == Some(DirEntryType::Dir)
+    }
+
+    /// Returns true if the file is a regular file.
+    #[inline]
+    pub fn is_file(&self) -> bool {
+        self.file_type() == Some(DirEntryType::Reg)
+    }
+
+    /// Returns true if the file is a symbolic link.
+    #[inline]
+    pub fn is_symlink(&self) -> bool {
+        self.file_type() == Some(DirEntryType::Lnk)
+    }
+
+    /// Returns the permission bits of the file's mode, including the
+    /// setuid, setgid, and sticky bits, but excluding the file type.
+    #[inline]
+    pub fn permissions(&self) -> Option<linux_unsafe::mode_t> {
+        self.field(linux_unsafe::STATX_MODE, self.raw.stx_mode)
+            .map(|mode| (mode as linux_unsafe::mode_t) & !linux_unsafe::S_IFMT)
+    }
+
+    /// Returns the number of hard links to the file.
+    #[inline]
+    pub fn nlink(&self) -> Option<u32> {
+        self.field(linux_unsafe::STATX_NLINK, self.raw.stx_nlink)
+    }
+
+    /// Returns the user id of the file's owner.
+    #[inline]
+    pub fn uid(&self) -> Option<linux_unsafe::uid_t> {
+        self.field(linux_unsafe::STATX_UID, self.raw.stx_uid)
+    }
+
+    /// Returns the group id of the file's owner.
+    #[inline]
+    pub fn gid(&self) -> Option<linux_unsafe::gid_t> {
+        self.field(linux_unsafe::STATX_GID, self.raw.stx_gid)
+    }
+
+    /// Returns the inode number of the file.
+    #[inline]
+    pub fn ino(&self) -> Option<u64> {
+        self.field(linux_unsafe::STATX_INO, self.raw.stx_ino)
+    }
+
+    /// Returns the size of the file in bytes.
+    #[inline]
+    pub fn size(&self) -> Option<u64> {
+        self.field(linux_unsafe::STATX_SIZE, self.raw.stx_size)
+    }
+
+    /// Returns the number of 512-byte blocks allocated to the file.
+    #[inline]
+    pub fn blocks(&self) -> Option<u64> {
+        self.field(linux_unsafe::STATX_BLOCKS, self.raw.stx_blocks)
+    }
+
+    /// Returns the preferred block size for efficient I/O on the file.
+    ///
+    /// The kernel always populates this field.
+    #[inline(always)]
+    pub const fn block_size(&self) -> u32 {
+        self.raw.stx_blksize
+    }
+
+    /// Returns the major and minor numbers of the device containing the file.
+    ///
+    /// The kernel always populates this field.
+    #[inline(always)]
+    pub const fn dev(&self) -> (u32, u32) {
+        (self.raw.stx_dev_major, self.raw.stx_dev_minor)
+    }
+
+    /// Returns the major and minor numbers of the device that the file
+    /// represents, if it's a device special file.
+    ///
+    /// The kernel always populates this field.
+    #[inline(always)]
+    pub const fn rdev(&self) -> (u32, u32) {
+        (self.raw.stx_rdev_major, self.raw.stx_rdev_minor)
+    }
+
+    /// Returns the id of the mount containing the file, as used in
+    /// `/proc/self/mountinfo`.
+    #[inline]
+    pub fn mount_id(&self) -> Option<u64> {
+        self.field(linux_unsafe::STATX_MNT_ID, self.raw.stx_mnt_id)
+    }
+
+    /// Returns the time when the file was last accessed.
+    #[inline]
+    pub fn accessed(&self) -> Option<statx_timestamp> {
+        self.field(linux_unsafe::STATX_ATIME, self.raw.stx_atime)
+    }
+
+    /// Returns the time when the file's content was last modified.
+    #[inline]
+    pub fn modified(&self) -> Option<statx_timestamp> {
+        self.field(linux_unsafe::STATX_MTIME, self.raw.stx_mtime)
+    }
+
+    /// Returns the time when the file's metadata was last changed.
+    #[inline]
+    pub fn changed(&self) -> Option<statx_timestamp> {
+        self.field(linux_unsafe::STATX_CTIME, self.raw.stx_ctime)
+    }
+
+    /// Returns the time when the file was created.
+    #[inline]
+    pub fn created(&self) -> Option<statx_timestamp> {
+        self.field(linux_unsafe::STATX_BTIME, self.raw.stx_btime)
+    }
+
+    /// Returns the memory and file offset alignments, in that order, that
+    /// are required for direct I/O using `O_DIRECT`.
+    ///
+    /// Returns `None` if the filesystem didn't report this information or
+    /// if the file doesn't support direct I/O, which the kernel signals by
+    /// reporting a memory alignment of zero.
+    #[inline]
+    pub fn dio_alignment(&self) -> Option<(u32, u32)> {
+        self.field(
+            linux_unsafe::STATX_DIOALIGN,
+            (self.raw.stx_dio_mem_align, self.raw.stx_dio_offset_align),
+        )
+        .filter(|&(mem_align, _)| mem_align != 0)
+    }
+
+    /// Returns whether the file has the given `STATX_ATTR_*` attribute,
+    /// or `None` if the filesystem doesn't support that attribute.
+    #[inline]
+    pub const fn attribute(&self, attr: u64) -> Option<bool> {
+        if (self.raw.stx_attributes_mask & attr) == attr {
+            Some((self.raw.stx_attributes & attr) == attr)
+        } else {
+            None
+        }
+    }
+
+    /// Returns whether the file is immutable.
+    #[inline]
+    pub const fn is_immutable(&self) -> Option<bool> {
+        self.attribute(linux_unsafe::STATX_ATTR_IMMUTABLE)
+    }
+
+    /// Returns whether the file can only be opened in append mode for
+    /// writing.
+    #[inline]
+    pub const fn is_append_only(&self) -> Option<bool> {
+        self.attribute(linux_unsafe::STATX_ATTR_APPEND)
+    }
+
+    /// Returns whether the file is compressed by the filesystem.
+    #[inline]
+    pub const fn is_compressed(&self) -> Option<bool> {
+        self.attribute(linux_unsafe::STATX_ATTR_COMPRESSED)
+    }
+
+    /// Returns whether the file is encrypted by the filesystem.
+    #[inline]
+    pub const fn is_encrypted(&self) -> Option<bool> {
+        self.attribute(linux_unsafe::STATX_ATTR_ENCRYPTED)
+    }
+
+    /// Returns whether the file has fs-verity enabled.
+    #[inline]
+    pub const fn is_verity(&self) -> Option<bool> {
+        self.attribute(linux_unsafe::STATX_ATTR_VERITY)
+    }
+
+    /// Returns whether the file is in the DAX (direct access) state.
+    #[inline]
+    pub const fn is_dax(&self) -> Option<bool> {
+        self.attribute(linux_unsafe::STATX_ATTR_DAX)
+    }
+
+    /// Returns whether the file is the root of a mount.
+    #[inline]
+    pub const fn is_mount_root(&self) -> Option<bool> {
+        self.attribute(linux_unsafe::STATX_ATTR_MOUNT_ROOT)
+    }
+}
+
+impl From<statx> for Metadata {
+    #[inline(always)]
+    fn from(raw: statx) -> Self {
+        Self::from_raw(raw)
+    }
+}
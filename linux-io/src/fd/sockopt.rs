@@ -41,6 +41,28 @@ pub const SO_DONTROUTE: DirectSockOpt<int> = unsafe { sockopt(SOL_SOCKET, 5) };
 /// `1` enables keepalive messages, while `0` disables them.
 pub const SO_KEEPALIVE: DirectSockOpt<int> = unsafe { sockopt(SOL_SOCKET, 9) };
 
+/// Receive the credentials of the sending process as `SCM_CREDENTIALS`
+/// ancillary data on a Unix domain socket.
+///
+/// `1` enables passing credentials, while `0` disables it.
+pub const SO_PASSCRED: DirectSockOpt<int> =
+    unsafe { sockopt(SOL_SOCKET, linux_unsafe::SO_PASSCRED) };
+
+/// Generate timestamps for sent and received packets, reported as
+/// `SCM_TIMESTAMPING` ancillary data.
+///
+/// The value is a combination of `SOF_TIMESTAMPING_*` flags from
+/// [`linux_unsafe`] selecting which timestamps to generate and report.
+pub const SO_TIMESTAMPING: DirectSockOpt<int> =
+    unsafe { sockopt(SOL_SOCKET, linux_unsafe::SO_TIMESTAMPING_OLD) };
+
+/// Report the time each packet was received as `SCM_TIMESTAMPNS` ancillary
+/// data, with nanosecond precision.
+///
+/// `1` enables timestamps, while `0` disables them.
+pub const SO_TIMESTAMPNS: DirectSockOpt<int> =
+    unsafe { sockopt(SOL_SOCKET, linux_unsafe::SO_TIMESTAMPNS_OLD) };
+
 /// Implemented by options that can be used with `setsockopt`.
 ///
 /// Safety: Implementers must ensure that they only generate valid combinations
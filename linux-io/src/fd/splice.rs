@@ -0,0 +1,45 @@
+use crate::result::{self, Result};
+
+flags_type! {
+    /// Flags for the pipe operations in [`crate::pipe`](mod@crate::pipe), such as
+    /// `splice` and `tee`.
+    pub struct SpliceFlags(linux_unsafe::uint);
+
+    /// No flags.
+    const NONE = 0;
+    /// Hint that the kernel should move pages instead of copying them, if
+    /// possible.
+    const MOVE = linux_unsafe::SPLICE_F_MOVE;
+    /// Don't block on pipe I/O, failing with `EAGAIN` instead. The other
+    /// file may still block unless it's also in non-blocking mode.
+    const NONBLOCK = linux_unsafe::SPLICE_F_NONBLOCK;
+    /// Hint that more data will follow in a subsequent operation, which
+    /// is useful when the output is a socket.
+    const MORE = linux_unsafe::SPLICE_F_MORE;
+}
+
+/// Repeatedly calls `op` to move up to `len` bytes in total, passing the
+/// number of bytes still remaining each time, and returns the total number
+/// of bytes moved.
+///
+/// This stops early if `op` returns zero, which represents end of file, or
+/// if it fails with `EAGAIN` after at least one byte has already been moved.
+/// It retries automatically if `op` fails with `EINTR`, and returns any
+/// other error immediately.
+///
+/// This is intended for use with data movement operations such as `splice`
+/// and [`super::File::sendfile_to`], which can each move fewer bytes than
+/// requested.
+pub fn transfer_all(len: usize, mut op: impl FnMut(usize) -> Result<usize>) -> Result<usize> {
+    let mut moved = 0;
+    while moved < len {
+        match op(len - moved) {
+            Ok(0) => break,
+            Ok(n) => moved += n,
+            Err(result::EINTR) => continue,
+            Err(result::EAGAIN) if moved > 0 => break,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(moved)
+}
@@ -0,0 +1,66 @@
+/// Declares a newtype wrapping a set of flags of a raw integer type, along
+/// with the given flag constants.
+///
+/// The generated type has `from_raw`, `raw`, and `contains` methods, and
+/// implements the `|` and `|=` operators for combining flags.
+macro_rules! flags_type {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident($raw:ty);
+
+        $(
+            $(#[$const_attr:meta])*
+            const $const_name:ident = $value:expr;
+        )*
+    ) => {
+        $(#[$attr])*
+        ///
+        /// Combine flags using the `|` operator.
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+        #[repr(transparent)]
+        $vis struct $name($raw);
+
+        impl $name {
+            $(
+                $(#[$const_attr])*
+                pub const $const_name: Self = Self($value);
+            )*
+
+            /// Wrap a raw combination of flags.
+            #[inline(always)]
+            pub const fn from_raw(raw: $raw) -> Self {
+                Self(raw)
+            }
+
+            /// Returns the raw flags value, for use with the functions in
+            /// [`linux_unsafe`].
+            #[inline(always)]
+            pub const fn raw(self) -> $raw {
+                self.0
+            }
+
+            /// Returns true if all of the flags in `other` are also set in
+            /// `self`.
+            #[inline(always)]
+            pub const fn contains(self, other: Self) -> bool {
+                (self.0 & other.0) == other.0
+            }
+        }
+
+        impl core::ops::BitOr for $name {
+            type Output = Self;
+
+            #[inline(always)]
+            fn bitor(self, rhs: Self) -> Self {
+                Self(self.0 | rhs.0)
+            }
+        }
+
+        impl core::ops::BitOrAssign for $name {
+            #[inline(always)]
+            fn bitor_assign(&mut self, rhs: Self) {
+                self.0 |= rhs.0;
+            }
+        }
+    };
+}
@@ -0,0 +1,243 @@
+//! An inotify instance reports changes to the files and directories that
+//! it is watching as a stream of variable-length event records, which can
+//! be decoded using [`InotifyEvents`](crate::inotify::InotifyEvents).
+
+use core::ffi::CStr;
+
+use crate::fd::ioctl::IoDevice;
+use crate::result::Result;
+use crate::File;
+
+/// A marker type for [`File`] objects that represent inotify instances.
+#[derive(Clone, Copy, Debug)]
+pub struct InotifyDevice;
+
+impl IoDevice for InotifyDevice {}
+
+/// An inotify instance.
+pub type Inotify = File<InotifyDevice>;
+
+impl File<InotifyDevice> {
+    /// Create a new inotify instance with no watches.
+    ///
+    /// Most callers should include [`InotifyFlags::CLOEXEC`] in `flags`.
+    #[inline]
+    pub fn new(flags: InotifyFlags) -> Result<Self> {
+        let result = unsafe { linux_unsafe::inotify_init1(flags.raw()) };
+        result
+            .map(|fd| unsafe { File::from_raw_fd(fd) })
+            .map_err(|e| e.into())
+    }
+
+    /// Start watching the file or directory at the given path, or change
+    /// the mask of an existing watch for the same file.
+    ///
+    /// The returned watch descriptor identifies the watch in the events
+    /// that it produces.
+    #[inline]
+    pub fn add_watch(&self, path: &CStr, mask: WatchMask) -> Result<WatchDescriptor> {
+        let result = unsafe { linux_unsafe::inotify_add_watch(self.fd, path.as_ptr(), mask.raw()) };
+        result.map(WatchDescriptor).map_err(|e| e.into())
+    }
+
+    /// Stop watching the file associated with the given watch descriptor.
+    ///
+    /// The kernel produces an event with [`WatchMask::IGNORED`] once the
+    /// watch has been removed.
+    #[inline]
+    pub fn remove_watch(&self, wd: WatchDescriptor) -> Result<()> {
+        let result = unsafe { linux_unsafe::inotify_rm_watch(self.fd, wd.0) };
+        result.map(|_| ()).map_err(|e| e.into())
+    }
+
+    /// Read one or more pending events into the given buffer, returning an
+    /// iterator over the events that were read.
+    ///
+    /// Blocks until at least one event is pending unless the inotify
+    /// instance is in non-blocking mode, in which case it returns
+    /// [`EAGAIN`](crate::result::EAGAIN) if no events are pending. Fails
+    /// with [`EINVAL`](crate::result::EINVAL) if the buffer is too small
+    /// for the next event, which can include a filename of up to
+    /// `NAME_MAX` bytes.
+    #[inline]
+    pub fn read_events<'a>(&self, buf: &'a mut [u8]) -> Result<InotifyEvents<'a>> {
+        let len = self.read(buf)?;
+        Ok(InotifyEvents::from_buffer(&buf[..len]))
+    }
+}
+
+/// Identifies a watch added with [`Inotify::add_watch`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct WatchDescriptor(linux_unsafe::int);
+
+impl WatchDescriptor {
+    /// Wrap a raw watch descriptor.
+    #[inline(always)]
+    pub const fn from_raw(raw: linux_unsafe::int) -> Self {
+        Self(raw)
+    }
+
+    /// Returns the raw watch descriptor.
+    #[inline(always)]
+    pub const fn raw(self) -> linux_unsafe::int {
+        self.0
+    }
+}
+
+/// A single event extracted from a buffer populated by reading from an
+/// inotify instance.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct InotifyEvent<'a> {
+    /// The watch that produced the event.
+    pub wd: WatchDescriptor,
+    /// The type of event, along with any event flags.
+    pub mask: WatchMask,
+    /// A value that connects the [`WatchMask::MOVED_FROM`] and
+    /// [`WatchMask::MOVED_TO`] events for a single rename, or zero for
+    /// other events.
+    pub cookie: u32,
+    /// The name of the file within a watched directory that the event
+    /// relates to, or `None` if the event relates to the watched file
+    /// itself.
+    pub name: Option<&'a CStr>,
+}
+
+/// An iterator over events in an already-populated inotify read buffer.
+pub struct InotifyEvents<'a> {
+    remain: &'a [u8],
+}
+
+impl<'a> InotifyEvents<'a> {
+    pub fn from_buffer(buf: &'a [u8]) -> Self {
+        Self { remain: buf }
+    }
+
+    /// Consume the iterator object and obtain the remaining bytes that it
+    /// hasn't yet transformed into `InotifyEvent` values.
+    pub fn to_remaining_bytes(self) -> &'a [u8] {
+        self.remain
+    }
+}
+
+impl<'a> Iterator for InotifyEvents<'a> {
+    type Item = InotifyEvent<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (ret, remain) = inotify_event_from_buf(self.remain);
+        self.remain = remain;
+        ret
+    }
+}
+
+fn inotify_event_from_buf<'a>(buf: &'a [u8]) -> (Option<InotifyEvent<'a>>, &'a [u8]) {
+    const HEADER_SIZE: usize = core::mem::size_of::<linux_unsafe::inotify_event>();
+
+    if buf.len() < HEADER_SIZE {
+        // Not enough bytes left for an event.
+        return (None, buf);
+    }
+    // The caller's buffer might not be suitably aligned for the header.
+    let hdr =
+        unsafe { core::ptr::read_unaligned(buf.as_ptr() as *const linux_unsafe::inotify_event) };
+    let name_len = hdr.len as usize;
+    if buf.len() - HEADER_SIZE < name_len {
+        return (None, buf);
+    }
+    let name = if name_len == 0 {
+        None
+    } else {
+        // The kernel pads the name with null bytes, so there's always at
+        // least one terminator.
+        match CStr::from_bytes_until_nul(&buf[HEADER_SIZE..HEADER_SIZE + name_len]) {
+            Ok(name) => Some(name),
+            Err(_) => return (None, buf),
+        }
+    };
+
+    let remain = &buf[HEADER_SIZE + name_len..];
+    let ret = InotifyEvent {
+        wd: WatchDescriptor(hdr.wd),
+        mask: WatchMask(hdr.mask),
+        cookie: hdr.cookie,
+        name,
+    };
+    (Some(ret), remain)
+}
+
+flags_type! {
+    /// Flags for [`Inotify::new`].
+    pub struct InotifyFlags(linux_unsafe::int);
+
+    /// No flags.
+    const NONE = 0;
+    /// Set the close-on-exec flag on the new file descriptor.
+    const CLOEXEC = linux_unsafe::IN_CLOEXEC;
+    /// Put the new file descriptor in non-blocking mode.
+    const NONBLOCK = linux_unsafe::IN_NONBLOCK;
+}
+
+flags_type! {
+    /// Event types and flags for use with [`Inotify::add_watch`], and the event
+    /// types and flags reported in each [`InotifyEvent`].
+    pub struct WatchMask(u32);
+
+    /// No events.
+    const NONE = 0;
+    /// The file was accessed.
+    const ACCESS = linux_unsafe::IN_ACCESS;
+    /// The file was modified.
+    const MODIFY = linux_unsafe::IN_MODIFY;
+    /// The file's metadata changed.
+    const ATTRIB = linux_unsafe::IN_ATTRIB;
+    /// A file opened for writing was closed.
+    const CLOSE_WRITE = linux_unsafe::IN_CLOSE_WRITE;
+    /// A file not opened for writing was closed.
+    const CLOSE_NOWRITE = linux_unsafe::IN_CLOSE_NOWRITE;
+    /// The file was opened.
+    const OPEN = linux_unsafe::IN_OPEN;
+    /// A file was renamed out of a watched directory.
+    const MOVED_FROM = linux_unsafe::IN_MOVED_FROM;
+    /// A file was renamed into a watched directory.
+    const MOVED_TO = linux_unsafe::IN_MOVED_TO;
+    /// A file was created in a watched directory.
+    const CREATE = linux_unsafe::IN_CREATE;
+    /// A file was deleted from a watched directory.
+    const DELETE = linux_unsafe::IN_DELETE;
+    /// The watched file itself was deleted.
+    const DELETE_SELF = linux_unsafe::IN_DELETE_SELF;
+    /// The watched file itself was moved.
+    const MOVE_SELF = linux_unsafe::IN_MOVE_SELF;
+    /// Both [`Self::CLOSE_WRITE`] and [`Self::CLOSE_NOWRITE`].
+    const CLOSE = linux_unsafe::IN_CLOSE;
+    /// Both [`Self::MOVED_FROM`] and [`Self::MOVED_TO`].
+    const MOVE = linux_unsafe::IN_MOVE;
+    /// All of the event types that can be requested.
+    const ALL_EVENTS = linux_unsafe::IN_ALL_EVENTS;
+
+    /// Watch flag: fail with `ENOTDIR` unless the path is a directory.
+    const ONLYDIR = linux_unsafe::IN_ONLYDIR;
+    /// Watch flag: don't follow a symbolic link at the end of the path.
+    const DONT_FOLLOW = linux_unsafe::IN_DONT_FOLLOW;
+    /// Watch flag: stop reporting events for children after they have been
+    /// unlinked from a watched directory.
+    const EXCL_UNLINK = linux_unsafe::IN_EXCL_UNLINK;
+    /// Watch flag: fail with `EEXIST` if the file is already watched.
+    const MASK_CREATE = linux_unsafe::IN_MASK_CREATE;
+    /// Watch flag: add to the mask of an existing watch instead of
+    /// replacing it.
+    const MASK_ADD = linux_unsafe::IN_MASK_ADD;
+    /// Watch flag: remove the watch after its first event.
+    const ONESHOT = linux_unsafe::IN_ONESHOT;
+
+    /// Reported flag: the filesystem containing the watched file was
+    /// unmounted.
+    const UNMOUNT = linux_unsafe::IN_UNMOUNT;
+    /// Reported flag: the event queue overflowed, and so some events were
+    /// lost.
+    const Q_OVERFLOW = linux_unsafe::IN_Q_OVERFLOW;
+    /// Reported flag: the watch was removed.
+    const IGNORED = linux_unsafe::IN_IGNORED;
+    /// Reported flag: the subject of the event is a directory.
+    const ISDIR = linux_unsafe::IN_ISDIR;
+}
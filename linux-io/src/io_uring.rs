@@ -0,0 +1,928 @@
+//! io_uring is an interface for asynchronous I/O where the program and the
+//! kernel share a pair of ring buffers: the program places requests in the
+//! submission queue and the kernel places the results in the completion
+//! queue, so that many operations can be started and reaped with few or no
+//! system calls.
+//!
+//! Create a ring using [`IoUring::new`] or [`IoUring::with_options`],
+//! describe each operation using one of the constructors of
+//! [`SubmissionEntry`](crate::io_uring::SubmissionEntry), add the entries
+//! to the ring using [`IoUring::push`], and then use [`IoUring::submit`] or
+//! [`IoUring::submit_and_wait`] to ask the kernel to start them. Each
+//! result is returned as a [`Completion`](crate::io_uring::Completion)
+//! carrying the `user_data` value of its entry.
+//!
+//! Most submission entries refer to memory owned by the caller, such as
+//! buffers to read into. The kernel accesses that memory asynchronously, at
+//! some point between submission and completion, and so pushing an entry
+//! is unsafe: the caller must keep that memory valid until the operation's
+//! completion has been retrieved.
+
+use core::ffi::CStr;
+use core::sync::atomic::{fence, AtomicU32, Ordering};
+use core::time::Duration;
+
+use linux_unsafe::{int, io_uring_cqe, io_uring_sqe, size_t, void};
+
+use crate::fd::ioctl::IoDevice;
+use crate::fd::{IoSlice, IoSliceMut, OpenOptions, OpenWithMode, OpenWithoutMode};
+use crate::mem::Mapping;
+use crate::result::Result;
+use crate::File;
+
+/// The offset to use with the read and write operations to use and update
+/// the file's current position, rather than an explicit position.
+pub const CURRENT_POSITION: u64 = u64::MAX;
+
+/// A marker type for [`File`] objects that represent io_uring instances.
+#[derive(Clone, Copy, Debug)]
+pub struct IoUringDevice;
+
+impl IoDevice for IoUringDevice {}
+
+/// An io_uring instance along with its shared submission and completion
+/// queues, which are unmapped and closed when dropped.
+#[derive(Debug)]
+pub struct IoUring {
+    sq: SubmissionQueue,
+    cq: CompletionQueue,
+    setup_flags: u32,
+    features: u32,
+    _sq_ring: Mapping,
+    _cq_ring: Option<Mapping>,
+    _sqes: Mapping,
+    file: File<IoUringDevice>,
+}
+
+// Safety: the raw pointers in the queues refer only to the mappings owned
+// by the same object, and all operations that modify the queues require
+// an exclusive borrow.
+unsafe impl Send for IoUring {}
+
+impl IoUring {
+    /// Create a new io_uring instance whose submission queue has at least
+    /// `entries` entries, using the default options.
+    #[inline]
+    pub fn new(entries: u32) -> Result<Self> {
+        Self::with_options(entries, IoUringOptions::new())
+    }
+
+    /// Create a new io_uring instance whose submission queue has at least
+    /// `entries` entries, using the given options.
+    ///
+    /// The new file descriptor always has the close-on-exec flag set.
+    pub fn with_options(entries: u32, options: IoUringOptions) -> Result<Self> {
+        if (options.flags & UNSUPPORTED_SETUP_FLAGS) != 0 {
+            return Err(crate::result::EINVAL);
+        }
+        let mut params: linux_unsafe::io_uring_params = unsafe { core::mem::zeroed() };
+        params.flags = options.flags;
+        params.cq_entries = options.cq_entries;
+        params.sq_thread_cpu = options.sq_thread_cpu;
+        params.sq_thread_idle = options.sq_thread_idle;
+        let result = unsafe { linux_unsafe::io_uring_setup(entries, &mut params as *mut _) };
+        let fd = result.map_err(crate::result::Error::from)?;
+        let file: File<IoUringDevice> = unsafe { File::from_raw_fd(fd) };
+
+        let sq_len = params.sq_off.array as size_t
+            + (params.sq_entries as size_t) * core::mem::size_of::<u32>();
+        let cq_len = params.cq_off.cqes as size_t
+            + (params.cq_entries as size_t) * core::mem::size_of::<io_uring_cqe>();
+        let single_mmap = (params.features & linux_unsafe::IORING_FEAT_SINGLE_MMAP) != 0;
+
+        let sq_ring = if single_mmap {
+            map_ring(&file, linux_unsafe::IORING_OFF_SQ_RING, sq_len.max(cq_len))?
+        } else {
+            map_ring(&file, linux_unsafe::IORING_OFF_SQ_RING, sq_len)?
+        };
+        let cq_ring = if single_mmap {
+            None
+        } else {
+            Some(map_ring(&file, linux_unsafe::IORING_OFF_CQ_RING, cq_len)?)
+        };
+        let sqes = map_ring(
+            &file,
+            linux_unsafe::IORING_OFF_SQES,
+            (params.sq_entries as size_t) * core::mem::size_of::<io_uring_sqe>(),
+        )?;
+
+        let sq_base = sq_ring.as_ptr() as *mut u8;
+        let cq_base = match &cq_ring {
+            Some(m) => m.as_ptr() as *mut u8,
+            None => sq_base,
+        };
+        let sq = unsafe { SubmissionQueue::new(sq_base, &params, sqes.as_ptr()) };
+        let cq = unsafe { CompletionQueue::new(cq_base, &params) };
+
+        Ok(Self {
+            sq,
+            cq,
+            setup_flags: params.flags,
+            features: params.features,
+            _sq_ring: sq_ring,
+            _cq_ring: cq_ring,
+            _sqes: sqes,
+            file,
+        })
+    }
+
+    /// Returns the file representing the io_uring instance.
+    #[inline(always)]
+    pub fn file(&self) -> &File<IoUringDevice> {
+        &self.file
+    }
+
+    /// Returns the raw `IORING_FEAT_*` flags describing the features that
+    /// the kernel supports for this instance.
+    #[inline(always)]
+    pub fn features(&self) -> u32 {
+        self.features
+    }
+
+    /// Returns the number of entries in the submission queue, which might
+    /// be more than was requested when creating the instance.
+    #[inline(always)]
+    pub fn submission_queue_size(&self) -> u32 {
+        self.sq.entries
+    }
+
+    /// Returns the number of entries in the completion queue.
+    #[inline(always)]
+    pub fn completion_queue_size(&self) -> u32 {
+        self.cq.entries
+    }
+
+    /// Add an entry to the submission queue.
+    ///
+    /// The kernel does not start the operation until the next call to
+    /// [`Self::submit`] or [`Self::submit_and_wait`], unless the instance
+    /// uses [`IoUringOptions::submission_polling`]. Fails with
+    /// [`EBUSY`](crate::result::EBUSY) if the submission queue is full.
+    ///
+    /// Safety: All memory that `entry` refers to, such as buffers and paths,
+    /// must remain valid and must not be accessed in a way that conflicts
+    /// with the operation until its completion has been returned by
+    /// [`Self::pop_completion`].
+    #[inline]
+    pub unsafe fn push(&mut self, entry: &SubmissionEntry) -> Result<()> {
+        unsafe { self.sq.push(&entry.raw) }
+    }
+
+    /// Ask the kernel to start the operations that have been added to the
+    /// submission queue, returning the number that were submitted.
+    #[inline]
+    pub fn submit(&self) -> Result<u32> {
+        self.submit_and_wait(0)
+    }
+
+    /// Ask the kernel to start the operations that have been added to the
+    /// submission queue and then wait until at least `want` completions
+    /// are available, returning the number of entries that were submitted.
+    ///
+    /// When the instance uses [`IoUringOptions::submission_polling`] this
+    /// makes a system call only if the kernel's polling thread has gone idle
+    /// or if `want` is nonzero.
+    pub fn submit_and_wait(&self, want: u32) -> Result<u32> {
+        let to_submit = self.sq.pending();
+        let mut flags: linux_unsafe::uint = 0;
+        if want > 0 {
+            flags |= linux_unsafe::IORING_ENTER_GETEVENTS;
+        }
+        if (self.setup_flags & linux_unsafe::IORING_SETUP_SQPOLL) != 0 {
+            // The tail update must be visible to the polling thread before
+            // we check whether it has gone to sleep.
+            fence(Ordering::SeqCst);
+            if self.sq.needs_wakeup() {
+                flags |= linux_unsafe::IORING_ENTER_SQ_WAKEUP;
+            } else if want == 0 {
+                return Ok(to_submit);
+            }
+        }
+        let result = unsafe {
+            linux_unsafe::io_uring_enter(
+                self.file.fd,
+                to_submit,
+                want,
+                flags,
+                core::ptr::null_mut(),
+            )
+        };
+        result.map(|n| n as u32).map_err(|e| e.into())
+    }
+
+    /// Remove and return the oldest entry from the completion queue, or
+    /// `None` if the completion queue is empty.
+    #[inline]
+    pub fn pop_completion(&mut self) -> Option<Completion> {
+        self.cq.pop().map(|raw| Completion { raw })
+    }
+
+    /// Register buffers for use with [`SubmissionEntry::read_fixed`] and
+    /// [`SubmissionEntry::write_fixed`], which avoids the cost of mapping
+    /// the buffers into the kernel for each operation.
+    ///
+    /// Only one set of buffers can be registered at a time, and the kernel
+    /// limits their total size and the number of buffers.
+    ///
+    /// Safety: The buffers must remain valid until they are unregistered
+    /// using [`Self::unregister_buffers`] or the instance is dropped.
+    #[inline]
+    pub unsafe fn register_buffers(&self, bufs: &[IoSliceMut<'_>]) -> Result<()> {
+        unsafe {
+            self.register_raw(
+                linux_unsafe::IORING_REGISTER_BUFFERS,
+                bufs.as_ptr() as *mut void,
+                bufs.len() as linux_unsafe::uint,
+            )
+        }
+        .map(|_| ())
+    }
+
+    /// Unregister all of the buffers that were registered using
+    /// [`Self::register_buffers`].
+    #[inline]
+    pub fn unregister_buffers(&self) -> Result<()> {
+        unsafe {
+            self.register_raw(
+                linux_unsafe::IORING_UNREGISTER_BUFFERS,
+                core::ptr::null_mut(),
+                0,
+            )
+        }
+        .map(|_| ())
+    }
+
+    /// Register files for use with [`Target::Fixed`], which avoids the cost
+    /// of looking up the file descriptor for each operation.
+    ///
+    /// Each registered file is identified by its index in `fds`. An element
+    /// of `-1` reserves an empty slot that can later be filled using
+    /// [`Self::update_files`]. The instance holds its own reference to each
+    /// file, so the given file descriptors can be closed after registration.
+    #[inline]
+    pub fn register_files(&self, fds: &[int]) -> Result<()> {
+        unsafe {
+            self.register_raw(
+                linux_unsafe::IORING_REGISTER_FILES,
+                fds.as_ptr() as *mut void,
+                fds.len() as linux_unsafe::uint,
+            )
+        }
+        .map(|_| ())
+    }
+
+    /// Replace some of the registered files, starting at index `offset`,
+    /// returning the number of slots that were updated.
+    ///
+    /// An element of `-1` clears the corresponding slot.
+    #[inline]
+    pub fn update_files(&self, offset: u32, fds: &[int]) -> Result<u32> {
+        let mut update = linux_unsafe::io_uring_files_update {
+            offset,
+            resv: 0,
+            fds: fds.as_ptr() as u64,
+        };
+        unsafe {
+            self.register_raw(
+                linux_unsafe::IORING_REGISTER_FILES_UPDATE,
+                &mut update as *mut _ as *mut void,
+                fds.len() as linux_unsafe::uint,
+            )
+        }
+        .map(|n| n as u32)
+    }
+
+    /// Unregister all of the files that were registered using
+    /// [`Self::register_files`].
+    #[inline]
+    pub fn unregister_files(&self) -> Result<()> {
+        unsafe {
+            self.register_raw(
+                linux_unsafe::IORING_UNREGISTER_FILES,
+                core::ptr::null_mut(),
+                0,
+            )
+        }
+        .map(|_| ())
+    }
+
+    /// Call `io_uring_register` with the given raw arguments.
+    ///
+    /// Safety: `arg` and `nr_args` must be valid for the given `opcode`.
+    #[inline]
+    pub unsafe fn register_raw(
+        &self,
+        opcode: linux_unsafe::uint,
+        arg: *mut void,
+        nr_args: linux_unsafe::uint,
+    ) -> Result<int> {
+        let result = unsafe { linux_unsafe::io_uring_register(self.file.fd, opcode, arg, nr_args) };
+        result.map_err(|e| e.into())
+    }
+}
+
+/// Setup flags that would change the layout or mapping of the rings in ways
+/// that [`IoUring`] does not support.
+const UNSUPPORTED_SETUP_FLAGS: u32 = linux_unsafe::IORING_SETUP_SQE128
+    | linux_unsafe::IORING_SETUP_CQE32
+    | linux_unsafe::IORING_SETUP_NO_MMAP
+    | linux_unsafe::IORING_SETUP_REGISTERED_FD_ONLY
+    | linux_unsafe::IORING_SETUP_NO_SQARRAY;
+
+fn map_ring(
+    file: &File<IoUringDevice>,
+    offset: linux_unsafe::off_t,
+    len: size_t,
+) -> Result<Mapping> {
+    let prot = linux_unsafe::PROT_READ | linux_unsafe::PROT_WRITE;
+    let flags = linux_unsafe::MAP_SHARED | linux_unsafe::MAP_POPULATE;
+    let addr = unsafe { file.mmap_raw(offset, len, core::ptr::null_mut(), prot, flags) }?;
+    Ok(unsafe { Mapping::from_raw_parts(addr, len, prot) })
+}
+
+/// Pointers into the mapped submission queue ring.
+#[derive(Debug)]
+struct SubmissionQueue {
+    head: *const AtomicU32,
+    tail: *const AtomicU32,
+    flags: *const AtomicU32,
+    mask: u32,
+    entries: u32,
+    sqes: *mut io_uring_sqe,
+    // Only this process writes the tail, so we track it locally.
+    local_tail: u32,
+}
+
+impl SubmissionQueue {
+    unsafe fn new(base: *mut u8, params: &linux_unsafe::io_uring_params, sqes: *mut void) -> Self {
+        let off = &params.sq_off;
+        let ptr = |offset: u32| unsafe { base.add(offset as usize) };
+        let mask = unsafe { *(ptr(off.ring_mask) as *const u32) };
+        let entries = unsafe { *(ptr(off.ring_entries) as *const u32) };
+        let tail = ptr(off.tail) as *const AtomicU32;
+
+        // We always fill the submission queue entries in order, so the
+        // indirection array just maps each slot to itself.
+        let array = ptr(off.array) as *mut u32;
+        for i in 0..entries {
+            unsafe { array.add(i as usize).write(i) };
+        }
+
+        Self {
+            head: ptr(off.head) as *const AtomicU32,
+            tail,
+            flags: ptr(off.flags) as *const AtomicU32,
+            mask,
+            entries,
+            sqes: sqes as *mut io_uring_sqe,
+            local_tail: unsafe { (*tail).load(Ordering::Relaxed) },
+        }
+    }
+
+    unsafe fn push(&mut self, entry: &io_uring_sqe) -> Result<()> {
+        let head = unsafe { (*self.head).load(Ordering::Acquire) };
+        if self.local_tail.wrapping_sub(head) >= self.entries {
+            return Err(crate::result::EBUSY);
+        }
+        let idx = (self.local_tail & self.mask) as usize;
+        unsafe { self.sqes.add(idx).write(*entry) };
+        self.local_tail = self.local_tail.wrapping_add(1);
+        // The release ordering makes the entry visible to the kernel
+        // before the new tail.
+        unsafe { (*self.tail).store(self.local_tail, Ordering::Release) };
+        Ok(())
+    }
+
+    fn pending(&self) -> u32 {
+        let head = unsafe { (*self.head).load(Ordering::Acquire) };
+        self.local_tail.wrapping_sub(head)
+    }
+
+    fn needs_wakeup(&self) -> bool {
+        let flags = unsafe { (*self.flags).load(Ordering::Relaxed) };
+        (flags & linux_unsafe::IORING_SQ_NEED_WAKEUP) != 0
+    }
+}
+
+/// Pointers into the mapped completion queue ring.
+#[derive(Debug)]
+struct CompletionQueue {
+    head: *const AtomicU32,
+    tail: *const AtomicU32,
+    mask: u32,
+    entries: u32,
+    cqes: *const io_uring_cqe,
+    // Only this process writes the head, so we track it locally.
+    local_head: u32,
+}
+
+impl CompletionQueue {
+    unsafe fn new(base: *mut u8, params: &linux_unsafe::io_uring_params) -> Self {
+        let off = &params.cq_off;
+        let ptr = |offset: u32| unsafe { base.add(offset as usize) };
+        let head = ptr(off.head) as *const AtomicU32;
+        Self {
+            head,
+            tail: ptr(off.tail) as *const AtomicU32,
+            mask: unsafe { *(ptr(off.ring_mask) as *const u32) },
+            entries: unsafe { *(ptr(off.ring_entries) as *const u32) },
+            cqes: ptr(off.cqes) as *const io_uring_cqe,
+            local_head: unsafe { (*head).load(Ordering::Relaxed) },
+        }
+    }
+
+    fn pop(&mut self) -> Option<io_uring_cqe> {
+        // The acquire ordering makes the entries visible to us before we
+        // read them.
+        let tail = unsafe { (*self.tail).load(Ordering::Acquire) };
+        if self.local_head == tail {
+            return None;
+        }
+        let idx = (self.local_head & self.mask) as usize;
+        let cqe = unsafe { self.cqes.add(idx).read() };
+        self.local_head = self.local_head.wrapping_add(1);
+        // The release ordering prevents the kernel from overwriting the
+        // entry before we've finished reading it.
+        unsafe { (*self.head).store(self.local_head, Ordering::Release) };
+        Some(cqe)
+    }
+}
+
+/// Options for creating an [`IoUring`] using [`IoUring::with_options`].
+///
+/// Start with [`IoUringOptions::new`] and then use the other methods to set
+/// additional options.
+#[derive(Clone, Copy, Debug)]
+pub struct IoUringOptions {
+    flags: u32,
+    cq_entries: u32,
+    sq_thread_cpu: u32,
+    sq_thread_idle: u32,
+}
+
+impl IoUringOptions {
+    /// The default options.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            flags: 0,
+            cq_entries: 0,
+            sq_thread_cpu: 0,
+            sq_thread_idle: 0,
+        }
+    }
+
+    /// Request a completion queue with at least the given number of
+    /// entries, instead of the default of twice the submission queue size.
+    #[inline(always)]
+    pub const fn completion_queue_size(mut self, entries: u32) -> Self {
+        self.flags |= linux_unsafe::IORING_SETUP_CQSIZE;
+        self.cq_entries = entries;
+        self
+    }
+
+    /// Reduce queue sizes that are larger than the kernel's maximum,
+    /// instead of failing.
+    #[inline(always)]
+    pub const fn clamp(mut self) -> Self {
+        self.flags |= linux_unsafe::IORING_SETUP_CLAMP;
+        self
+    }
+
+    /// Use busy-polling for completions rather than interrupts. Only
+    /// supported for files opened with `O_DIRECT` on devices that support
+    /// polling.
+    #[inline(always)]
+    pub const fn io_polling(mut self) -> Self {
+        self.flags |= linux_unsafe::IORING_SETUP_IOPOLL;
+        self
+    }
+
+    /// Create a kernel thread that polls the submission queue, so that
+    /// new entries can be submitted without a system call.
+    ///
+    /// The thread goes to sleep after it has been idle for the given
+    /// duration, which is rounded down to a whole number of milliseconds,
+    /// after which [`IoUring::submit`] wakes it again. Older kernels require
+    /// the `CAP_SYS_NICE` capability for this option.
+    #[inline(always)]
+    pub const fn submission_polling(mut self, idle: Duration) -> Self {
+        let ms = idle.as_millis();
+        self.flags |= linux_unsafe::IORING_SETUP_SQPOLL;
+        self.sq_thread_idle = if ms > (u32::MAX as u128) {
+            u32::MAX
+        } else {
+            ms as u32
+        };
+        self
+    }
+
+    /// Bind the submission queue polling thread to the given CPU. Only
+    /// valid with [`Self::submission_polling`].
+    #[inline(always)]
+    pub const fn submission_polling_cpu(mut self, cpu: u32) -> Self {
+        self.flags |= linux_unsafe::IORING_SETUP_SQ_AFF;
+        self.sq_thread_cpu = cpu;
+        self
+    }
+
+    /// Add raw `IORING_SETUP_*` flags that have no dedicated method.
+    ///
+    /// Flags that change the size of the queue entries or how the rings
+    /// are mapped, such as `IORING_SETUP_SQE128`, are not supported and
+    /// make [`IoUring::with_options`] fail with
+    /// [`EINVAL`](crate::result::EINVAL).
+    #[inline(always)]
+    pub const fn raw_flags(mut self, flags: u32) -> Self {
+        self.flags |= flags;
+        self
+    }
+}
+
+impl Default for IoUringOptions {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Identifies the file that a [`SubmissionEntry`] operates on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Target {
+    /// A file descriptor.
+    Fd(int),
+    /// The index of a file registered using [`IoUring::register_files`].
+    Fixed(u32),
+}
+
+impl<Device> From<&File<Device>> for Target {
+    #[inline(always)]
+    fn from(file: &File<Device>) -> Self {
+        Self::Fd(file.fd)
+    }
+}
+
+/// An operation to add to the submission queue using [`IoUring::push`].
+///
+/// This has the same memory layout as the kernel's `io_uring_sqe` type.
+#[derive(Clone, Copy, Debug)]
+#[repr(transparent)]
+pub struct SubmissionEntry {
+    raw: io_uring_sqe,
+}
+
+impl SubmissionEntry {
+    /// Wrap a raw submission queue entry.
+    #[inline(always)]
+    pub const fn from_raw(raw: io_uring_sqe) -> Self {
+        Self { raw }
+    }
+
+    /// Returns the raw submission queue entry.
+    #[inline(always)]
+    pub const fn raw(&self) -> &io_uring_sqe {
+        &self.raw
+    }
+
+    #[inline(always)]
+    fn new(opcode: u8, target: Target) -> Self {
+        let mut raw = io_uring_sqe {
+            opcode,
+            ..Default::default()
+        };
+        match target {
+            Target::Fd(fd) => raw.fd = fd,
+            Target::Fixed(idx) => {
+                raw.fd = idx as i32;
+                raw.flags |= linux_unsafe::IOSQE_FIXED_FILE;
+            }
+        }
+        Self { raw }
+    }
+
+    #[inline(always)]
+    fn with_buf(mut self, ptr: *const u8, len: usize) -> Self {
+        self.raw.addr = ptr as u64;
+        self.raw.len = len as u32;
+        self
+    }
+
+    /// An operation that does nothing except produce a completion.
+    #[inline]
+    pub fn nop() -> Self {
+        Self::new(linux_unsafe::IORING_OP_NOP, Target::Fd(-1))
+    }
+
+    /// Read from the file at the given offset into `buf`, like
+    /// [`File::read_at`].
+    ///
+    /// Use [`CURRENT_POSITION`] as the offset to read from and advance the
+    /// file's current position instead, like [`File::read`].
+    #[inline]
+    pub fn read(target: impl Into<Target>, buf: &mut [u8], offset: u64) -> Self {
+        let mut ret = Self::new(linux_unsafe::IORING_OP_READ, target.into())
+            .with_buf(buf.as_mut_ptr(), buf.len());
+        ret.raw.off = offset;
+        ret
+    }
+
+    /// Write `buf` to the file at the given offset, like [`File::write_at`].
+    ///
+    /// Use [`CURRENT_POSITION`] as the offset to write at and advance the
+    /// file's current position instead, like [`File::write`].
+    #[inline]
+    pub fn write(target: impl Into<Target>, buf: &[u8], offset: u64) -> Self {
+        let mut ret = Self::new(linux_unsafe::IORING_OP_WRITE, target.into())
+            .with_buf(buf.as_ptr(), buf.len());
+        ret.raw.off = offset;
+        ret
+    }
+
+    /// Read from the file at the given offset into several buffers, like
+    /// [`File::read_vectored_at`].
+    #[inline]
+    pub fn readv(target: impl Into<Target>, bufs: &mut [IoSliceMut<'_>], offset: u64) -> Self {
+        let mut ret = Self::new(linux_unsafe::IORING_OP_READV, target.into())
+            .with_buf(bufs.as_mut_ptr() as *const u8, bufs.len());
+        ret.raw.off = offset;
+        ret
+    }
+
+    /// Write several buffers to the file at the given offset, like
+    /// [`File::write_vectored_at`].
+    #[inline]
+    pub fn writev(target: impl Into<Target>, bufs: &[IoSlice<'_>], offset: u64) -> Self {
+        let mut ret = Self::new(linux_unsafe::IORING_OP_WRITEV, target.into())
+            .with_buf(bufs.as_ptr() as *const u8, bufs.len());
+        ret.raw.off = offset;
+        ret
+    }
+
+    /// Like [`Self::read`], but `buf` must be within the buffer at index
+    /// `buf_index` of those registered using [`IoUring::register_buffers`].
+    #[inline]
+    pub fn read_fixed(
+        target: impl Into<Target>,
+        buf: &mut [u8],
+        offset: u64,
+        buf_index: u16,
+    ) -> Self {
+        let mut ret = Self::new(linux_unsafe::IORING_OP_READ_FIXED, target.into())
+            .with_buf(buf.as_mut_ptr(), buf.len());
+        ret.raw.off = offset;
+        ret.raw.buf_index = buf_index;
+        ret
+    }
+
+    /// Like [`Self::write`], but `buf` must be within the buffer at index
+    /// `buf_index` of those registered using [`IoUring::register_buffers`].
+    #[inline]
+    pub fn write_fixed(target: impl Into<Target>, buf: &[u8], offset: u64, buf_index: u16) -> Self {
+        let mut ret = Self::new(linux_unsafe::IORING_OP_WRITE_FIXED, target.into())
+            .with_buf(buf.as_ptr(), buf.len());
+        ret.raw.off = offset;
+        ret.raw.buf_index = buf_index;
+        ret
+    }
+
+    /// Flush the file's data and metadata to its storage device, like
+    /// [`File::sync`].
+    #[inline]
+    pub fn fsync(target: impl Into<Target>) -> Self {
+        Self::new(linux_unsafe::IORING_OP_FSYNC, target.into())
+    }
+
+    /// Flush the file's data to its storage device, along with only the
+    /// metadata needed to read that data back.
+    #[inline]
+    pub fn fdatasync(target: impl Into<Target>) -> Self {
+        let mut ret = Self::new(linux_unsafe::IORING_OP_FSYNC, target.into());
+        ret.raw.op_flags = linux_unsafe::IORING_FSYNC_DATASYNC;
+        ret
+    }
+
+    /// Accept a connection on a listening socket, discarding the peer
+    /// address. The result is the new socket's file descriptor.
+    ///
+    /// `flags` can include `SOCK_CLOEXEC` and `SOCK_NONBLOCK`, as for
+    /// the `accept4` system call.
+    #[inline]
+    pub fn accept(target: impl Into<Target>, flags: int) -> Self {
+        let mut ret = Self::new(linux_unsafe::IORING_OP_ACCEPT, target.into());
+        ret.raw.op_flags = flags as u32;
+        ret
+    }
+
+    /// Connect a socket to the given address, like [`File::connect`].
+    #[inline]
+    pub fn connect(target: impl Into<Target>, addr: &impl crate::socket::SockAddr) -> Self {
+        let (raw_ptr, raw_len) = unsafe { addr.sockaddr_raw_const() };
+        let mut ret = Self::new(linux_unsafe::IORING_OP_CONNECT, target.into());
+        ret.raw.addr = raw_ptr as u64;
+        ret.raw.off = raw_len as u64;
+        ret
+    }
+
+    /// Send data on a connected socket, using the given `MSG_*` flags.
+    #[inline]
+    pub fn send(target: impl Into<Target>, buf: &[u8], flags: int) -> Self {
+        let mut ret = Self::new(linux_unsafe::IORING_OP_SEND, target.into())
+            .with_buf(buf.as_ptr(), buf.len());
+        ret.raw.op_flags = flags as u32;
+        ret
+    }
+
+    /// Receive data from a socket, using the given `MSG_*` flags.
+    #[inline]
+    pub fn recv(target: impl Into<Target>, buf: &mut [u8], flags: int) -> Self {
+        let mut ret = Self::new(linux_unsafe::IORING_OP_RECV, target.into())
+            .with_buf(buf.as_mut_ptr(), buf.len());
+        ret.raw.op_flags = flags as u32;
+        ret
+    }
+
+    /// An operation that completes when the given time has passed, or when
+    /// `count` other operations have completed if `count` is nonzero.
+    ///
+    /// When the time passes first the result is
+    /// [`ETIME`](crate::result::ETIME). Use
+    /// [`crate::time::kernel_timespec_from_duration`] to prepare `ts`.
+    #[inline]
+    pub fn timeout(ts: &linux_unsafe::kernel_timespec, count: u32, flags: TimeoutFlags) -> Self {
+        let mut ret = Self::new(linux_unsafe::IORING_OP_TIMEOUT, Target::Fd(-1))
+            .with_buf(ts as *const _ as *const u8, 1);
+        ret.raw.off = count as u64;
+        ret.raw.op_flags = flags.raw();
+        ret
+    }
+
+    /// Open a file relative to the given directory, like
+    /// [`File::open_relative`]. The result is the new file descriptor.
+    #[inline]
+    pub fn openat<Device>(
+        dir: &File<Device>,
+        path: &CStr,
+        options: OpenOptions<OpenWithoutMode>,
+    ) -> Self {
+        Self::openat_raw(dir, path, options.into_raw_flags(), 0)
+    }
+
+    /// Open a file relative to the given directory, like
+    /// [`File::open_relative_with_mode`]. The result is the new file
+    /// descriptor.
+    #[inline]
+    pub fn openat_with_mode<Device>(
+        dir: &File<Device>,
+        path: &CStr,
+        options: OpenOptions<OpenWithMode>,
+        mode: linux_unsafe::mode_t,
+    ) -> Self {
+        Self::openat_raw(dir, path, options.into_raw_flags(), mode)
+    }
+
+    #[inline(always)]
+    fn openat_raw<Device>(
+        dir: &File<Device>,
+        path: &CStr,
+        flags: int,
+        mode: linux_unsafe::mode_t,
+    ) -> Self {
+        let mut ret = Self::new(linux_unsafe::IORING_OP_OPENAT, Target::from(dir));
+        ret.raw.addr = path.as_ptr() as u64;
+        ret.raw.len = mode;
+        ret.raw.op_flags = flags as u32;
+        ret
+    }
+
+    /// Close the given file.
+    ///
+    /// The file descriptor is leaked if the entry is never submitted.
+    #[inline]
+    pub fn close<Device>(file: File<Device>) -> Self {
+        Self::new(
+            linux_unsafe::IORING_OP_CLOSE,
+            Target::Fd(file.into_raw_fd()),
+        )
+    }
+
+    /// Clear the slot at the given index of the files registered using
+    /// [`IoUring::register_files`].
+    #[inline]
+    pub fn close_fixed(index: u32) -> Self {
+        let mut ret = Self::new(linux_unsafe::IORING_OP_CLOSE, Target::Fd(0));
+        ret.raw.splice_fd_in = index.wrapping_add(1) as i32;
+        ret
+    }
+
+    /// Retrieve metadata about a file relative to the given directory into
+    /// `buf`, like [`File::metadata_relative_raw`].
+    ///
+    /// Use [`crate::fd::Metadata::from_raw`] to interpret the result.
+    #[inline]
+    pub fn statx<Device>(
+        dir: &File<Device>,
+        path: &CStr,
+        flags: int,
+        mask: u32,
+        buf: &mut linux_unsafe::statx,
+    ) -> Self {
+        let mut ret = Self::new(linux_unsafe::IORING_OP_STATX, Target::from(dir));
+        ret.raw.addr = path.as_ptr() as u64;
+        ret.raw.len = mask;
+        ret.raw.off = buf as *mut _ as u64;
+        ret.raw.op_flags = flags as u32;
+        ret
+    }
+
+    /// Set the value that will be returned in the operation's completion.
+    #[inline(always)]
+    pub const fn with_user_data(mut self, user_data: u64) -> Self {
+        self.raw.user_data = user_data;
+        self
+    }
+
+    /// Add the given flags to the entry.
+    #[inline(always)]
+    pub const fn with_flags(mut self, flags: EntryFlags) -> Self {
+        self.raw.flags |= flags.0;
+        self
+    }
+}
+
+flags_type! {
+    /// Flags for use with [`SubmissionEntry::with_flags`].
+    pub struct EntryFlags(u8);
+
+    /// No flags.
+    const NONE = 0;
+    /// Don't start the operation until all earlier operations have
+    /// completed, and don't start later operations until it has completed.
+    const DRAIN = linux_unsafe::IOSQE_IO_DRAIN;
+    /// Don't start the next entry until this one has completed
+    /// successfully. If this one fails then the next one is cancelled.
+    const LINK = linux_unsafe::IOSQE_IO_LINK;
+    /// Like [`Self::LINK`], but the next entry starts even if this one
+    /// fails.
+    const HARDLINK = linux_unsafe::IOSQE_IO_HARDLINK;
+    /// Always perform the operation in a kernel worker thread, rather than
+    /// first trying to complete it without blocking.
+    const ASYNC = linux_unsafe::IOSQE_ASYNC;
+    /// Don't produce a completion if the operation succeeds.
+    const SKIP_SUCCESS = linux_unsafe::IOSQE_CQE_SKIP_SUCCESS;
+}
+
+flags_type! {
+    /// Flags for [`SubmissionEntry::timeout`].
+    pub struct TimeoutFlags(u32);
+
+    /// No flags, so the timeout is relative to the current time on the
+    /// monotonic clock.
+    const NONE = 0;
+    /// Treat the timeout as an absolute time rather than a duration.
+    const ABSTIME = linux_unsafe::IORING_TIMEOUT_ABS;
+    /// Measure the timeout using the boot time clock.
+    const BOOTTIME = linux_unsafe::IORING_TIMEOUT_BOOTTIME;
+    /// Measure the timeout using the realtime clock.
+    const REALTIME = linux_unsafe::IORING_TIMEOUT_REALTIME;
+    /// Report expiry of the timeout as success rather than as
+    /// [`ETIME`](crate::result::ETIME).
+    const ETIME_SUCCESS = linux_unsafe::IORING_TIMEOUT_ETIME_SUCCESS;
+}
+
+/// The result of an operation, returned by [`IoUring::pop_completion`].
+#[derive(Clone, Copy, Debug)]
+#[repr(transparent)]
+pub struct Completion {
+    raw: io_uring_cqe,
+}
+
+impl Completion {
+    /// Returns the `user_data` value of the operation's submission entry.
+    #[inline(always)]
+    pub const fn user_data(&self) -> u64 {
+        self.raw.user_data
+    }
+
+    /// Returns the result of the operation, whose meaning depends on the
+    /// kind of operation.
+    #[inline(always)]
+    pub fn result(&self) -> Result<u32> {
+        if self.raw.res < 0 {
+            Err(crate::result::Error::new(-self.raw.res))
+        } else {
+            Ok(self.raw.res as u32)
+        }
+    }
+
+    /// Returns the raw `IORING_CQE_F_*` flags of the completion.
+    #[inline(always)]
+    pub const fn flags(&self) -> u32 {
+        self.raw.flags
+    }
+
+    /// Returns the raw completion queue entry.
+    #[inline(always)]
+    pub const fn raw(&self) -> &io_uring_cqe {
+        &self.raw
+    }
+}
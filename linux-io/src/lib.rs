@@ -21,6 +21,9 @@
 #![no_std]
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 
+#[macro_use]
+mod flags;
+
 /// Access to the "poll" system call.
 pub mod poll;
 
@@ -39,10 +42,53 @@ pub mod tty;
 
 /// Socket address manipulation, socket device ioctls, etc.
 pub mod socket;
+pub use socket::socketpair;
 
 /// Synchronization primitives built using Linux kernel features.
 pub mod sync;
 
+/// Creating, signalling, and waiting for child processes using pidfds.
+pub mod process;
+
+/// Accepting signals through file descriptors.
+pub mod signal;
+
+/// Waiting for events on many files at once, using epoll.
+pub mod epoll;
+
+/// Event notification counters, using eventfd.
+pub mod eventfd;
+pub use eventfd::EventFd;
+
+/// Timers that notify through file descriptors, using timerfd.
+pub mod timerfd;
+pub use timerfd::TimerFd;
+
+/// Watching for changes to files and directories, using inotify.
+pub mod inotify;
+pub use inotify::Inotify;
+
+/// Filesystem event monitoring and access control, using fanotify.
+pub mod fanotify;
+pub use fanotify::Fanotify;
+
+/// Asynchronous I/O using shared submission and completion queues, with io_uring.
+pub mod io_uring;
+pub use io_uring::IoUring;
+
+/// Clocks, sleeping, and monotonic timestamps.
+pub mod time;
+
+/// Pipes, and the operations that the kernel supports only for pipes.
+pub mod pipe;
+pub use pipe::pipe;
+
+/// Memory mappings and related memory management.
+pub mod mem;
+
+/// Access to a program's arguments, environment, and auxiliary vector.
+pub mod startup;
+
 /// For safely representing pointers in `ioctl` request types, and similar.
 pub mod ptr;
 
@@ -0,0 +1,289 @@
+use linux_unsafe::{int, size_t, void};
+
+use crate::result::Result;
+use crate::File;
+
+/// A region of memory mapped into the address space of the current process
+/// using the `mmap` system call, which is unmapped when dropped.
+///
+/// Create an anonymous mapping using [`Mapping::anonymous`], or map a file
+/// into memory using [`File::mmap`].
+#[derive(Debug)]
+pub struct Mapping {
+    addr: *mut void,
+    len: size_t,
+    prot: int,
+}
+
+// Safety: A mapping is just a region of memory, and so has the same
+// thread-safety characteristics as a heap-allocated buffer.
+unsafe impl Send for Mapping {}
+unsafe impl Sync for Mapping {}
+
+impl Mapping {
+    /// Create a new anonymous mapping of the given length, whose contents are
+    /// initially zero.
+    ///
+    /// Unless `options` includes [`MapOptions::shared`], the new memory is
+    /// private to this process, and any child processes receive copy-on-write
+    /// copies of it.
+    #[inline]
+    pub fn anonymous(len: size_t, options: MapOptions) -> Result<Self> {
+        let flags = options.flags | linux_unsafe::MAP_ANONYMOUS;
+        let result =
+            unsafe { linux_unsafe::mmap(core::ptr::null_mut(), len, options.prot, flags, -1, 0) };
+        result
+            .map(|addr| unsafe { Self::from_raw_parts(addr, len, options.prot) })
+            .map_err(|e| e.into())
+    }
+
+    /// Wrap a mapping previously created by some other means, so that it
+    /// will be unmapped when the result is dropped.
+    ///
+    /// Safety: `addr` and `len` must describe exactly one mapping created
+    /// with `mmap` whose protection flags are `prot`, and nothing else may
+    /// unmap it or change its protection flags while the result is live.
+    #[inline(always)]
+    pub const unsafe fn from_raw_parts(addr: *mut void, len: size_t, prot: int) -> Self {
+        Self { addr, len, prot }
+    }
+
+    /// Consume the mapping without unmapping it, returning its address and
+    /// length.
+    #[inline(always)]
+    pub fn into_raw_parts(self) -> (*mut void, size_t) {
+        let ret = (self.addr, self.len);
+        core::mem::forget(self);
+        ret
+    }
+
+    /// Returns a pointer to the start of the mapping.
+    #[inline(always)]
+    pub const fn as_ptr(&self) -> *mut void {
+        self.addr
+    }
+
+    /// Returns the length of the mapping in bytes.
+    #[inline(always)]
+    pub const fn len(&self) -> size_t {
+        self.len
+    }
+
+    /// Returns true if the mapping has zero length.
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns a view of the mapping as a slice.
+    ///
+    /// Panics if the mapping is not readable.
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        assert!(
+            (self.prot & linux_unsafe::PROT_READ) != 0,
+            "mapping is not readable"
+        );
+        unsafe { core::slice::from_raw_parts(self.addr as *const u8, self.len) }
+    }
+
+    /// Returns a view of the mapping as a mutable slice.
+    ///
+    /// Panics if the mapping is not both readable and writable.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        let want = linux_unsafe::PROT_READ | linux_unsafe::PROT_WRITE;
+        assert!(
+            (self.prot & want) == want,
+            "mapping is not readable and writable"
+        );
+        unsafe { core::slice::from_raw_parts_mut(self.addr as *mut u8, self.len) }
+    }
+
+    /// Change the access protections of the whole mapping.
+    #[inline]
+    pub fn protect(&mut self, options: MapOptions) -> Result<()> {
+        let result = unsafe { linux_unsafe::mprotect(self.addr, self.len, options.prot) };
+        result
+            .map(|_| self.prot = options.prot)
+            .map_err(|e| e.into())
+    }
+
+    /// Give the kernel advice about how the mapping will be used, using one
+    /// of the `MADV_*` constants from [`linux_unsafe`].
+    ///
+    /// Safety: Some advice values, such as `MADV_DONTNEED` and `MADV_FREE`,
+    /// change the contents of the mapping, and so the caller must ensure
+    /// that's acceptable for the current use of the memory.
+    #[inline]
+    pub unsafe fn advise_raw(&self, advice: int) -> Result<()> {
+        let result = unsafe { linux_unsafe::madvise(self.addr, self.len, advice) };
+        result.map(|_| ()).map_err(|e| e.into())
+    }
+
+    /// Lock the mapping into memory, so that it cannot be paged out.
+    #[inline]
+    pub fn lock(&self) -> Result<()> {
+        let result = unsafe { linux_unsafe::mlock2(self.addr, self.len, 0) };
+        result.map(|_| ()).map_err(|e| e.into())
+    }
+
+    /// Undo the effect of [`Self::lock`].
+    #[inline]
+    pub fn unlock(&self) -> Result<()> {
+        let result = unsafe { linux_unsafe::munlock(self.addr, self.len) };
+        result.map(|_| ()).map_err(|e| e.into())
+    }
+
+    /// Write any modifications of a shared file-backed mapping back to the
+    /// underlying file, blocking until complete.
+    #[inline]
+    pub fn sync(&self) -> Result<()> {
+        let result = unsafe { linux_unsafe::msync(self.addr, self.len, linux_unsafe::MS_SYNC) };
+        result.map(|_| ()).map_err(|e| e.into())
+    }
+}
+
+impl Drop for Mapping {
+    /// Unmaps the memory when the mapping is no longer in scope.
+    ///
+    /// This implicit unmap ignores errors, which can occur only if the
+    /// mapping was somehow already unmapped.
+    #[allow(unused_must_use)] // intentionally discarding munmap result
+    fn drop(&mut self) {
+        unsafe { linux_unsafe::munmap(self.addr, self.len) };
+    }
+}
+
+impl<Device> File<Device> {
+    /// Map part of the file into memory.
+    ///
+    /// Safety: If the mapping is shared and the file is modified by some
+    /// other means while the mapping is live, the contents of slices
+    /// previously returned by [`Mapping::as_slice`] will change unexpectedly.
+    /// Accessing parts of the mapping beyond the end of the file causes
+    /// the process to receive `SIGBUS`. The caller must prevent both of
+    /// these situations.
+    #[inline]
+    pub unsafe fn mmap(
+        &self,
+        offset: linux_unsafe::off_t,
+        len: size_t,
+        options: MapOptions,
+    ) -> Result<Mapping> {
+        let addr = unsafe {
+            self.mmap_raw(
+                offset,
+                len,
+                core::ptr::null_mut(),
+                options.prot,
+                options.flags,
+            )
+        }?;
+        Ok(unsafe { Mapping::from_raw_parts(addr, len, options.prot) })
+    }
+}
+
+/// Options for creating a [`Mapping`].
+///
+/// Start with [`MapOptions::read_only`] or [`MapOptions::read_write`] and
+/// then use the other methods to set additional options.
+#[derive(Clone, Copy, Debug)]
+pub struct MapOptions {
+    prot: int,
+    flags: int,
+}
+
+impl MapOptions {
+    /// Options for a private read-only mapping.
+    #[inline(always)]
+    pub const fn read_only() -> Self {
+        Self {
+            prot: linux_unsafe::PROT_READ,
+            flags: linux_unsafe::MAP_PRIVATE,
+        }
+    }
+
+    /// Options for a private readable and writable mapping.
+    #[inline(always)]
+    pub const fn read_write() -> Self {
+        Self {
+            prot: linux_unsafe::PROT_READ | linux_unsafe::PROT_WRITE,
+            flags: linux_unsafe::MAP_PRIVATE,
+        }
+    }
+
+    /// Options for a mapping that cannot be accessed at all, such as for
+    /// a guard region.
+    #[inline(always)]
+    pub const fn no_access() -> Self {
+        Self {
+            prot: linux_unsafe::PROT_NONE,
+            flags: linux_unsafe::MAP_PRIVATE,
+        }
+    }
+
+    /// Make the mapping executable.
+    #[inline(always)]
+    pub const fn executable(mut self) -> Self {
+        self.prot |= linux_unsafe::PROT_EXEC;
+        self
+    }
+
+    /// Share the mapping with other processes, so that modifications are
+    /// visible to any other mapping of the same file and are written back
+    /// to the file, or are visible to child processes for an anonymous
+    /// mapping.
+    #[inline(always)]
+    pub const fn shared(mut self) -> Self {
+        self.flags = (self.flags & !linux_unsafe::MAP_PRIVATE) | linux_unsafe::MAP_SHARED;
+        self
+    }
+
+    /// Populate the page tables for the mapping immediately, rather than on
+    /// first access.
+    #[inline(always)]
+    pub const fn populate(mut self) -> Self {
+        self.flags |= linux_unsafe::MAP_POPULATE;
+        self
+    }
+
+    /// Lock the pages of the mapping into memory.
+    #[inline(always)]
+    pub const fn locked(mut self) -> Self {
+        self.flags |= linux_unsafe::MAP_LOCKED;
+        self
+    }
+
+    /// Don't reserve swap space for the mapping.
+    #[inline(always)]
+    pub const fn no_reserve(mut self) -> Self {
+        self.flags |= linux_unsafe::MAP_NORESERVE;
+        self
+    }
+
+    /// Use the system's default huge page size for the mapping.
+    ///
+    /// Huge pages must be preallocated by the system administrator, and the
+    /// mapping length must be a multiple of the huge page size.
+    #[inline(always)]
+    pub const fn huge_pages(mut self) -> Self {
+        self.flags |= linux_unsafe::MAP_HUGETLB;
+        self
+    }
+
+    /// Use a specific huge page size for the mapping, given as the base-2
+    /// logarithm of the page size in bytes, such as 21 for 2MiB pages.
+    #[inline(always)]
+    pub const fn huge_page_size_log2(mut self, log2: u8) -> Self {
+        self.flags |= linux_unsafe::MAP_HUGETLB
+            | (((log2 as int) & linux_unsafe::MAP_HUGE_MASK) << linux_unsafe::MAP_HUGE_SHIFT);
+        self
+    }
+
+    /// Returns the raw `prot` and `flags` arguments for `mmap`.
+    #[inline(always)]
+    pub const fn raw(&self) -> (int, int) {
+        (self.prot, self.flags)
+    }
+}
@@ -0,0 +1,199 @@
+//! The two ends of a pipe have different device types, so that operations
+//! which require a particular end of a pipe, such as `splice` and `tee`,
+//! can be restricted to the correct kind of file at compile time.
+
+use crate::fd::ioctl::{ioctl_read, IoDevice, IoctlReqRead, SubDevice};
+use crate::fd::{raw_offset_ptr, transfer_all, IoSlice, SpliceFlags};
+use crate::result::Result;
+use crate::File;
+
+/// Create a new pipe, returning its read end and its write end.
+///
+/// `flags` is a combination of `O_CLOEXEC`, `O_NONBLOCK`, and `O_DIRECT`
+/// from [`linux_unsafe`]. `O_DIRECT` creates a pipe in "packet mode", where
+/// each write is a separate packet and each read consumes at most one
+/// packet.
+#[inline]
+pub fn pipe(flags: linux_unsafe::int) -> Result<(File<PipeReader>, File<PipeWriter>)> {
+    let mut fds: [linux_unsafe::int; 2] = [-1, -1];
+    let result = unsafe { linux_unsafe::pipe2(fds.as_mut_ptr(), flags) };
+    result
+        .map(|_| unsafe { (File::from_raw_fd(fds[0]), File::from_raw_fd(fds[1])) })
+        .map_err(|e| e.into())
+}
+
+/// A marker type for [`File`] objects that represent either end of a pipe.
+///
+/// The `ioctl` requests for this device are available for both
+/// [`PipeReader`] and [`PipeWriter`] files.
+#[derive(Clone, Copy, Debug)]
+pub struct PipeDevice;
+
+impl IoDevice for PipeDevice {}
+
+/// A marker type for [`File`] objects that represent the read end of a pipe.
+#[derive(Clone, Copy, Debug)]
+pub struct PipeReader;
+
+impl IoDevice for PipeReader {}
+unsafe impl SubDevice<PipeDevice> for PipeReader {}
+
+/// A marker type for [`File`] objects that represent the write end of a pipe.
+#[derive(Clone, Copy, Debug)]
+pub struct PipeWriter;
+
+impl IoDevice for PipeWriter {}
+unsafe impl SubDevice<PipeDevice> for PipeWriter {}
+
+/// `ioctl` request for retrieving the number of bytes currently waiting to
+/// be read from a pipe.
+// NOTE: This ioctl number isn't valid for all Linux architectures, but is valid
+// for all of the ones linux-unsafe supports at the time of writing.
+pub const FIONREAD: IoctlReqRead<PipeDevice, linux_unsafe::int> = unsafe { ioctl_read(0x541B) };
+
+impl File<PipeReader> {
+    /// Move up to `len` bytes from the pipe to another file using the
+    /// `splice` system call, without passing the data through userspace.
+    ///
+    /// If `off_out` is `None` then the other file's current read/write
+    /// position is used and updated. Otherwise the given offset is used and
+    /// updated, and the file's current position is unchanged. The offset
+    /// must be `None` if the other file is also a pipe.
+    #[inline]
+    pub fn splice_to<OutDevice>(
+        &self,
+        out: &File<OutDevice>,
+        off_out: Option<&mut u64>,
+        len: usize,
+        flags: SpliceFlags,
+    ) -> Result<usize> {
+        let result = unsafe {
+            linux_unsafe::splice(
+                self.fd,
+                core::ptr::null_mut(),
+                out.fd,
+                raw_offset_ptr(off_out),
+                len,
+                flags.raw(),
+            )
+        };
+        result.map(|v| v as _).map_err(|e| e.into())
+    }
+
+    /// Like [`Self::splice_to`], but repeats the operation using
+    /// [`transfer_all`] until `len` bytes have been moved, the pipe reaches
+    /// end of file, or an operation would block after some bytes were
+    /// already moved.
+    #[inline]
+    pub fn splice_all_to<OutDevice>(
+        &self,
+        out: &File<OutDevice>,
+        mut off_out: Option<&mut u64>,
+        len: usize,
+        flags: SpliceFlags,
+    ) -> Result<usize> {
+        transfer_all(len, |remain| {
+            self.splice_to(out, off_out.as_deref_mut(), remain, flags)
+        })
+    }
+
+    /// Duplicate up to `len` bytes from this pipe to another pipe using the
+    /// `tee` system call, without consuming the data from this pipe.
+    #[inline]
+    pub fn tee_to(&self, out: &File<PipeWriter>, len: usize, flags: SpliceFlags) -> Result<usize> {
+        let result = unsafe { linux_unsafe::tee(self.fd, out.fd, len, flags.raw()) };
+        result.map(|v| v as _).map_err(|e| e.into())
+    }
+}
+
+impl File<PipeWriter> {
+    /// Splice the given buffers into the pipe using the `vmsplice` system
+    /// call.
+    ///
+    /// Safety: The kernel may refer to the memory of the buffers directly
+    /// rather than copying it, so the caller must not modify or free the
+    /// buffers until the data has been consumed from the other end of the
+    /// pipe.
+    #[inline]
+    pub unsafe fn vmsplice(&self, bufs: &[IoSlice<'_>], flags: SpliceFlags) -> Result<usize> {
+        let iov = IoSlice::as_raw_ptr(bufs);
+        let result = unsafe {
+            linux_unsafe::vmsplice(self.fd, iov, bufs.len() as linux_unsafe::ulong, flags.raw())
+        };
+        result.map(|v| v as _).map_err(|e| e.into())
+    }
+}
+
+impl<Device> File<Device> {
+    /// Move up to `len` bytes from this file to a pipe using the `splice`
+    /// system call, without passing the data through userspace.
+    ///
+    /// If `off_in` is `None` then this file's current read/write position is
+    /// used and updated. Otherwise the given offset is used and updated, and
+    /// the file's current position is unchanged. The offset must be `None`
+    /// if this file is also a pipe.
+    #[inline]
+    pub fn splice_to_pipe(
+        &self,
+        off_in: Option<&mut u64>,
+        out: &File<PipeWriter>,
+        len: usize,
+        flags: SpliceFlags,
+    ) -> Result<usize> {
+        let result = unsafe {
+            linux_unsafe::splice(
+                self.fd,
+                raw_offset_ptr(off_in),
+                out.fd,
+                core::ptr::null_mut(),
+                len,
+                flags.raw(),
+            )
+        };
+        result.map(|v| v as _).map_err(|e| e.into())
+    }
+
+    /// Like [`Self::splice_to_pipe`], but repeats the operation using
+    /// [`transfer_all`] until `len` bytes have been moved, this file reaches
+    /// end of file, or an operation would block after some bytes were
+    /// already moved.
+    #[inline]
+    pub fn splice_all_to_pipe(
+        &self,
+        mut off_in: Option<&mut u64>,
+        out: &File<PipeWriter>,
+        len: usize,
+        flags: SpliceFlags,
+    ) -> Result<usize> {
+        transfer_all(len, |remain| {
+            self.splice_to_pipe(off_in.as_deref_mut(), out, remain, flags)
+        })
+    }
+
+    /// Returns the capacity of the pipe in bytes, using the `F_GETPIPE_SZ`
+    /// `fcntl` command.
+    ///
+    /// This is available for all files so that it can be used with pipes
+    /// obtained by other means, such as standard input. It fails with
+    /// `EBADF` if the file is not a pipe.
+    #[inline]
+    pub fn pipe_size(&self) -> Result<usize> {
+        self.fcntl(crate::fd::fcntl::F_GETPIPE_SZ, ())
+            .map(|v| v as usize)
+    }
+
+    /// Change the capacity of the pipe using the `F_SETPIPE_SZ` `fcntl`
+    /// command, returning the actual new capacity.
+    ///
+    /// The kernel may round the requested size up to a more convenient value.
+    /// Unprivileged processes cannot exceed the limit given in
+    /// `/proc/sys/fs/pipe-max-size`.
+    #[inline]
+    pub fn set_pipe_size(&self, size: usize) -> Result<usize> {
+        if size > (linux_unsafe::int::MAX as usize) {
+            return Err(crate::result::EINVAL);
+        }
+        self.fcntl(crate::fd::fcntl::F_SETPIPE_SZ, size as linux_unsafe::int)
+            .map(|v| v as usize)
+    }
+}
@@ -1,6 +1,6 @@
-use core::{cell::UnsafeCell, ptr::null};
+use core::{cell::UnsafeCell, ptr::null, time::Duration};
 
-use crate::result::{Error, Result};
+use crate::result::Result;
 
 /// A request for events related to a particular file descriptor in a call to
 /// [`poll`].
@@ -22,6 +22,12 @@ impl<'a> PollRequest<'a> {
     /// The the given file must outlive the returned poll request.
     #[inline]
     pub const fn new<'f: 'a>(file: &'f super::File) -> Self {
+        Self::for_device(file)
+    }
+
+    /// Like [`Self::new`], but for a file of any device type.
+    #[inline]
+    pub const fn for_device<'f: 'a, Device>(file: &'f super::File<Device>) -> Self {
         let fd = file.fd;
         Self {
             fd,
@@ -144,7 +150,46 @@ impl PollResponse {
 /// The kernel may have an upper limit on the number of entries in reqs that
 /// is smaller than the maximum value of `usize`. If the given slice is too
 /// long then this function will return the EINVAL error code.
+///
+/// `timeout` is a number of milliseconds, passed to the kernel as-is. Unlike
+/// the C library's `poll`, a negative timeout is not treated as infinite and
+/// instead causes the kernel to return EINVAL. Use [`poll_timeout`] to wait
+/// indefinitely.
 pub fn poll(reqs: &mut [PollRequest], timeout: linux_unsafe::int) -> Result<linux_unsafe::int> {
+    // We actually use ppoll rather than poll, because poll is not
+    // available on recently-added architectures like riscv64.
+    let tmo = linux_unsafe::timespec {
+        tv_sec: (timeout / 1000) as linux_unsafe::long,
+        tv_nsec: ((timeout % 1000) * 1_000_000) as linux_unsafe::long,
+    };
+    ppoll(reqs, &tmo as *const _)
+}
+
+/// Like [`poll`], but with the timeout given as a [`Duration`], or `None`
+/// to wait indefinitely until at least one of the requests has a response.
+///
+/// Returns EINVAL if the timeout is too long to represent on the current
+/// platform.
+pub fn poll_timeout(
+    reqs: &mut [PollRequest],
+    timeout: Option<Duration>,
+) -> Result<linux_unsafe::int> {
+    // ppoll represents an infinite timeout as a null timeout pointer.
+    let tmo = match timeout {
+        Some(timeout) => Some(crate::time::timespec_from_duration(timeout)?),
+        None => None,
+    };
+    let tmo_p = match &tmo {
+        Some(tmo) => tmo as *const _,
+        None => null(),
+    };
+    ppoll(reqs, tmo_p)
+}
+
+fn ppoll(
+    reqs: &mut [PollRequest],
+    tmo_p: *const linux_unsafe::timespec,
+) -> Result<linux_unsafe::int> {
     // NOTE: We're effectively transmuting our PollRequest type into
     // the kernel's struct pollfd here. This is safe because the layout
     // of our struct should exactly match the kernel's, and the kernel
@@ -155,16 +200,17 @@ pub fn poll(reqs: &mut [PollRequest], timeout: linux_unsafe::int) -> Result<linu
         // More file descriptors than the kernel can physicall support on this
         // platform, so we'll return a synthetic EINVAL to mimic how the
         // kernel would behave if it had a smaller soft limit.
-        return Err(Error::new(22)); // hard-coded EINVAL value (TODO: expose this as a constant from linux-unsafe instead?)
+        return Err(crate::result::EINVAL);
     }
     let reqs_count = reqs.len() as linux_unsafe::nfds_t;
-    // We actually use ppoll rather than poll, because poll is not
-    // available on recently-added architectures like riscv64.
-    let tmo = linux_unsafe::timespec {
-        tv_sec: (timeout / 1000) as linux_unsafe::long,
-        tv_nsec: ((timeout % 1000) * 1_000_000) as linux_unsafe::long,
+    let result = unsafe {
+        linux_unsafe::ppoll(
+            reqs_ptr,
+            reqs_count,
+            tmo_p,
+            null(),
+            core::mem::size_of::<linux_unsafe::sigset_t>(),
+        )
     };
-    let tmo_p = &tmo as *const _;
-    let result = unsafe { linux_unsafe::ppoll(reqs_ptr, reqs_count, tmo_p, null()) };
     result.map(|count| count as _).map_err(|e| e.into())
 }
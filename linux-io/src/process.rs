@@ -0,0 +1,263 @@
+//! Process management using process file descriptors ("pidfds").
+//!
+//! A pidfd is a file descriptor that refers to a specific process, which
+//! avoids the race conditions inherent in referring to processes by their
+//! numeric process id, since those can be reused once a process has been
+//! reaped.
+
+use crate::fd::ioctl::IoDevice;
+use crate::poll::PollRequest;
+use crate::result::Result;
+use crate::File;
+
+/// A child process represented by a pidfd.
+///
+/// Dropping a `Child` closes its pidfd but does not wait for the process to
+/// exit, and so a process that has exited but not yet been waited for will
+/// remain as a "zombie" until its parent waits for it or exits itself.
+#[derive(Debug)]
+pub struct Child {
+    pidfd: File<PidFdDevice>,
+    pid: linux_unsafe::pid_t,
+}
+
+impl Child {
+    /// Obtain a pidfd for the process with the given process id, which must
+    /// be a child of the calling process for most of the methods of the
+    /// result to succeed.
+    ///
+    /// This is subject to a race condition if the given process might have
+    /// already exited and been reaped, because then the process id could
+    /// have been reused by an unrelated process. Use [`Self::fork`] to
+    /// obtain a pidfd atomically with creating the child process.
+    #[inline]
+    pub fn open(pid: linux_unsafe::pid_t) -> Result<Self> {
+        let result = unsafe { linux_unsafe::pidfd_open(pid, 0) };
+        result
+            .map(|fd| unsafe { Self::from_raw_pidfd(File::from_raw_fd(fd), pid) })
+            .map_err(|e| e.into())
+    }
+
+    /// Create a new child process that is a copy of the calling process, in
+    /// the same way as the traditional `fork` system call.
+    ///
+    /// Returns `Some` with the new child in the parent process, and `None`
+    /// in the child process.
+    ///
+    /// The child will send `SIGCHLD` to the parent when it terminates.
+    ///
+    /// **Safety:**
+    /// - In a multithreaded program the child process contains only a copy
+    ///   of the calling thread. The caller must ensure that the child does
+    ///   not use any resources whose state might be inconsistent due to the
+    ///   other threads having disappeared, such as a mutex that one of those
+    ///   threads had locked.
+    /// - All objects that own kernel resources other than file descriptors,
+    ///   such as memory mappings shared with the kernel, are duplicated in
+    ///   the child and so the caller must take care to avoid double-free.
+    #[inline]
+    pub unsafe fn fork() -> Result<Option<Self>> {
+        Self::clone_raw(linux_unsafe::CLONE_PIDFD, None)
+    }
+
+    /// Create a new child process in the same way as [`Self::fork`], but
+    /// placing the new child directly into the cgroup represented by the
+    /// given directory.
+    ///
+    /// Placing the child in the cgroup atomically during creation avoids
+    /// any period where the child is running with the parent's cgroup limits.
+    ///
+    /// **Safety:** The same requirements as for [`Self::fork`].
+    #[inline]
+    pub unsafe fn fork_into_cgroup<Device>(cgroup: &File<Device>) -> Result<Option<Self>> {
+        Self::clone_raw(
+            linux_unsafe::CLONE_PIDFD | linux_unsafe::CLONE_INTO_CGROUP,
+            Some(cgroup.fd),
+        )
+    }
+
+    unsafe fn clone_raw(flags: u64, cgroup_fd: Option<linux_unsafe::int>) -> Result<Option<Self>> {
+        let mut pidfd: linux_unsafe::int = -1;
+        let mut args = linux_unsafe::clone_args {
+            flags,
+            pidfd: &mut pidfd as *mut linux_unsafe::int as u64,
+            child_tid: 0,
+            parent_tid: 0,
+            exit_signal: linux_unsafe::SIGCHLD as u64,
+            stack: 0,
+            stack_size: 0,
+            tls: 0,
+            set_tid: 0,
+            set_tid_size: 0,
+            cgroup: cgroup_fd.unwrap_or(0) as u64,
+        };
+        let result = unsafe {
+            linux_unsafe::clone3(
+                &mut args as *mut _,
+                core::mem::size_of::<linux_unsafe::clone_args>(),
+            )
+        };
+        match result {
+            Ok(0) => Ok(None),
+            Ok(pid) => Ok(Some(unsafe {
+                Self::from_raw_pidfd(File::from_raw_fd(pidfd), pid)
+            })),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Wrap an existing pidfd and the process id it refers to.
+    ///
+    /// **Safety:** `pidfd` must really be a pidfd referring to the process
+    /// whose id is `pid`.
+    #[inline(always)]
+    pub const unsafe fn from_raw_pidfd(pidfd: File<PidFdDevice>, pid: linux_unsafe::pid_t) -> Self {
+        Self { pidfd, pid }
+    }
+
+    /// Returns the process id of the child process.
+    ///
+    /// The process id is meaningful only until the child has been waited for,
+    /// after which the kernel may reuse it for an unrelated process.
+    #[inline(always)]
+    pub const fn pid(&self) -> linux_unsafe::pid_t {
+        self.pid
+    }
+
+    /// Returns the pidfd representing the child process.
+    #[inline(always)]
+    pub const fn pidfd(&self) -> &File<PidFdDevice> {
+        &self.pidfd
+    }
+
+    /// Consumes the `Child` and returns its pidfd.
+    #[inline(always)]
+    pub fn into_pidfd(self) -> File<PidFdDevice> {
+        self.pidfd
+    }
+
+    /// Returns a request to use with [`crate::poll::poll`] to wait for the
+    /// child to terminate alongside other files.
+    ///
+    /// The pidfd becomes readable once the child process has terminated,
+    /// after which [`Self::wait`] will return without blocking.
+    #[inline]
+    pub fn poll_request(&self) -> PollRequest<'_> {
+        unsafe { PollRequest::for_device(&self.pidfd).events_raw(linux_unsafe::POLLIN) }
+    }
+
+    /// Send a signal to the child process.
+    #[inline]
+    pub fn send_signal(&self, sig: linux_unsafe::int) -> Result<()> {
+        let result = unsafe {
+            linux_unsafe::pidfd_send_signal(self.pidfd.fd, sig, core::ptr::null_mut(), 0)
+        };
+        result.map(|_| ()).map_err(|e| e.into())
+    }
+
+    /// Wait for the child process to terminate, and then reap it.
+    #[inline]
+    pub fn wait(&self) -> Result<ChildStatus> {
+        // Without WNOHANG the kernel always reports a state change on success.
+        self.wait_raw(linux_unsafe::WEXITED, None)
+            .map(|status| status.unwrap())
+    }
+
+    /// Check whether the child process has terminated without blocking,
+    /// reaping it if so.
+    ///
+    /// Returns `None` if the child process is still running.
+    #[inline]
+    pub fn try_wait(&self) -> Result<Option<ChildStatus>> {
+        self.wait_raw(linux_unsafe::WEXITED | linux_unsafe::WNOHANG, None)
+    }
+
+    /// Wait for a state change in the child process using the raw `options`
+    /// flags of the `waitid` system call, optionally also retrieving the
+    /// child's resource usage.
+    ///
+    /// Returns `None` only if `options` includes `WNOHANG` and the child
+    /// has not changed state.
+    pub fn wait_raw(
+        &self,
+        options: linux_unsafe::int,
+        rusage: Option<&mut linux_unsafe::rusage>,
+    ) -> Result<Option<ChildStatus>> {
+        let mut info = unsafe { core::mem::zeroed::<linux_unsafe::siginfo_t>() };
+        let rusage_ptr = match rusage {
+            Some(r) => r as *mut _,
+            None => core::ptr::null_mut(),
+        };
+        let result = unsafe {
+            linux_unsafe::waitid(
+                linux_unsafe::P_PIDFD,
+                self.pidfd.fd as linux_unsafe::id_t,
+                &mut info as *mut _,
+                options,
+                rusage_ptr,
+            )
+        };
+        if let Err(e) = result {
+            return Err(e.into());
+        }
+        let fields = unsafe { info.fields.sigchld };
+        if fields.si_pid == 0 {
+            return Ok(None);
+        }
+        Ok(Some(ChildStatus::from_raw(info.si_code, fields.si_status)))
+    }
+}
+
+/// A change of state of a child process, as returned by [`Child::wait`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChildStatus {
+    /// The child exited with the given exit status.
+    Exited(linux_unsafe::int),
+
+    /// The child was terminated by the given signal.
+    Killed(linux_unsafe::int),
+
+    /// The child was terminated by the given signal and produced a core dump.
+    Dumped(linux_unsafe::int),
+
+    /// The child was stopped by the given signal because it is being traced.
+    Trapped(linux_unsafe::int),
+
+    /// The child was stopped by the given signal.
+    Stopped(linux_unsafe::int),
+
+    /// The child was resumed by `SIGCONT`.
+    Continued,
+
+    /// The kernel reported a state change code that this crate doesn't know
+    /// about. The fields are the raw `si_code` and `si_status`.
+    Unknown(linux_unsafe::int, linux_unsafe::int),
+}
+
+impl ChildStatus {
+    /// Interpret the `si_code` and `si_status` fields of a `siginfo_t`
+    /// returned by `waitid`.
+    pub const fn from_raw(code: linux_unsafe::int, status: linux_unsafe::int) -> Self {
+        match code {
+            linux_unsafe::CLD_EXITED => Self::Exited(status),
+            linux_unsafe::CLD_KILLED => Self::Killed(status),
+            linux_unsafe::CLD_DUMPED => Self::Dumped(status),
+            linux_unsafe::CLD_TRAPPED => Self::Trapped(status),
+            linux_unsafe::CLD_STOPPED => Self::Stopped(status),
+            linux_unsafe::CLD_CONTINUED => Self::Continued,
+            _ => Self::Unknown(code, status),
+        }
+    }
+
+    /// Returns true if the child exited normally with exit status zero.
+    #[inline(always)]
+    pub const fn success(&self) -> bool {
+        matches!(self, Self::Exited(0))
+    }
+}
+
+/// Device type marker for [`crate::File`] instances that represent pidfds.
+#[derive(Clone, Copy, Debug)]
+pub struct PidFdDevice;
+
+impl IoDevice for PidFdDevice {}
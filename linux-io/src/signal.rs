@@ -0,0 +1,119 @@
+//! Signal handling through file descriptors, using "signalfd".
+//!
+//! A signalfd allows a program to accept signals synchronously by reading
+//! from a file descriptor, which means that signals can be handled in the
+//! same event loop as other I/O. The signals of interest must be blocked
+//! using [`block_signals`](crate::signal::block_signals) so that the kernel
+//! will leave them pending to be read from the signalfd, rather than
+//! delivering them as normal.
+
+use crate::fd::ioctl::IoDevice;
+use crate::result::Result;
+use crate::File;
+
+pub use linux_unsafe::{signalfd_siginfo, sigset_t};
+
+const SIGSET_SIZE: linux_unsafe::size_t = core::mem::size_of::<sigset_t>();
+
+/// A marker type for [`File`] objects that represent signalfd instances.
+#[derive(Clone, Copy, Debug)]
+pub struct SignalFdDevice;
+
+impl IoDevice for SignalFdDevice {}
+
+impl File<SignalFdDevice> {
+    /// Create a new signalfd that accepts the signals in the given mask.
+    ///
+    /// The new file descriptor has the close-on-exec flag set.
+    #[inline]
+    pub fn signalfd(mask: &sigset_t) -> Result<Self> {
+        unsafe { Self::signalfd_raw(mask, linux_unsafe::SFD_CLOEXEC) }
+    }
+
+    /// Create a new signalfd using the raw `flags` argument of the
+    /// `signalfd4` system call.
+    ///
+    /// Safety: `flags` must be a valid combination of `SFD_*` flags.
+    #[inline]
+    pub unsafe fn signalfd_raw(mask: &sigset_t, flags: linux_unsafe::int) -> Result<Self> {
+        let result = unsafe { linux_unsafe::signalfd4(-1, mask as *const _, SIGSET_SIZE, flags) };
+        result
+            .map(|fd| unsafe { File::from_raw_fd(fd) })
+            .map_err(|e| e.into())
+    }
+
+    /// Replace the set of signals that the signalfd accepts.
+    #[inline]
+    pub fn set_mask(&self, mask: &sigset_t) -> Result<()> {
+        let result = unsafe { linux_unsafe::signalfd4(self.fd, mask as *const _, SIGSET_SIZE, 0) };
+        result.map(|_| ()).map_err(|e| e.into())
+    }
+
+    /// Read one or more pending signals into the given buffer, returning the
+    /// part of the buffer that was populated.
+    ///
+    /// Blocks until at least one signal is pending unless the signalfd is
+    /// in non-blocking mode, in which case it returns [`EAGAIN`](crate::result::EAGAIN)
+    /// if no signals are pending.
+    pub fn read_signals<'a>(
+        &self,
+        buf: &'a mut [signalfd_siginfo],
+    ) -> Result<&'a [signalfd_siginfo]> {
+        const RECORD_SIZE: usize = core::mem::size_of::<signalfd_siginfo>();
+        let len = unsafe {
+            self.read_raw(
+                buf.as_mut_ptr() as *mut linux_unsafe::void,
+                buf.len() * RECORD_SIZE,
+            )
+        }?;
+        // The kernel only ever returns whole records.
+        Ok(&buf[..(len / RECORD_SIZE)])
+    }
+
+    /// Read a single pending signal.
+    ///
+    /// Blocks until a signal is pending unless the signalfd is in
+    /// non-blocking mode.
+    pub fn read_signal(&self) -> Result<signalfd_siginfo> {
+        let mut buf = [unsafe { core::mem::zeroed::<signalfd_siginfo>() }];
+        self.read_signals(&mut buf[..])?;
+        Ok(buf[0])
+    }
+}
+
+/// Add the given signals to the calling thread's set of blocked signals,
+/// returning the previous set.
+#[inline]
+pub fn block_signals(set: &sigset_t) -> Result<sigset_t> {
+    unsafe { change_signal_mask(linux_unsafe::SIG_BLOCK, set) }
+}
+
+/// Remove the given signals from the calling thread's set of blocked signals,
+/// returning the previous set.
+#[inline]
+pub fn unblock_signals(set: &sigset_t) -> Result<sigset_t> {
+    unsafe { change_signal_mask(linux_unsafe::SIG_UNBLOCK, set) }
+}
+
+/// Replace the calling thread's set of blocked signals, returning the
+/// previous set.
+#[inline]
+pub fn set_signal_mask(set: &sigset_t) -> Result<sigset_t> {
+    unsafe { change_signal_mask(linux_unsafe::SIG_SETMASK, set) }
+}
+
+/// Returns the set of signals that are pending for the calling thread because
+/// they are currently blocked.
+#[inline]
+pub fn pending_signals() -> Result<sigset_t> {
+    let mut set = sigset_t::new_empty();
+    let result = unsafe { linux_unsafe::rt_sigpending(set.as_mut_ptr(), SIGSET_SIZE) };
+    result.map(|_| set).map_err(|e| e.into())
+}
+
+unsafe fn change_signal_mask(how: linux_unsafe::int, set: &sigset_t) -> Result<sigset_t> {
+    let mut old = sigset_t::new_empty();
+    let result =
+        unsafe { linux_unsafe::rt_sigprocmask(how, set.as_ptr(), old.as_mut_ptr(), SIGSET_SIZE) };
+    result.map(|_| old).map_err(|e| e.into())
+}
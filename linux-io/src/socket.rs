@@ -1,11 +1,23 @@
 /// Address types for the IPv4 and IPv6 protocol families.
 pub mod ip;
 
+/// Sending and receiving messages with ancillary data, such as open files
+/// and process credentials.
+pub mod msg;
+
+/// Address types, protocols and message encoding for the netlink address
+/// family.
+pub mod netlink;
+
+/// Address types and constants for the Unix domain address family.
+pub mod unix;
+
 use core::mem::size_of;
 
 pub use linux_unsafe::sock_type;
 
 use crate::fd::ioctl::{ioctl_read, IoctlReqRead, _IOR};
+use crate::result::Result;
 
 /// A trait implemented by all socket address types.
 ///
@@ -37,6 +49,86 @@ pub unsafe trait SockAddr {
         -> (*mut linux_unsafe::sockaddr, linux_unsafe::socklen_t);
 }
 
+/// A trait implemented by socket address types that can be decoded from an
+/// address returned by the kernel, such as by [`crate::File::accept`].
+pub trait SockAddrFromRaw: Sized {
+    /// Decodes an address from the first `len` bytes of `raw`, or returns
+    /// `None` if it belongs to an address family that this type cannot
+    /// represent.
+    fn from_sockaddr_raw(
+        raw: &linux_unsafe::sockaddr_storage,
+        len: linux_unsafe::socklen_t,
+    ) -> Option<Self>;
+}
+
+flags_type! {
+    /// Flags for [`crate::File::accept`].
+    pub struct SocketFlags(linux_unsafe::int);
+
+    /// No flags.
+    const NONE = 0;
+    /// Set the close-on-exec flag on the new file descriptor.
+    const CLOEXEC = linux_unsafe::SOCK_CLOEXEC;
+    /// Put the new file descriptor in non-blocking mode.
+    const NONBLOCK = linux_unsafe::SOCK_NONBLOCK;
+}
+
+/// Which directions of a connection to shut down using
+/// [`crate::File::shutdown`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shutdown {
+    /// Disallow further receptions.
+    Read,
+    /// Disallow further transmissions. For a stream socket this sends the
+    /// peer an end-of-file indication once any pending data is sent.
+    Write,
+    /// Disallow both further receptions and further transmissions.
+    Both,
+}
+
+impl Shutdown {
+    /// Returns the raw `SHUT_*` value, for use with the functions in
+    /// [`linux_unsafe`].
+    #[inline(always)]
+    pub const fn raw(self) -> linux_unsafe::int {
+        match self {
+            Self::Read => linux_unsafe::SHUT_RD,
+            Self::Write => linux_unsafe::SHUT_WR,
+            Self::Both => linux_unsafe::SHUT_RDWR,
+        }
+    }
+}
+
+/// Create an unnamed pair of connected sockets using the `socketpair`
+/// system call.
+///
+/// As with [`crate::File::socket`], the protocol is specified as a typed
+/// constant which determines the device type of the two results. Only the
+/// Unix domain family supports socket pairs, so this is typically used with
+/// [`unix::AF_UNIX`] and one of the protocols from [`unix`].
+#[inline]
+pub fn socketpair<Device, Protocol>(
+    domain: linux_unsafe::sa_family_t,
+    typ: sock_type,
+    protocol: Protocol,
+) -> Result<(crate::File<Device>, crate::File<Device>)>
+where
+    Device: crate::fd::ioctl::IoDevice,
+    Protocol: SocketProtocol<Device = Device>,
+{
+    let mut fds: [linux_unsafe::int; 2] = [-1, -1];
+    let result =
+        unsafe { linux_unsafe::socketpair(domain, typ, protocol.raw_protocol_num(), &mut fds) };
+    result
+        .map(|_| unsafe {
+            (
+                crate::File::from_raw_fd(fds[0]),
+                crate::File::from_raw_fd(fds[1]),
+            )
+        })
+        .map_err(|e| e.into())
+}
+
 /// Represents a socket protocol that is compatible with sockets belonging to
 /// the domain/family `FAMILY`.
 ///
@@ -382,11 +382,13 @@ pub const AF_INET6: linux_unsafe::sa_family_t = 10;
 pub const IPPROTO_TCP: super::SocketProtocolFixed<tcp::TcpSocketDevice> =
     unsafe { super::socket_protocol(6) };
 
+pub const IPPROTO_UDP: super::SocketProtocolFixed<udp::UdpSocketDevice> =
+    unsafe { super::socket_protocol(17) };
+
 pub const IPPROTO_ICMP: linux_unsafe::int = 1;
 pub const IPPROTO_IGMP: linux_unsafe::int = 4;
 pub const IPPROTO_EGP: linux_unsafe::int = 8;
 pub const IPPROTO_PUP: linux_unsafe::int = 12;
-pub const IPPROTO_UDP: linux_unsafe::int = 17;
 pub const IPPROTO_IDP: linux_unsafe::int = 22;
 pub const IPPROTO_TP: linux_unsafe::int = 29;
 pub const IPPROTO_DCCP: linux_unsafe::int = 33;
@@ -407,6 +409,126 @@ pub const IPPROTO_ETHERNET: linux_unsafe::int = 143;
 pub const IPPROTO_RAW: linux_unsafe::int = 255;
 pub const IPPROTO_MPTCP: linux_unsafe::int = 262;
 
+/// Report the interface and addresses of each received IPv4 packet as
+/// `IP_PKTINFO` ancillary data.
+///
+/// `1` enables packet information, while `0` disables it.
+pub const IP_PKTINFO: crate::fd::sockopt::DirectSockOpt<linux_unsafe::int> =
+    unsafe { crate::fd::sockopt::sockopt(linux_unsafe::SOL_IP, linux_unsafe::IP_PKTINFO) };
+
+/// Set the network interface used for sending multicast datagrams from an
+/// IPv4 socket, identified by one of its local addresses.
+///
+/// [`Ipv4Addr::ANY`] lets the kernel choose an interface.
+pub const IP_MULTICAST_IF: crate::fd::sockopt::DirectSockOpt<Ipv4Addr> =
+    unsafe { crate::fd::sockopt::sockopt(linux_unsafe::SOL_IP, linux_unsafe::IP_MULTICAST_IF) };
+
+/// The time-to-live of multicast datagrams sent from an IPv4 socket, between
+/// `0` and `255`.
+pub const IP_MULTICAST_TTL: crate::fd::sockopt::DirectSockOpt<linux_unsafe::int> =
+    unsafe { crate::fd::sockopt::sockopt(linux_unsafe::SOL_IP, linux_unsafe::IP_MULTICAST_TTL) };
+
+/// Deliver multicast datagrams sent from an IPv4 socket back to local
+/// sockets that have joined the group.
+///
+/// `1` enables loopback, which is the default, while `0` disables it.
+pub const IP_MULTICAST_LOOP: crate::fd::sockopt::DirectSockOpt<linux_unsafe::int> =
+    unsafe { crate::fd::sockopt::sockopt(linux_unsafe::SOL_IP, linux_unsafe::IP_MULTICAST_LOOP) };
+
+/// Join an IPv4 multicast group. This option can only be set.
+pub const IP_ADD_MEMBERSHIP: crate::fd::sockopt::DirectSockOpt<Ipv4MulticastRequest> =
+    unsafe { crate::fd::sockopt::sockopt(linux_unsafe::SOL_IP, linux_unsafe::IP_ADD_MEMBERSHIP) };
+
+/// Leave an IPv4 multicast group. This option can only be set.
+pub const IP_DROP_MEMBERSHIP: crate::fd::sockopt::DirectSockOpt<Ipv4MulticastRequest> =
+    unsafe { crate::fd::sockopt::sockopt(linux_unsafe::SOL_IP, linux_unsafe::IP_DROP_MEMBERSHIP) };
+
+/// Set the network interface used for sending multicast datagrams from an
+/// IPv6 socket, identified by its interface index.
+///
+/// `0` lets the kernel choose an interface.
+pub const IPV6_MULTICAST_IF: crate::fd::sockopt::DirectSockOpt<linux_unsafe::int> =
+    unsafe { crate::fd::sockopt::sockopt(linux_unsafe::SOL_IPV6, linux_unsafe::IPV6_MULTICAST_IF) };
+
+/// The hop limit of multicast datagrams sent from an IPv6 socket, between
+/// `0` and `255`, or `-1` to use the default.
+pub const IPV6_MULTICAST_HOPS: crate::fd::sockopt::DirectSockOpt<linux_unsafe::int> = unsafe {
+    crate::fd::sockopt::sockopt(linux_unsafe::SOL_IPV6, linux_unsafe::IPV6_MULTICAST_HOPS)
+};
+
+/// Deliver multicast datagrams sent from an IPv6 socket back to local
+/// sockets that have joined the group.
+///
+/// `1` enables loopback, which is the default, while `0` disables it.
+pub const IPV6_MULTICAST_LOOP: crate::fd::sockopt::DirectSockOpt<linux_unsafe::int> = unsafe {
+    crate::fd::sockopt::sockopt(linux_unsafe::SOL_IPV6, linux_unsafe::IPV6_MULTICAST_LOOP)
+};
+
+/// Join an IPv6 multicast group. This option can only be set.
+pub const IPV6_JOIN_GROUP: crate::fd::sockopt::DirectSockOpt<Ipv6MulticastRequest> =
+    unsafe { crate::fd::sockopt::sockopt(linux_unsafe::SOL_IPV6, linux_unsafe::IPV6_JOIN_GROUP) };
+
+/// Leave an IPv6 multicast group. This option can only be set.
+pub const IPV6_LEAVE_GROUP: crate::fd::sockopt::DirectSockOpt<Ipv6MulticastRequest> =
+    unsafe { crate::fd::sockopt::sockopt(linux_unsafe::SOL_IPV6, linux_unsafe::IPV6_LEAVE_GROUP) };
+
+/// Identifies an IPv4 multicast group and the local interface to join or
+/// leave it on, for use with [`IP_ADD_MEMBERSHIP`] and
+/// [`IP_DROP_MEMBERSHIP`].
+///
+/// This has the same layout as the kernel's `ip_mreqn` structure.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct Ipv4MulticastRequest {
+    /// The address of the multicast group.
+    pub group: Ipv4Addr,
+    /// A local address of the interface to use, or [`Ipv4Addr::ANY`] to
+    /// select the interface using `interface_index` instead.
+    pub local_address: Ipv4Addr,
+    /// The index of the interface to use, or `0` to let the kernel choose
+    /// an interface when `local_address` is also [`Ipv4Addr::ANY`].
+    pub interface_index: linux_unsafe::int,
+}
+
+impl Ipv4MulticastRequest {
+    /// Create a request for the given group on the interface with the given
+    /// index, or on an interface chosen by the kernel if the index is `0`.
+    #[inline]
+    pub const fn new(group: Ipv4Addr, interface_index: linux_unsafe::int) -> Self {
+        Self {
+            group,
+            local_address: Ipv4Addr::ANY,
+            interface_index,
+        }
+    }
+}
+
+/// Identifies an IPv6 multicast group and the local interface to join or
+/// leave it on, for use with [`IPV6_JOIN_GROUP`] and [`IPV6_LEAVE_GROUP`].
+///
+/// This has the same layout as the kernel's `ipv6_mreq` structure.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct Ipv6MulticastRequest {
+    /// The address of the multicast group.
+    pub group: Ipv6Addr,
+    /// The index of the interface to use, or `0` to let the kernel choose
+    /// an interface.
+    pub interface_index: linux_unsafe::int,
+}
+
+impl Ipv6MulticastRequest {
+    /// Create a request for the given group on the interface with the given
+    /// index, or on an interface chosen by the kernel if the index is `0`.
+    #[inline]
+    pub const fn new(group: Ipv6Addr, interface_index: linux_unsafe::int) -> Self {
+        Self {
+            group,
+            interface_index,
+        }
+    }
+}
+
 unsafe impl super::SockAddr for SockAddrIpv4 {
     #[inline(always)]
     unsafe fn sockaddr_raw_const(
@@ -481,6 +603,53 @@ unsafe impl super::SockAddr for SockAddrIp {
     }
 }
 
+/// The length of the kernel's `sockaddr_in6`, which excludes the padding
+/// that our alignment adds to [`SockAddrIpv6`].
+const SOCKADDR_IN6_LEN: linux_unsafe::socklen_t = 28;
+
+impl super::SockAddrFromRaw for SockAddrIpv4 {
+    fn from_sockaddr_raw(
+        raw: &linux_unsafe::sockaddr_storage,
+        len: linux_unsafe::socklen_t,
+    ) -> Option<Self> {
+        if raw.family != AF_INET || (len as usize) < core::mem::size_of::<Self>() {
+            return None;
+        }
+        // Safe because sockaddr_storage is larger than and at least as
+        // aligned as our type, and we've checked the family.
+        Some(unsafe { *(raw as *const _ as *const Self) })
+    }
+}
+
+impl super::SockAddrFromRaw for SockAddrIpv6 {
+    fn from_sockaddr_raw(
+        raw: &linux_unsafe::sockaddr_storage,
+        len: linux_unsafe::socklen_t,
+    ) -> Option<Self> {
+        if raw.family != AF_INET6 || len < SOCKADDR_IN6_LEN {
+            return None;
+        }
+        // Safe because sockaddr_storage is larger than and at least as
+        // aligned as our type, and we've checked the family.
+        Some(unsafe { *(raw as *const _ as *const Self) })
+    }
+}
+
+impl super::SockAddrFromRaw for SockAddrIp {
+    fn from_sockaddr_raw(
+        raw: &linux_unsafe::sockaddr_storage,
+        len: linux_unsafe::socklen_t,
+    ) -> Option<Self> {
+        match raw.family {
+            AF_INET => <SockAddrIpv4 as super::SockAddrFromRaw>::from_sockaddr_raw(raw, len)
+                .map(|v4| Self(SockAddrIpInner { v4 })),
+            AF_INET6 => <SockAddrIpv6 as super::SockAddrFromRaw>::from_sockaddr_raw(raw, len)
+                .map(|v6| Self(SockAddrIpInner { v6 })),
+            _ => None,
+        }
+    }
+}
+
 impl From<Ipv4Addr> for IpAddr {
     fn from(value: Ipv4Addr) -> Self {
         IpAddr::V4(value)
@@ -572,3 +741,6 @@ unsafe impl crate::fd::ioctl::SubDevice<super::SocketDevice> for Ipv6SocketDevic
 
 /// Extra types and constants for working with TCP sockets.
 pub mod tcp;
+
+/// Extra types and constants for working with UDP sockets.
+pub mod udp;
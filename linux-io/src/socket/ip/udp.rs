@@ -0,0 +1,44 @@
+/// Device type marker for [`crate::File`] instances that represent UDP sockets.
+#[derive(Clone, Copy)]
+pub struct UdpSocketDevice;
+
+impl crate::fd::ioctl::IoDevice for UdpSocketDevice {}
+unsafe impl crate::fd::ioctl::SubDevice<super::Ipv4SocketDevice> for UdpSocketDevice {}
+unsafe impl crate::fd::ioctl::SubDevice<super::Ipv6SocketDevice> for UdpSocketDevice {}
+unsafe impl crate::fd::ioctl::SubDevice<super::super::SocketDevice> for UdpSocketDevice {}
+
+use crate::fd::ioctl::{ioctl_read, IoctlReqRead};
+use crate::fd::sockopt::{sockopt, DirectSockOpt};
+use linux_unsafe::int;
+
+/// Returns the size of the next pending datagram in the receive queue, or
+/// zero if no datagram is pending.
+pub const SIOCINQ: IoctlReqRead<UdpSocketDevice, int> = unsafe { ioctl_read(0x541B) };
+
+/// Returns the number of bytes of data in the socket send queue.
+pub const SIOCOUTQ: IoctlReqRead<UdpSocketDevice, int> = unsafe { ioctl_read(0x5411) };
+
+/// Accumulate all data written to the socket into a single datagram, which
+/// is sent only once the option is disabled again.
+///
+/// `1` enables corking, while `0` disables it and sends any pending data.
+pub const UDP_CORK: DirectSockOpt<int> =
+    unsafe { sockopt(linux_unsafe::SOL_UDP, linux_unsafe::UDP_CORK) };
+
+/// Use generic segmentation offload to split each large send into multiple
+/// datagrams of the given payload size, or `0` to disable segmentation.
+///
+/// The size can also be set for an individual message using
+/// [`ControlMessageWriter::udp_segment`](crate::socket::msg::ControlMessageWriter::udp_segment).
+pub const UDP_SEGMENT: DirectSockOpt<int> =
+    unsafe { sockopt(linux_unsafe::SOL_UDP, linux_unsafe::UDP_SEGMENT) };
+
+/// Use generic receive offload to coalesce consecutive datagrams from the
+/// same sender into a single larger read.
+///
+/// `1` enables coalescing, while `0` disables it. When enabled, each
+/// coalesced read carries a
+/// [`ControlMessage::UdpGro`](crate::socket::msg::ControlMessage::UdpGro)
+/// object giving the size of the original datagrams.
+pub const UDP_GRO: DirectSockOpt<int> =
+    unsafe { sockopt(linux_unsafe::SOL_UDP, linux_unsafe::UDP_GRO) };
@@ -0,0 +1,608 @@
+//! Ancillary data, also known as control messages, is extra information
+//! sent or received along with the data of a socket message using
+//! [`File::send_msg`](crate::File::send_msg) and
+//! [`File::recv_msg`](crate::File::recv_msg). It can be used to pass open
+//! files and process credentials between processes over a Unix socket, or
+//! to learn about how the kernel handled a received packet.
+//!
+//! Build ancillary data to send using
+//! [`ControlMessageWriter`](crate::socket::msg::ControlMessageWriter), and
+//! iterate over received ancillary data using
+//! [`RecvMsg::control_messages`](crate::socket::msg::RecvMsg::control_messages).
+//! Both work with caller-provided buffers, which can be declared using
+//! [`ControlBuffer`](crate::socket::msg::ControlBuffer) and sized using
+//! [`cmsg_space`](crate::socket::msg::cmsg_space).
+
+use core::mem::{align_of, size_of};
+use core::time::Duration;
+
+use linux_unsafe::{cmsghdr, int};
+
+use super::ip::Ipv4Addr;
+use super::SockAddrFromRaw;
+use crate::result::Result;
+use crate::time::duration_from_timespec;
+use crate::File;
+
+const fn cmsg_align(len: usize) -> usize {
+    (len + size_of::<usize>() - 1) & !(size_of::<usize>() - 1)
+}
+
+const CMSG_HDR_SPACE: usize = cmsg_align(size_of::<cmsghdr>());
+
+/// Returns the number of bytes of buffer space needed for one ancillary
+/// data object with `data_len` bytes of data, including its header and
+/// padding.
+///
+/// The space needed for several objects is the sum of their individual
+/// space requirements.
+#[inline(always)]
+pub const fn cmsg_space(data_len: usize) -> usize {
+    CMSG_HDR_SPACE + cmsg_align(data_len)
+}
+
+/// A buffer for ancillary data of `N` bytes, with the alignment that the
+/// kernel's `cmsghdr` type requires.
+///
+/// [`ControlMessageWriter::new`] and [`crate::File::recv_msg`] accept
+/// buffers of any alignment, but skip some bytes at the start of a buffer
+/// that isn't suitably aligned, so using this type ensures that all `N`
+/// bytes are usable.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct ControlBuffer<const N: usize> {
+    _align: [usize; 0],
+    bytes: [u8; N],
+}
+
+impl<const N: usize> ControlBuffer<N> {
+    /// Returns a new buffer filled with zeros.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            _align: [],
+            bytes: [0; N],
+        }
+    }
+
+    /// Returns the buffer as a mutable slice.
+    #[inline(always)]
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        &mut self.bytes
+    }
+}
+
+impl<const N: usize> Default for ControlBuffer<N> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the suitably-aligned part of the given buffer.
+#[inline]
+pub(crate) fn align_buffer(buf: &mut [u8]) -> &mut [u8] {
+    let skip = buf.as_ptr().align_offset(align_of::<cmsghdr>());
+    let skip = if skip > buf.len() { buf.len() } else { skip };
+    &mut buf[skip..]
+}
+
+/// Builds ancillary data to send using [`crate::File::send_msg`] in a
+/// caller-provided buffer.
+///
+/// Each method that adds an object fails with
+/// [`ENOBUFS`](crate::result::ENOBUFS) if the object doesn't fit in the
+/// remaining buffer space, leaving the existing content unchanged.
+#[derive(Debug)]
+pub struct ControlMessageWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> ControlMessageWriter<'a> {
+    /// Start writing ancillary data into the given buffer.
+    #[inline]
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self {
+            buf: align_buffer(buf),
+            len: 0,
+        }
+    }
+
+    /// Add an `SCM_RIGHTS` object that sends the given files, so that the
+    /// receiving process gets its own file descriptors referring to the
+    /// same open file descriptions.
+    ///
+    /// This is supported only for Unix domain sockets.
+    pub fn rights<Device>(&mut self, files: &[&File<Device>]) -> Result<()> {
+        let fd_size = size_of::<int>();
+        self.push_with(
+            linux_unsafe::SOL_SOCKET,
+            linux_unsafe::SCM_RIGHTS,
+            files.len() * fd_size,
+            |data| {
+                for (i, file) in files.iter().enumerate() {
+                    data[i * fd_size..(i + 1) * fd_size].copy_from_slice(&file.fd.to_ne_bytes());
+                }
+            },
+        )
+    }
+
+    /// Add an `SCM_CREDENTIALS` object that sends the given process
+    /// credentials.
+    ///
+    /// This is supported only for Unix domain sockets. Unless the sender
+    /// has the relevant capabilities, the credentials must match its own
+    /// process ID and one of its user IDs and group IDs.
+    #[inline]
+    pub fn credentials(&mut self, creds: Credentials) -> Result<()> {
+        self.push_value(
+            linux_unsafe::SOL_SOCKET,
+            linux_unsafe::SCM_CREDENTIALS,
+            &creds.to_raw(),
+        )
+    }
+
+    /// Add an `IP_PKTINFO` object to select the outgoing interface and
+    /// source address for an IPv4 datagram.
+    #[inline]
+    pub fn packet_info(&mut self, info: PacketInfo) -> Result<()> {
+        self.push_value(
+            linux_unsafe::SOL_IP,
+            linux_unsafe::IP_PKTINFO,
+            &info.to_raw(),
+        )
+    }
+
+    /// Add a `UDP_SEGMENT` object so that the kernel splits the data of
+    /// this message into multiple UDP datagrams with the given payload size,
+    /// overriding the socket's
+    /// [`UDP_SEGMENT`](crate::socket::ip::udp::UDP_SEGMENT) option.
+    #[inline]
+    pub fn udp_segment(&mut self, size: u16) -> Result<()> {
+        self.push_value(linux_unsafe::SOL_UDP, linux_unsafe::UDP_SEGMENT, &size)
+    }
+
+    /// Add an object with the given level, type, and raw data.
+    #[inline]
+    pub fn raw(&mut self, level: int, typ: int, data: &[u8]) -> Result<()> {
+        self.push_with(level, typ, data.len(), |buf| buf.copy_from_slice(data))
+    }
+
+    /// Returns the ancillary data written so far, to pass to
+    /// [`crate::File::send_msg`].
+    #[inline(always)]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    #[inline]
+    fn push_value<T: Copy>(&mut self, level: int, typ: int, value: &T) -> Result<()> {
+        self.push_with(level, typ, size_of::<T>(), |data| {
+            // Safe because T is a plain-old-data kernel type and data is
+            // exactly as long as T.
+            unsafe { (data.as_mut_ptr() as *mut T).write_unaligned(*value) }
+        })
+    }
+
+    fn push_with(
+        &mut self,
+        level: int,
+        typ: int,
+        data_len: usize,
+        fill: impl FnOnce(&mut [u8]),
+    ) -> Result<()> {
+        let space = cmsg_space(data_len);
+        if self.buf.len() - self.len < space {
+            return Err(crate::result::ENOBUFS);
+        }
+        let obj = &mut self.buf[self.len..self.len + space];
+        obj.fill(0);
+        let hdr = cmsghdr {
+            cmsg_len: CMSG_HDR_SPACE + data_len,
+            cmsg_level: level,
+            cmsg_type: typ,
+        };
+        unsafe { (obj.as_mut_ptr() as *mut cmsghdr).write_unaligned(hdr) };
+        fill(&mut obj[CMSG_HDR_SPACE..CMSG_HDR_SPACE + data_len]);
+        self.len += space;
+        Ok(())
+    }
+}
+
+/// The result of [`crate::File::recv_msg`].
+///
+/// Any received files that the caller doesn't take from the ancillary data
+/// are closed when this object is dropped.
+pub struct RecvMsg<'c> {
+    pub(crate) len: usize,
+    pub(crate) flags: MsgFlags,
+    pub(crate) addr: linux_unsafe::sockaddr_storage,
+    pub(crate) addr_len: linux_unsafe::socklen_t,
+    pub(crate) control: ControlMessages<'c>,
+}
+
+impl<'c> RecvMsg<'c> {
+    /// Returns the number of bytes of data received.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if no data was received.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the flags describing the received message, such as
+    /// [`MsgFlags::TRUNC`] if the data was truncated to fit the buffers or
+    /// [`MsgFlags::CTRUNC`] if the ancillary data was truncated.
+    #[inline(always)]
+    pub fn flags(&self) -> MsgFlags {
+        self.flags
+    }
+
+    /// Returns the address of the sender decoded as `Addr`, or `None` if
+    /// the socket didn't report a sender address or if it belongs to a
+    /// family that `Addr` cannot represent.
+    #[inline]
+    pub fn addr<Addr: SockAddrFromRaw>(&self) -> Option<Addr> {
+        if self.addr_len == 0 {
+            return None;
+        }
+        Addr::from_sockaddr_raw(&self.addr, self.addr_len)
+    }
+
+    /// Returns an iterator over the received ancillary data.
+    #[inline(always)]
+    pub fn control_messages(&mut self) -> &mut ControlMessages<'c> {
+        &mut self.control
+    }
+}
+
+impl<'c> core::fmt::Debug for RecvMsg<'c> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RecvMsg")
+            .field("len", &self.len)
+            .field("flags", &self.flags)
+            .field("addr_len", &self.addr_len)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An iterator over received ancillary data, returned by
+/// [`RecvMsg::control_messages`].
+///
+/// Any received files in objects that the iterator doesn't reach are
+/// closed when it is dropped.
+#[derive(Debug)]
+pub struct ControlMessages<'c> {
+    buf: &'c mut [u8],
+}
+
+impl<'c> ControlMessages<'c> {
+    /// Safety: `buf` must contain ancillary data populated by the kernel,
+    /// so that any file descriptors in `SCM_RIGHTS` objects are open and
+    /// owned by nothing else.
+    #[inline(always)]
+    pub(crate) unsafe fn from_received(buf: &'c mut [u8]) -> Self {
+        Self { buf }
+    }
+}
+
+impl<'c> Iterator for ControlMessages<'c> {
+    type Item = ControlMessage<'c>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let buf = core::mem::take(&mut self.buf);
+        if buf.len() < CMSG_HDR_SPACE {
+            return None;
+        }
+        let hdr = unsafe { (buf.as_ptr() as *const cmsghdr).read_unaligned() };
+        if hdr.cmsg_len < CMSG_HDR_SPACE {
+            return None;
+        }
+        let obj_len = if hdr.cmsg_len > buf.len() {
+            buf.len()
+        } else {
+            hdr.cmsg_len
+        };
+        let next_start = cmsg_align(obj_len);
+        let next_start = if next_start > buf.len() {
+            buf.len()
+        } else {
+            next_start
+        };
+        let (obj, rest) = buf.split_at_mut(next_start);
+        self.buf = rest;
+        let data = &mut obj[CMSG_HDR_SPACE..obj_len];
+        Some(ControlMessage::decode(hdr.cmsg_level, hdr.cmsg_type, data))
+    }
+}
+
+impl<'c> Drop for ControlMessages<'c> {
+    fn drop(&mut self) {
+        // Dropping any remaining received files closes them.
+        for msg in self.by_ref() {
+            drop(msg);
+        }
+    }
+}
+
+/// An ancillary data object, returned by [`ControlMessages`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ControlMessage<'c> {
+    /// Files sent using `SCM_RIGHTS`.
+    Rights(ReceivedFiles<'c>),
+    /// Process credentials sent using `SCM_CREDENTIALS`, or added by the
+    /// kernel when the socket has the `SO_PASSCRED` option enabled.
+    Credentials(Credentials),
+    /// Information about a received IPv4 packet, when the socket has the
+    /// `IP_PKTINFO` option enabled.
+    PacketInfo(PacketInfo),
+    /// The time a packet was received, as a duration since the Unix epoch,
+    /// when the socket has the `SO_TIMESTAMPNS` option enabled.
+    TimestampNs(Duration),
+    /// Timestamps for a packet, when the socket has the `SO_TIMESTAMPING`
+    /// option enabled.
+    Timestamping(Timestamps),
+    /// The payload size of the original datagrams that were coalesced into
+    /// a single read, when the socket has the
+    /// [`UDP_GRO`](crate::socket::ip::udp::UDP_GRO) option enabled.
+    UdpGro(int),
+    /// Some other kind of object, or one whose data was malformed.
+    Other {
+        level: int,
+        typ: int,
+        data: &'c [u8],
+    },
+}
+
+impl<'c> ControlMessage<'c> {
+    fn decode(level: int, typ: int, data: &'c mut [u8]) -> Self {
+        use linux_unsafe::{kernel_timespec, scm_timestamping, scm_timestamping64, timespec};
+
+        match (level, typ) {
+            (linux_unsafe::SOL_SOCKET, linux_unsafe::SCM_RIGHTS) => {
+                return Self::Rights(ReceivedFiles { data });
+            }
+            (linux_unsafe::SOL_SOCKET, linux_unsafe::SCM_CREDENTIALS) => {
+                if let Some(raw) = read_data::<linux_unsafe::ucred>(data) {
+                    return Self::Credentials(Credentials::from_raw(raw));
+                }
+            }
+            (linux_unsafe::SOL_IP, linux_unsafe::IP_PKTINFO) => {
+                if let Some(raw) = read_data::<linux_unsafe::in_pktinfo>(data) {
+                    return Self::PacketInfo(PacketInfo::from_raw(raw));
+                }
+            }
+            (linux_unsafe::SOL_UDP, linux_unsafe::UDP_GRO) => {
+                if let Some(size) = read_data::<int>(data) {
+                    return Self::UdpGro(size);
+                }
+            }
+            (linux_unsafe::SOL_SOCKET, linux_unsafe::SCM_TIMESTAMPNS_OLD) => {
+                if let Some(ts) = read_data::<timespec>(data) {
+                    if let Ok(d) = duration_from_timespec(&ts) {
+                        return Self::TimestampNs(d);
+                    }
+                }
+            }
+            (linux_unsafe::SOL_SOCKET, linux_unsafe::SCM_TIMESTAMPNS_NEW) => {
+                if let Some(ts) = read_data::<kernel_timespec>(data) {
+                    if let Some(d) = duration_from_kernel_timespec(&ts) {
+                        return Self::TimestampNs(d);
+                    }
+                }
+            }
+            (linux_unsafe::SOL_SOCKET, linux_unsafe::SCM_TIMESTAMPING_OLD) => {
+                if let Some(raw) = read_data::<scm_timestamping>(data) {
+                    let ts = raw.ts.map(|ts| duration_from_timespec(&ts).ok());
+                    return Self::Timestamping(Timestamps::new(ts[0], ts[2]));
+                }
+            }
+            (linux_unsafe::SOL_SOCKET, linux_unsafe::SCM_TIMESTAMPING_NEW) => {
+                if let Some(raw) = read_data::<scm_timestamping64>(data) {
+                    let ts = raw.ts.map(|ts| duration_from_kernel_timespec(&ts));
+                    return Self::Timestamping(Timestamps::new(ts[0], ts[2]));
+                }
+            }
+            _ => {}
+        }
+        Self::Other { level, typ, data }
+    }
+}
+
+fn read_data<T: Copy>(data: &[u8]) -> Option<T> {
+    if data.len() < size_of::<T>() {
+        return None;
+    }
+    // Safe because we only use this with plain-old-data kernel types.
+    Some(unsafe { (data.as_ptr() as *const T).read_unaligned() })
+}
+
+fn duration_from_kernel_timespec(ts: &linux_unsafe::kernel_timespec) -> Option<Duration> {
+    if ts.tv_sec < 0 || ts.tv_nsec < 0 || ts.tv_nsec >= 1_000_000_000 {
+        return None;
+    }
+    Some(Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32))
+}
+
+/// Files received in an `SCM_RIGHTS` ancillary data object.
+///
+/// This is an iterator that transfers ownership of each file to the caller.
+/// Any files that the caller doesn't take are closed when it is dropped.
+#[derive(Debug)]
+pub struct ReceivedFiles<'c> {
+    data: &'c mut [u8],
+}
+
+impl<'c> Iterator for ReceivedFiles<'c> {
+    type Item = File;
+
+    fn next(&mut self) -> Option<File> {
+        let data = core::mem::take(&mut self.data);
+        if data.len() < size_of::<int>() {
+            return None;
+        }
+        let (fd, rest) = data.split_at_mut(size_of::<int>());
+        self.data = rest;
+        let fd = int::from_ne_bytes(fd.try_into().unwrap());
+        // Safe because the kernel installed this file descriptor for us, and
+        // we've removed it from the buffer so it can't be taken again.
+        Some(unsafe { File::from_raw_fd(fd) })
+    }
+}
+
+impl<'c> Drop for ReceivedFiles<'c> {
+    fn drop(&mut self) {
+        for file in self.by_ref() {
+            drop(file);
+        }
+    }
+}
+
+/// Process credentials, for use with `SCM_CREDENTIALS` ancillary data and
+/// the [`SO_PEERCRED`](crate::socket::unix::SO_PEERCRED) socket option.
+///
+/// This has the same layout as the kernel's `ucred` structure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct Credentials {
+    pub pid: linux_unsafe::pid_t,
+    pub uid: linux_unsafe::uid_t,
+    pub gid: linux_unsafe::gid_t,
+}
+
+impl Credentials {
+    /// Returns the process ID and real user and group IDs of the current
+    /// process.
+    #[inline]
+    pub fn current() -> Self {
+        unsafe {
+            Self {
+                pid: linux_unsafe::getpid(),
+                uid: linux_unsafe::getuid(),
+                gid: linux_unsafe::getgid(),
+            }
+        }
+    }
+
+    /// Convert from the kernel's representation.
+    #[inline(always)]
+    pub const fn from_raw(raw: linux_unsafe::ucred) -> Self {
+        Self {
+            pid: raw.pid,
+            uid: raw.uid,
+            gid: raw.gid,
+        }
+    }
+
+    /// Convert to the kernel's representation.
+    #[inline(always)]
+    pub const fn to_raw(self) -> linux_unsafe::ucred {
+        linux_unsafe::ucred {
+            pid: self.pid,
+            uid: self.uid,
+            gid: self.gid,
+        }
+    }
+}
+
+/// Information about the interface and addresses of an IPv4 packet, for use
+/// with `IP_PKTINFO` ancillary data.
+#[derive(Clone, Copy, Debug)]
+pub struct PacketInfo {
+    /// The index of the interface that the packet was received on, or for
+    /// sending, the interface to send on or zero to let the kernel choose.
+    pub interface_index: int,
+    /// The local address of the packet, which is the source address when
+    /// sending or the address the kernel would use as the source of a reply
+    /// when receiving.
+    pub local_address: Ipv4Addr,
+    /// The destination address from the header of a received packet. This
+    /// is ignored when sending.
+    pub destination: Ipv4Addr,
+}
+
+impl PacketInfo {
+    /// Convert from the kernel's representation.
+    #[inline(always)]
+    pub const fn from_raw(raw: linux_unsafe::in_pktinfo) -> Self {
+        Self {
+            interface_index: raw.ipi_ifindex,
+            local_address: Ipv4Addr::from_octets(raw.ipi_spec_dst.to_ne_bytes()),
+            destination: Ipv4Addr::from_octets(raw.ipi_addr.to_ne_bytes()),
+        }
+    }
+
+    /// Convert to the kernel's representation.
+    #[inline(always)]
+    pub const fn to_raw(self) -> linux_unsafe::in_pktinfo {
+        linux_unsafe::in_pktinfo {
+            ipi_ifindex: self.interface_index,
+            ipi_spec_dst: u32::from_ne_bytes(self.local_address.as_octets()),
+            ipi_addr: u32::from_ne_bytes(self.destination.as_octets()),
+        }
+    }
+}
+
+/// Packet timestamps from `SO_TIMESTAMPING`, as durations since the Unix
+/// epoch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Timestamps {
+    /// The timestamp generated by the kernel, if any.
+    pub software: Option<Duration>,
+    /// The raw timestamp generated by the network hardware, if any.
+    pub hardware: Option<Duration>,
+}
+
+impl Timestamps {
+    #[inline(always)]
+    fn new(software: Option<Duration>, hardware: Option<Duration>) -> Self {
+        // The kernel reports timestamps it didn't generate as zero.
+        let nonzero = |ts: Option<Duration>| ts.filter(|d| !d.is_zero());
+        Self {
+            software: nonzero(software),
+            hardware: nonzero(hardware),
+        }
+    }
+}
+
+flags_type! {
+    /// Flags for [`crate::File::send_msg`] and [`crate::File::recv_msg`], and
+    /// the flags describing a received message.
+    pub struct MsgFlags(int);
+
+    /// No flags.
+    const NONE = 0;
+    /// Send or receive out-of-band data.
+    const OOB = linux_unsafe::MSG_OOB;
+    /// Receive data without removing it from the queue.
+    const PEEK = linux_unsafe::MSG_PEEK;
+    /// Fail with [`EAGAIN`](crate::result::EAGAIN) instead of blocking.
+    const DONTWAIT = linux_unsafe::MSG_DONTWAIT;
+    /// Wait until the full request is satisfied when receiving from a
+    /// stream socket.
+    const WAITALL = linux_unsafe::MSG_WAITALL;
+    /// Don't raise `SIGPIPE` when sending on a stream socket whose peer has
+    /// closed the connection.
+    const NOSIGNAL = linux_unsafe::MSG_NOSIGNAL;
+    /// More data will follow, so the kernel can delay sending.
+    const MORE = linux_unsafe::MSG_MORE;
+    /// Set the close-on-exec flag on any file descriptors received in
+    /// `SCM_RIGHTS` ancillary data.
+    const CMSG_CLOEXEC = linux_unsafe::MSG_CMSG_CLOEXEC;
+    /// Receive queued errors, such as transmit timestamps.
+    const ERRQUEUE = linux_unsafe::MSG_ERRQUEUE;
+    /// In a received message's flags, indicates the end of a record.
+    const EOR = linux_unsafe::MSG_EOR;
+    /// In a received message's flags, indicates that the data was
+    /// truncated because the buffers were too small.
+    const TRUNC = linux_unsafe::MSG_TRUNC;
+    /// In a received message's flags, indicates that some ancillary data
+    /// was discarded because the control buffer was too small.
+    const CTRUNC = linux_unsafe::MSG_CTRUNC;
+}
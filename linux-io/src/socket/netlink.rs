@@ -0,0 +1,1032 @@
+//! Netlink is a datagram-based protocol family for communicating with the
+//! kernel, used in particular to inspect and configure network interfaces,
+//! addresses and routes using the [`NETLINK_ROUTE`](crate::socket::netlink::NETLINK_ROUTE)
+//! protocol.
+//!
+//! Build request messages in a caller-provided buffer using
+//! [`MessageBuilder`](crate::socket::netlink::MessageBuilder), and decode
+//! responses using [`Messages`](crate::socket::netlink::Messages), neither of
+//! which allocate. [`File::netlink_request`](crate::File::netlink_request)
+//! combines the two to send a request and process each of its responses,
+//! including multipart responses and acknowledgements.
+
+use core::ffi::CStr;
+use core::mem::size_of;
+
+use linux_unsafe::{int, nlmsghdr, rtattr};
+
+use super::ip::{IpAddr, Ipv4Addr, Ipv6Addr};
+use super::msg::MsgFlags;
+use crate::fd::ioctl::{IoDevice, SubDevice};
+use crate::fd::sockopt::{sockopt, DirectSockOpt};
+use crate::fd::IoSlice;
+use crate::result::Result;
+use crate::File;
+
+/// Represents the netlink address family.
+pub const AF_NETLINK: linux_unsafe::sa_family_t = 16;
+
+/// Socket address type for the netlink address family.
+///
+/// A netlink address identifies a socket by its port ID, which is zero for
+/// the kernel, and can also select multicast groups to receive
+/// notifications from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct SockAddrNetlink {
+    nl_family: linux_unsafe::sa_family_t,
+    nl_pad: u16,
+    nl_pid: u32,
+    nl_groups: u32,
+}
+
+impl SockAddrNetlink {
+    /// The address of the kernel, which is the destination for requests.
+    pub const KERNEL: Self = Self::new(0, 0);
+
+    /// Create a new [`SockAddrNetlink`] with the given port ID and bitmask
+    /// of multicast groups.
+    ///
+    /// When binding a socket, a port ID of zero lets the kernel assign a
+    /// unique port ID.
+    #[inline]
+    pub const fn new(port_id: u32, groups: u32) -> Self {
+        Self {
+            nl_family: AF_NETLINK,
+            nl_pad: 0,
+            nl_pid: port_id,
+            nl_groups: groups,
+        }
+    }
+
+    /// Returns the port ID part of the address.
+    #[inline(always)]
+    pub const fn port_id(&self) -> u32 {
+        self.nl_pid
+    }
+
+    /// Returns the bitmask of multicast groups part of the address.
+    #[inline(always)]
+    pub const fn groups(&self) -> u32 {
+        self.nl_groups
+    }
+}
+
+unsafe impl super::SockAddr for SockAddrNetlink {
+    #[inline(always)]
+    unsafe fn sockaddr_raw_const(
+        &self,
+    ) -> (*const linux_unsafe::sockaddr, linux_unsafe::socklen_t) {
+        (
+            self as *const Self as *const _,
+            core::mem::size_of::<Self>() as linux_unsafe::socklen_t,
+        )
+    }
+
+    #[inline(always)]
+    unsafe fn sockaddr_raw_mut(
+        &mut self,
+    ) -> (*mut linux_unsafe::sockaddr, linux_unsafe::socklen_t) {
+        (
+            self as *mut Self as *mut _,
+            core::mem::size_of::<Self>() as linux_unsafe::socklen_t,
+        )
+    }
+}
+
+impl super::SockAddrFromRaw for SockAddrNetlink {
+    fn from_sockaddr_raw(
+        raw: &linux_unsafe::sockaddr_storage,
+        len: linux_unsafe::socklen_t,
+    ) -> Option<Self> {
+        if raw.family != AF_NETLINK || (len as usize) < core::mem::size_of::<Self>() {
+            return None;
+        }
+        // Safe because sockaddr_storage is larger than and at least as
+        // aligned as our type, and we've checked the family.
+        Some(unsafe { *(raw as *const _ as *const Self) })
+    }
+}
+
+/// Device type marker for [`crate::File`] instances that represent netlink
+/// sockets.
+#[derive(Clone, Copy)]
+pub struct NetlinkSocketDevice;
+
+impl IoDevice for NetlinkSocketDevice {}
+unsafe impl SubDevice<super::SocketDevice> for NetlinkSocketDevice {}
+
+/// The routing protocol, for managing network interfaces, addresses, routes
+/// and neighbor tables. Use with [`SOCK_RAW`](super::sock_type::SOCK_RAW).
+pub const NETLINK_ROUTE: super::SocketProtocolFixed<NetlinkSocketDevice> =
+    unsafe { super::socket_protocol(linux_unsafe::NETLINK_ROUTE) };
+
+/// The audit subsystem protocol. Use with
+/// [`SOCK_RAW`](super::sock_type::SOCK_RAW).
+pub const NETLINK_AUDIT: super::SocketProtocolFixed<NetlinkSocketDevice> =
+    unsafe { super::socket_protocol(linux_unsafe::NETLINK_AUDIT) };
+
+/// The protocol for receiving kernel object events ("uevents"), such as
+/// devices being added or removed. Use with
+/// [`SOCK_DGRAM`](super::sock_type::SOCK_DGRAM).
+pub const NETLINK_KOBJECT_UEVENT: super::SocketProtocolFixed<NetlinkSocketDevice> =
+    unsafe { super::socket_protocol(linux_unsafe::NETLINK_KOBJECT_UEVENT) };
+
+/// The generic netlink protocol, which multiplexes families of messages
+/// defined by various kernel subsystems. Use with
+/// [`SOCK_RAW`](super::sock_type::SOCK_RAW).
+pub const NETLINK_GENERIC: super::SocketProtocolFixed<NetlinkSocketDevice> =
+    unsafe { super::socket_protocol(linux_unsafe::NETLINK_GENERIC) };
+
+/// Join the multicast group with the given number, such as one of the
+/// `RTNLGRP_*` constants from [`linux_unsafe`]. This option can only be set.
+pub const NETLINK_ADD_MEMBERSHIP: DirectSockOpt<u32> = unsafe {
+    sockopt(
+        linux_unsafe::SOL_NETLINK,
+        linux_unsafe::NETLINK_ADD_MEMBERSHIP,
+    )
+};
+
+/// Leave the multicast group with the given number. This option can only
+/// be set.
+pub const NETLINK_DROP_MEMBERSHIP: DirectSockOpt<u32> = unsafe {
+    sockopt(
+        linux_unsafe::SOL_NETLINK,
+        linux_unsafe::NETLINK_DROP_MEMBERSHIP,
+    )
+};
+
+/// Don't report [`ENOBUFS`](crate::result::ENOBUFS) when the receive
+/// buffer overflows and notifications are lost.
+///
+/// `1` suppresses the errors, while `0` reports them.
+pub const NETLINK_NO_ENOBUFS: DirectSockOpt<int> =
+    unsafe { sockopt(linux_unsafe::SOL_NETLINK, linux_unsafe::NETLINK_NO_ENOBUFS) };
+
+/// Omit the copy of the original request from the payload of error
+/// messages.
+///
+/// `1` omits the request, while `0` includes it.
+pub const NETLINK_CAP_ACK: DirectSockOpt<int> =
+    unsafe { sockopt(linux_unsafe::SOL_NETLINK, linux_unsafe::NETLINK_CAP_ACK) };
+
+/// Include extended information, such as an error message, in error
+/// messages.
+///
+/// `1` enables extended acknowledgements, while `0` disables them.
+pub const NETLINK_EXT_ACK: DirectSockOpt<int> =
+    unsafe { sockopt(linux_unsafe::SOL_NETLINK, linux_unsafe::NETLINK_EXT_ACK) };
+
+/// Strictly check the headers and attributes of dump requests, so that
+/// the kernel can filter dumps based on them.
+///
+/// `1` enables strict checking, while `0` disables it.
+pub const NETLINK_GET_STRICT_CHK: DirectSockOpt<int> = unsafe {
+    sockopt(
+        linux_unsafe::SOL_NETLINK,
+        linux_unsafe::NETLINK_GET_STRICT_CHK,
+    )
+};
+
+impl File<NetlinkSocketDevice> {
+    /// Send a request message to the kernel and then receive its responses
+    /// into `buf`, passing each response message to `f`.
+    ///
+    /// `request` must start with a single message, typically built using
+    /// [`MessageBuilder`]. Responses are matched to the request by its
+    /// sequence number, and others are ignored.
+    ///
+    /// The request is always sent with [`MessageFlags::ACK`] set, whether or
+    /// not `request` includes it, because otherwise the kernel sends nothing
+    /// at all in response to a successful request that returns no data,
+    /// such as most `NEW` and `DEL` requests. This therefore returns once
+    /// the kernel acknowledges the request, or after the final part of a
+    /// dump, which the kernel ends with `NLMSG_DONE` instead. Errors
+    /// reported by the kernel are returned as errors, as is any error
+    /// returned by `f`.
+    ///
+    /// `buf` must be large enough for each datagram the kernel sends, or
+    /// this fails with [`EMSGSIZE`](crate::result::EMSGSIZE). 32 KiB is
+    /// enough for all routing protocol responses.
+    pub fn netlink_request(
+        &self,
+        request: &[u8],
+        buf: &mut [u8],
+        mut f: impl FnMut(Message<'_>) -> Result<()>,
+    ) -> Result<()> {
+        let req = match Messages::new(request).next() {
+            Some(Ok(msg)) => msg,
+            _ => return Err(crate::result::EINVAL),
+        };
+        let mut hdr = req.header;
+        hdr.nlmsg_flags |= MessageFlags::ACK.raw();
+        // Safe because nlmsghdr is a plain-old-data kernel type.
+        let hdr_bytes = unsafe {
+            core::slice::from_raw_parts(&hdr as *const nlmsghdr as *const u8, size_of::<nlmsghdr>())
+        };
+        self.send_msg_to(
+            SockAddrNetlink::KERNEL,
+            &[
+                IoSlice::new(hdr_bytes),
+                IoSlice::new(&request[size_of::<nlmsghdr>()..]),
+            ],
+            &[],
+            MsgFlags::NONE,
+        )?;
+        loop {
+            let (len, from) = self.recv_from_as::<SockAddrNetlink>(buf, MsgFlags::TRUNC)?;
+            if len > buf.len() {
+                return Err(crate::result::EMSGSIZE);
+            }
+            if !matches!(from, Some(from) if from.port_id() == 0) {
+                continue;
+            }
+            for msg in Messages::new(&buf[..len]) {
+                let msg = msg?;
+                if msg.seq() != req.seq() {
+                    continue;
+                }
+                if let Some(result) = msg.result() {
+                    return result;
+                }
+                if msg.msg_type() == linux_unsafe::NLMSG_NOOP {
+                    continue;
+                }
+                f(msg)?;
+            }
+        }
+    }
+}
+
+const NLMSG_HDRLEN: usize = nl_align(size_of::<nlmsghdr>());
+const RTA_HDRLEN: usize = nl_align(size_of::<rtattr>());
+
+/// Rounds up to the four-byte alignment used for both messages and
+/// attributes.
+const fn nl_align(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+/// Implemented by the fixed-size protocol headers that follow the netlink
+/// message header, such as [`linux_unsafe::ifinfomsg`].
+///
+/// Safety: Implementers must be plain-old-data types without padding, for
+/// which any bit pattern is valid.
+pub unsafe trait FixedHeader: Copy {}
+
+unsafe impl FixedHeader for linux_unsafe::ifinfomsg {}
+unsafe impl FixedHeader for linux_unsafe::ifaddrmsg {}
+unsafe impl FixedHeader for linux_unsafe::rtmsg {}
+
+/// Builds a netlink message in a caller-provided buffer.
+///
+/// A message consists of a header, typically followed by a protocol-specific
+/// fixed header and then a sequence of attributes. Each method that adds
+/// content fails with [`ENOBUFS`](crate::result::ENOBUFS) if the content
+/// doesn't fit in the remaining buffer space, leaving the existing content
+/// unchanged.
+#[derive(Debug)]
+pub struct MessageBuilder<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> MessageBuilder<'a> {
+    /// Start building a message of the given type in the given buffer.
+    ///
+    /// Requests to the kernel must include [`MessageFlags::REQUEST`].
+    pub fn new(buf: &'a mut [u8], msg_type: u16, flags: MessageFlags, seq: u32) -> Result<Self> {
+        if buf.len() < NLMSG_HDRLEN {
+            return Err(crate::result::ENOBUFS);
+        }
+        let mut ret = Self { buf, len: 0 };
+        let hdr = nlmsghdr {
+            nlmsg_len: 0,
+            nlmsg_type: msg_type,
+            nlmsg_flags: flags.raw(),
+            nlmsg_seq: seq,
+            nlmsg_pid: 0,
+        };
+        ret.push_value(&hdr)?;
+        Ok(ret)
+    }
+
+    /// Add the protocol-specific fixed header, such as
+    /// [`linux_unsafe::ifinfomsg`] for link messages.
+    #[inline]
+    pub fn header<H: FixedHeader>(&mut self, header: &H) -> Result<()> {
+        self.push_value(header)
+    }
+
+    /// Add an attribute with the given type and raw data.
+    pub fn attr(&mut self, typ: u16, data: &[u8]) -> Result<()> {
+        let start = self.len;
+        self.push_attr_header(typ, data.len())?;
+        if let Err(e) = self.push_bytes(data) {
+            self.len = start;
+            self.update_len();
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Add an attribute containing a `u8` value.
+    #[inline]
+    pub fn attr_u8(&mut self, typ: u16, v: u8) -> Result<()> {
+        self.attr(typ, &[v])
+    }
+
+    /// Add an attribute containing a `u16` value in native byte order.
+    #[inline]
+    pub fn attr_u16(&mut self, typ: u16, v: u16) -> Result<()> {
+        self.attr(typ, &v.to_ne_bytes())
+    }
+
+    /// Add an attribute containing a `u32` value in native byte order.
+    #[inline]
+    pub fn attr_u32(&mut self, typ: u16, v: u32) -> Result<()> {
+        self.attr(typ, &v.to_ne_bytes())
+    }
+
+    /// Add an attribute containing a `u64` value in native byte order.
+    #[inline]
+    pub fn attr_u64(&mut self, typ: u16, v: u64) -> Result<()> {
+        self.attr(typ, &v.to_ne_bytes())
+    }
+
+    /// Add an attribute containing a null-terminated string.
+    #[inline]
+    pub fn attr_cstr(&mut self, typ: u16, v: &CStr) -> Result<()> {
+        self.attr(typ, v.to_bytes_with_nul())
+    }
+
+    /// Add an attribute containing an IPv4 or IPv6 address in network byte
+    /// order.
+    #[inline]
+    pub fn attr_ip_addr(&mut self, typ: u16, v: impl Into<IpAddr>) -> Result<()> {
+        match v.into() {
+            IpAddr::V4(addr) => self.attr(typ, &addr.as_octets()),
+            IpAddr::V6(addr) => self.attr(typ, &addr.as_octets()),
+        }
+    }
+
+    /// Add an attribute whose data is a sequence of further attributes,
+    /// added by `f`.
+    ///
+    /// If `f` fails then the nested attribute is removed and its error is
+    /// returned.
+    pub fn nested(&mut self, typ: u16, f: impl FnOnce(&mut Self) -> Result<()>) -> Result<()> {
+        let start = self.len;
+        self.push_attr_header(typ | linux_unsafe::NLA_F_NESTED, 0)?;
+        if let Err(e) = f(self) {
+            self.len = start;
+            self.update_len();
+            return Err(e);
+        }
+        let attr_len = self.len - start;
+        if attr_len > u16::MAX as usize {
+            self.len = start;
+            self.update_len();
+            return Err(crate::result::EMSGSIZE);
+        }
+        self.buf[start..start + 2].copy_from_slice(&(attr_len as u16).to_ne_bytes());
+        Ok(())
+    }
+
+    /// Returns the sequence number of the message.
+    #[inline]
+    pub fn seq(&self) -> u32 {
+        let hdr = unsafe { (self.buf.as_ptr() as *const nlmsghdr).read_unaligned() };
+        hdr.nlmsg_seq
+    }
+
+    /// Returns the message built so far, to send to the kernel.
+    #[inline(always)]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    fn push_attr_header(&mut self, typ: u16, data_len: usize) -> Result<()> {
+        let attr_len = RTA_HDRLEN + data_len;
+        if attr_len > u16::MAX as usize {
+            return Err(crate::result::EMSGSIZE);
+        }
+        if self.buf.len() - self.len < nl_align(attr_len) {
+            return Err(crate::result::ENOBUFS);
+        }
+        self.push_value(&rtattr {
+            rta_len: attr_len as u16,
+            rta_type: typ,
+        })
+    }
+
+    #[inline]
+    fn push_value<T: Copy>(&mut self, v: &T) -> Result<()> {
+        // Safe because T is a plain-old-data kernel type.
+        let bytes =
+            unsafe { core::slice::from_raw_parts(v as *const T as *const u8, size_of::<T>()) };
+        self.push_bytes(bytes)
+    }
+
+    fn push_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        let space = nl_align(bytes.len());
+        if self.buf.len() - self.len < space {
+            return Err(crate::result::ENOBUFS);
+        }
+        let dst = &mut self.buf[self.len..self.len + space];
+        dst[..bytes.len()].copy_from_slice(bytes);
+        dst[bytes.len()..].fill(0);
+        self.len += space;
+        self.update_len();
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn update_len(&mut self) {
+        self.buf[..4].copy_from_slice(&(self.len as u32).to_ne_bytes());
+    }
+}
+
+/// An iterator over the netlink messages in a buffer, such as a datagram
+/// received from a netlink socket.
+///
+/// If the buffer contains a malformed message then the iterator produces
+/// [`EPROTO`](crate::result::EPROTO) and then stops.
+#[derive(Clone, Debug)]
+pub struct Messages<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Messages<'a> {
+    /// Iterate over the messages in the given buffer.
+    #[inline(always)]
+    pub const fn new(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+}
+
+impl<'a> Iterator for Messages<'a> {
+    type Item = Result<Message<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let buf = core::mem::take(&mut self.buf);
+        if buf.is_empty() {
+            return None;
+        }
+        let header = match read_value::<nlmsghdr>(buf) {
+            Some(header) => header,
+            None => return Some(Err(crate::result::EPROTO)),
+        };
+        let len = header.nlmsg_len as usize;
+        if len < NLMSG_HDRLEN || len > buf.len() {
+            return Some(Err(crate::result::EPROTO));
+        }
+        let next = nl_align(len);
+        self.buf = if next < buf.len() { &buf[next..] } else { &[] };
+        Some(Ok(Message {
+            header,
+            payload: &buf[NLMSG_HDRLEN..len],
+        }))
+    }
+}
+
+/// A netlink message, as produced by [`Messages`].
+#[derive(Clone, Copy, Debug)]
+pub struct Message<'a> {
+    header: nlmsghdr,
+    payload: &'a [u8],
+}
+
+impl<'a> Message<'a> {
+    /// Returns the message type, which is either one of the `NLMSG_*`
+    /// control message types or a protocol-specific type such as
+    /// `RTM_NEWLINK`.
+    #[inline(always)]
+    pub const fn msg_type(&self) -> u16 {
+        self.header.nlmsg_type
+    }
+
+    /// Returns the message flags.
+    #[inline(always)]
+    pub const fn flags(&self) -> MessageFlags {
+        MessageFlags::from_raw(self.header.nlmsg_flags)
+    }
+
+    /// Returns the sequence number, which for a response matches the
+    /// sequence number of the request.
+    #[inline(always)]
+    pub const fn seq(&self) -> u32 {
+        self.header.nlmsg_seq
+    }
+
+    /// Returns the port ID of the sender, which is zero for the kernel.
+    #[inline(always)]
+    pub const fn port_id(&self) -> u32 {
+        self.header.nlmsg_pid
+    }
+
+    /// Returns the message payload, which follows the message header.
+    #[inline(always)]
+    pub const fn payload(&self) -> &'a [u8] {
+        self.payload
+    }
+
+    /// Returns `Some` if this message marks the end of a response, with
+    /// the result it reports, or `None` for any other message.
+    ///
+    /// Acknowledgements and the `NLMSG_DONE` message at the end of a
+    /// multipart response report success, while error messages report the
+    /// kernel's error code.
+    pub fn result(&self) -> Option<Result<()>> {
+        let err = match self.header.nlmsg_type {
+            linux_unsafe::NLMSG_ERROR => match read_value::<int>(self.payload) {
+                Some(err) => err,
+                None => return Some(Err(crate::result::EPROTO)),
+            },
+            // A dump that failed part way through reports its error in
+            // the payload of the final message, but that payload is
+            // optional.
+            linux_unsafe::NLMSG_DONE => read_value::<int>(self.payload).unwrap_or(0),
+            linux_unsafe::NLMSG_OVERRUN => return Some(Err(crate::result::ENOBUFS)),
+            _ => return None,
+        };
+        if err < 0 {
+            Some(Err(crate::result::Error::new(-err)))
+        } else {
+            Some(Ok(()))
+        }
+    }
+
+    /// Decodes the payload as the fixed header `H` followed by attributes,
+    /// or returns `None` if the payload is too short.
+    #[inline]
+    pub fn decode<H: FixedHeader>(&self) -> Option<(H, Attributes<'a>)> {
+        let header = read_value::<H>(self.payload)?;
+        let start = nl_align(size_of::<H>());
+        let attrs = if start < self.payload.len() {
+            &self.payload[start..]
+        } else {
+            &[]
+        };
+        Some((header, Attributes::new(attrs)))
+    }
+
+    /// Decodes an `RTM_NEWLINK` or `RTM_DELLINK` message, or returns `None`
+    /// for any other message.
+    #[inline]
+    pub fn link(&self) -> Option<LinkMessage<'a>> {
+        match self.msg_type() {
+            linux_unsafe::RTM_NEWLINK | linux_unsafe::RTM_DELLINK => {
+                let (header, attrs) = self.decode()?;
+                Some(LinkMessage { header, attrs })
+            }
+            _ => None,
+        }
+    }
+
+    /// Decodes an `RTM_NEWADDR` or `RTM_DELADDR` message, or returns `None`
+    /// for any other message.
+    #[inline]
+    pub fn address(&self) -> Option<AddressMessage<'a>> {
+        match self.msg_type() {
+            linux_unsafe::RTM_NEWADDR | linux_unsafe::RTM_DELADDR => {
+                let (header, attrs) = self.decode()?;
+                Some(AddressMessage { header, attrs })
+            }
+            _ => None,
+        }
+    }
+
+    /// Decodes an `RTM_NEWROUTE` or `RTM_DELROUTE` message, or returns
+    /// `None` for any other message.
+    #[inline]
+    pub fn route(&self) -> Option<RouteMessage<'a>> {
+        match self.msg_type() {
+            linux_unsafe::RTM_NEWROUTE | linux_unsafe::RTM_DELROUTE => {
+                let (header, attrs) = self.decode()?;
+                Some(RouteMessage { header, attrs })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// An iterator over a sequence of netlink attributes, such as those
+/// following the fixed header of a message or nested inside another
+/// attribute.
+///
+/// The iterator stops early if it encounters a malformed attribute.
+#[derive(Clone, Debug)]
+pub struct Attributes<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Attributes<'a> {
+    /// Iterate over the attributes in the given buffer.
+    #[inline(always)]
+    pub const fn new(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+
+    /// Returns the first attribute with the given type, if any.
+    #[inline]
+    pub fn get(&self, typ: u16) -> Option<Attribute<'a>> {
+        self.clone().find(|attr| attr.typ() == typ)
+    }
+}
+
+impl<'a> Iterator for Attributes<'a> {
+    type Item = Attribute<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let buf = core::mem::take(&mut self.buf);
+        let header = read_value::<rtattr>(buf)?;
+        let len = header.rta_len as usize;
+        if len < RTA_HDRLEN || len > buf.len() {
+            return None;
+        }
+        let next = nl_align(len);
+        self.buf = if next < buf.len() { &buf[next..] } else { &[] };
+        Some(Attribute {
+            typ: header.rta_type,
+            data: &buf[RTA_HDRLEN..len],
+        })
+    }
+}
+
+/// A netlink attribute, as produced by [`Attributes`].
+#[derive(Clone, Copy, Debug)]
+pub struct Attribute<'a> {
+    typ: u16,
+    data: &'a [u8],
+}
+
+impl<'a> Attribute<'a> {
+    /// Returns the attribute type, excluding the flag bits.
+    #[inline(always)]
+    pub const fn typ(&self) -> u16 {
+        self.typ & linux_unsafe::NLA_TYPE_MASK
+    }
+
+    /// Returns true if the attribute is flagged as containing nested
+    /// attributes.
+    ///
+    /// Not all protocols set this flag, so [`Self::nested`] can be used
+    /// regardless of it for attributes known to contain nested attributes.
+    #[inline(always)]
+    pub const fn is_nested(&self) -> bool {
+        (self.typ & linux_unsafe::NLA_F_NESTED) != 0
+    }
+
+    /// Returns the attribute's data.
+    #[inline(always)]
+    pub const fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Interprets the data as a sequence of nested attributes.
+    #[inline(always)]
+    pub const fn nested(&self) -> Attributes<'a> {
+        Attributes::new(self.data)
+    }
+
+    /// Interprets the data as a `u8` value.
+    #[inline]
+    pub fn as_u8(&self) -> Option<u8> {
+        self.data.try_into().ok().map(u8::from_ne_bytes)
+    }
+
+    /// Interprets the data as a `u16` value in native byte order.
+    #[inline]
+    pub fn as_u16(&self) -> Option<u16> {
+        self.data.try_into().ok().map(u16::from_ne_bytes)
+    }
+
+    /// Interprets the data as a `u32` value in native byte order.
+    #[inline]
+    pub fn as_u32(&self) -> Option<u32> {
+        self.data.try_into().ok().map(u32::from_ne_bytes)
+    }
+
+    /// Interprets the data as a `u64` value in native byte order.
+    #[inline]
+    pub fn as_u64(&self) -> Option<u64> {
+        self.data.try_into().ok().map(u64::from_ne_bytes)
+    }
+
+    /// Interprets the data as a null-terminated string, ignoring anything
+    /// after the first null byte.
+    #[inline]
+    pub fn as_cstr(&self) -> Option<&'a CStr> {
+        let end = self.data.iter().position(|b| *b == 0)?;
+        // Safe because we've found the first null byte.
+        Some(unsafe { CStr::from_bytes_with_nul_unchecked(&self.data[..=end]) })
+    }
+
+    /// Interprets the data as an IPv4 or IPv6 address in network byte
+    /// order, depending on its length.
+    #[inline]
+    pub fn as_ip_addr(&self) -> Option<IpAddr> {
+        if let Ok(octets) = self.data.try_into() {
+            return Some(IpAddr::V4(Ipv4Addr::from_octets(octets)));
+        }
+        if let Ok(octets) = self.data.try_into() {
+            return Some(IpAddr::V6(Ipv6Addr::from_octets(octets)));
+        }
+        None
+    }
+}
+
+/// A decoded `RTM_NEWLINK` or `RTM_DELLINK` message, describing a network
+/// interface.
+#[derive(Clone, Debug)]
+pub struct LinkMessage<'a> {
+    header: linux_unsafe::ifinfomsg,
+    attrs: Attributes<'a>,
+}
+
+impl<'a> LinkMessage<'a> {
+    /// Returns the fixed header of the message.
+    #[inline(always)]
+    pub const fn header(&self) -> &linux_unsafe::ifinfomsg {
+        &self.header
+    }
+
+    /// Returns the interface index.
+    #[inline(always)]
+    pub const fn index(&self) -> int {
+        self.header.ifi_index
+    }
+
+    /// Returns the `IFF_*` interface flags.
+    #[inline(always)]
+    pub const fn flags(&self) -> linux_unsafe::uint {
+        self.header.ifi_flags
+    }
+
+    /// Returns the `ARPHRD_*` hardware type of the interface.
+    #[inline(always)]
+    pub const fn link_type(&self) -> u16 {
+        self.header.ifi_type
+    }
+
+    /// Returns an iterator over the message's `IFLA_*` attributes.
+    #[inline(always)]
+    pub fn attrs(&self) -> Attributes<'a> {
+        self.attrs.clone()
+    }
+
+    /// Returns the interface name, from the `IFLA_IFNAME` attribute.
+    #[inline]
+    pub fn name(&self) -> Option<&'a CStr> {
+        self.attrs.get(linux_unsafe::IFLA_IFNAME)?.as_cstr()
+    }
+
+    /// Returns the maximum transmission unit, from the `IFLA_MTU`
+    /// attribute.
+    #[inline]
+    pub fn mtu(&self) -> Option<u32> {
+        self.attrs.get(linux_unsafe::IFLA_MTU)?.as_u32()
+    }
+
+    /// Returns the hardware address, from the `IFLA_ADDRESS` attribute.
+    #[inline]
+    pub fn hardware_address(&self) -> Option<&'a [u8]> {
+        Some(self.attrs.get(linux_unsafe::IFLA_ADDRESS)?.data())
+    }
+}
+
+/// A decoded `RTM_NEWADDR` or `RTM_DELADDR` message, describing an address
+/// of a network interface.
+#[derive(Clone, Debug)]
+pub struct AddressMessage<'a> {
+    header: linux_unsafe::ifaddrmsg,
+    attrs: Attributes<'a>,
+}
+
+impl<'a> AddressMessage<'a> {
+    /// Returns the fixed header of the message.
+    #[inline(always)]
+    pub const fn header(&self) -> &linux_unsafe::ifaddrmsg {
+        &self.header
+    }
+
+    /// Returns the address family, such as
+    /// [`AF_INET`](crate::socket::ip::AF_INET).
+    #[inline(always)]
+    pub const fn family(&self) -> linux_unsafe::sa_family_t {
+        self.header.ifa_family as linux_unsafe::sa_family_t
+    }
+
+    /// Returns the length of the network prefix, in bits.
+    #[inline(always)]
+    pub const fn prefix_len(&self) -> u8 {
+        self.header.ifa_prefixlen
+    }
+
+    /// Returns the `RT_SCOPE_*` scope of the address.
+    #[inline(always)]
+    pub const fn scope(&self) -> u8 {
+        self.header.ifa_scope
+    }
+
+    /// Returns the index of the interface that the address belongs to.
+    #[inline(always)]
+    pub const fn index(&self) -> u32 {
+        self.header.ifa_index
+    }
+
+    /// Returns the `IFA_F_*` address flags, from the `IFA_FLAGS` attribute
+    /// if present and otherwise from the fixed header.
+    #[inline]
+    pub fn flags(&self) -> u32 {
+        match self.attrs.get(linux_unsafe::IFA_FLAGS) {
+            Some(attr) => attr.as_u32().unwrap_or(0),
+            None => self.header.ifa_flags as u32,
+        }
+    }
+
+    /// Returns an iterator over the message's `IFA_*` attributes.
+    #[inline(always)]
+    pub fn attrs(&self) -> Attributes<'a> {
+        self.attrs.clone()
+    }
+
+    /// Returns the address of the interface, from the `IFA_LOCAL`
+    /// attribute.
+    ///
+    /// This differs from [`Self::address`] only for point-to-point
+    /// interfaces.
+    #[inline]
+    pub fn local(&self) -> Option<IpAddr> {
+        self.attrs.get(linux_unsafe::IFA_LOCAL)?.as_ip_addr()
+    }
+
+    /// Returns the address from the `IFA_ADDRESS` attribute, which is the
+    /// address of the interface, or of the remote end of a point-to-point
+    /// interface.
+    #[inline]
+    pub fn address(&self) -> Option<IpAddr> {
+        self.attrs.get(linux_unsafe::IFA_ADDRESS)?.as_ip_addr()
+    }
+
+    /// Returns the address label, from the `IFA_LABEL` attribute.
+    #[inline]
+    pub fn label(&self) -> Option<&'a CStr> {
+        self.attrs.get(linux_unsafe::IFA_LABEL)?.as_cstr()
+    }
+}
+
+/// A decoded `RTM_NEWROUTE` or `RTM_DELROUTE` message, describing a route.
+#[derive(Clone, Debug)]
+pub struct RouteMessage<'a> {
+    header: linux_unsafe::rtmsg,
+    attrs: Attributes<'a>,
+}
+
+impl<'a> RouteMessage<'a> {
+    /// Returns the fixed header of the message.
+    #[inline(always)]
+    pub const fn header(&self) -> &linux_unsafe::rtmsg {
+        &self.header
+    }
+
+    /// Returns the address family, such as
+    /// [`AF_INET`](crate::socket::ip::AF_INET).
+    #[inline(always)]
+    pub const fn family(&self) -> linux_unsafe::sa_family_t {
+        self.header.rtm_family as linux_unsafe::sa_family_t
+    }
+
+    /// Returns the length of the destination prefix, in bits.
+    #[inline(always)]
+    pub const fn destination_len(&self) -> u8 {
+        self.header.rtm_dst_len
+    }
+
+    /// Returns the length of the source prefix, in bits.
+    #[inline(always)]
+    pub const fn source_len(&self) -> u8 {
+        self.header.rtm_src_len
+    }
+
+    /// Returns the `RTPROT_*` value describing where the route came from.
+    #[inline(always)]
+    pub const fn protocol(&self) -> u8 {
+        self.header.rtm_protocol
+    }
+
+    /// Returns the `RT_SCOPE_*` scope of the route.
+    #[inline(always)]
+    pub const fn scope(&self) -> u8 {
+        self.header.rtm_scope
+    }
+
+    /// Returns the `RTN_*` route type.
+    #[inline(always)]
+    pub const fn route_type(&self) -> u8 {
+        self.header.rtm_type
+    }
+
+    /// Returns the routing table ID, from the `RTA_TABLE` attribute if
+    /// present and otherwise from the fixed header.
+    #[inline]
+    pub fn table(&self) -> u32 {
+        match self.attrs.get(linux_unsafe::RTA_TABLE) {
+            Some(attr) => attr.as_u32().unwrap_or(0),
+            None => self.header.rtm_table as u32,
+        }
+    }
+
+    /// Returns an iterator over the message's `RTA_*` attributes.
+    #[inline(always)]
+    pub fn attrs(&self) -> Attributes<'a> {
+        self.attrs.clone()
+    }
+
+    /// Returns the destination prefix, from the `RTA_DST` attribute. This
+    /// is absent for a default route.
+    #[inline]
+    pub fn destination(&self) -> Option<IpAddr> {
+        self.attrs.get(linux_unsafe::RTA_DST)?.as_ip_addr()
+    }
+
+    /// Returns the gateway address, from the `RTA_GATEWAY` attribute.
+    #[inline]
+    pub fn gateway(&self) -> Option<IpAddr> {
+        self.attrs.get(linux_unsafe::RTA_GATEWAY)?.as_ip_addr()
+    }
+
+    /// Returns the preferred source address, from the `RTA_PREFSRC`
+    /// attribute.
+    #[inline]
+    pub fn preferred_source(&self) -> Option<IpAddr> {
+        self.attrs.get(linux_unsafe::RTA_PREFSRC)?.as_ip_addr()
+    }
+
+    /// Returns the index of the output interface, from the `RTA_OIF`
+    /// attribute.
+    #[inline]
+    pub fn output_interface(&self) -> Option<u32> {
+        self.attrs.get(linux_unsafe::RTA_OIF)?.as_u32()
+    }
+
+    /// Returns the route priority, or metric, from the `RTA_PRIORITY`
+    /// attribute.
+    #[inline]
+    pub fn priority(&self) -> Option<u32> {
+        self.attrs.get(linux_unsafe::RTA_PRIORITY)?.as_u32()
+    }
+}
+
+fn read_value<T: Copy>(buf: &[u8]) -> Option<T> {
+    if buf.len() < size_of::<T>() {
+        return None;
+    }
+    // Safe because we only use this with plain-old-data kernel types.
+    Some(unsafe { (buf.as_ptr() as *const T).read_unaligned() })
+}
+
+flags_type! {
+    /// Flags for netlink message headers.
+    ///
+    /// Some flag values have different meanings depending on the kind of
+    /// request, and so there are multiple constants with the same value.
+    pub struct MessageFlags(u16);
+
+    /// No flags.
+    const NONE = 0;
+    /// The message is a request. All messages sent to the kernel must
+    /// include this flag.
+    const REQUEST = linux_unsafe::NLM_F_REQUEST;
+    /// The message is part of a multipart response, which ends with an
+    /// `NLMSG_DONE` message.
+    const MULTI = linux_unsafe::NLM_F_MULTI;
+    /// Request an acknowledgement on success.
+    const ACK = linux_unsafe::NLM_F_ACK;
+    /// Echo this request back to the sender.
+    const ECHO = linux_unsafe::NLM_F_ECHO;
+    /// In a response, indicates that a dump was inconsistent because the
+    /// data changed while it was in progress.
+    const DUMP_INTR = linux_unsafe::NLM_F_DUMP_INTR;
+    /// In a response, indicates that a dump was filtered as requested.
+    const DUMP_FILTERED = linux_unsafe::NLM_F_DUMP_FILTERED;
+    /// For "get" requests, return the complete table instead of a single
+    /// entry.
+    const ROOT = linux_unsafe::NLM_F_ROOT;
+    /// For "get" requests, return all entries matching the criteria in the
+    /// request.
+    const MATCH = linux_unsafe::NLM_F_MATCH;
+    /// For "get" requests, return an atomic snapshot of the table.
+    const ATOMIC = linux_unsafe::NLM_F_ATOMIC;
+    /// For "get" requests, return all entries as a multipart response.
+    const DUMP = linux_unsafe::NLM_F_DUMP;
+    /// For "new" requests, replace an existing matching object.
+    const REPLACE = linux_unsafe::NLM_F_REPLACE;
+    /// For "new" requests, fail if a matching object already exists.
+    const EXCL = linux_unsafe::NLM_F_EXCL;
+    /// For "new" requests, create the object if it doesn't already exist.
+    const CREATE = linux_unsafe::NLM_F_CREATE;
+    /// For "new" requests, add the object to the end of the list.
+    const APPEND = linux_unsafe::NLM_F_APPEND;
+}
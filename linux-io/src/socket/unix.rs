@@ -0,0 +1,271 @@
+use core::ffi::CStr;
+use core::mem::size_of;
+
+use crate::fd::ioctl::{ioctl_read, IoDevice, IoctlReqRead, SubDevice};
+use crate::fd::sockopt::{sockopt_readonly, DirectSockOptReadOnly, GetSockOpt};
+use crate::process::PidFdDevice;
+use crate::result::Result;
+use crate::File;
+use linux_unsafe::int;
+
+/// Represents the Unix domain (local) address family.
+pub const AF_UNIX: linux_unsafe::sa_family_t = 1;
+
+/// The size of the path field in the kernel's `sockaddr_un` structure.
+const SUN_PATH_LEN: usize = 108;
+
+/// The offset of the path field in the kernel's `sockaddr_un` structure.
+const SUN_PATH_OFFSET: usize = size_of::<linux_unsafe::sa_family_t>();
+
+/// Socket address type for the Unix domain address family.
+///
+/// A Unix domain socket address is either a filesystem path, a name in the
+/// Linux-specific abstract namespace, or unnamed. See [`UnixAddress`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[repr(C)]
+pub struct SockAddrUnix {
+    sun_family: linux_unsafe::sa_family_t,
+    // One byte longer than the kernel's sun_path field, with the extra byte
+    // always zero so that even a path that fills the kernel's field is
+    // terminated.
+    sun_path: [u8; SUN_PATH_LEN + 1],
+    len: linux_unsafe::socklen_t,
+}
+
+impl SockAddrUnix {
+    /// Returns the unnamed address, as used by a socket that isn't bound.
+    #[inline]
+    pub const fn unnamed() -> Self {
+        Self {
+            sun_family: AF_UNIX,
+            sun_path: [0; SUN_PATH_LEN + 1],
+            len: SUN_PATH_OFFSET as linux_unsafe::socklen_t,
+        }
+    }
+
+    /// Create an address referring to the given filesystem path.
+    ///
+    /// Fails with [`EINVAL`](crate::result::EINVAL) if the path is empty, or
+    /// [`ENAMETOOLONG`](crate::result::ENAMETOOLONG) if the path and its
+    /// terminating null byte don't fit in the kernel's 108-byte limit.
+    #[inline]
+    pub fn from_pathname(path: &CStr) -> Result<Self> {
+        let path = path.to_bytes_with_nul();
+        if path.len() == 1 {
+            return Err(crate::result::EINVAL);
+        }
+        Self::from_path_bytes(path)
+    }
+
+    /// Create an address referring to the given name in the abstract
+    /// namespace, which is independent of the filesystem.
+    ///
+    /// The name is arbitrary bytes, and can include null bytes. Fails with
+    /// [`ENAMETOOLONG`](crate::result::ENAMETOOLONG) if the name is longer
+    /// than 107 bytes.
+    #[inline]
+    pub fn from_abstract(name: &[u8]) -> Result<Self> {
+        if name.len() >= SUN_PATH_LEN {
+            return Err(crate::result::ENAMETOOLONG);
+        }
+        let mut path = [0_u8; SUN_PATH_LEN];
+        path[1..name.len() + 1].copy_from_slice(name);
+        Self::from_path_bytes(&path[..name.len() + 1])
+    }
+
+    #[inline(always)]
+    fn from_path_bytes(path: &[u8]) -> Result<Self> {
+        if path.len() > SUN_PATH_LEN {
+            return Err(crate::result::ENAMETOOLONG);
+        }
+        let mut ret = Self::unnamed();
+        ret.sun_path[..path.len()].copy_from_slice(path);
+        ret.len = (SUN_PATH_OFFSET + path.len()) as linux_unsafe::socklen_t;
+        Ok(ret)
+    }
+
+    /// Returns the kind of address this is, along with its path or name.
+    #[inline]
+    pub fn address(&self) -> UnixAddress<'_> {
+        let path_len = self.len as usize - SUN_PATH_OFFSET;
+        if path_len == 0 {
+            UnixAddress::Unnamed
+        } else if self.sun_path[0] == 0 {
+            UnixAddress::Abstract(&self.sun_path[1..path_len])
+        } else {
+            // The final byte of sun_path is always zero, so there is always
+            // a null terminator to find.
+            let end = self.sun_path.iter().position(|b| *b == 0).unwrap();
+            UnixAddress::Pathname(unsafe {
+                CStr::from_bytes_with_nul_unchecked(&self.sun_path[..=end])
+            })
+        }
+    }
+}
+
+impl core::fmt::Debug for SockAddrUnix {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("SockAddrUnix")
+            .field(&self.address())
+            .finish()
+    }
+}
+
+/// The different kinds of Unix domain socket address, as returned by
+/// [`SockAddrUnix::address`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnixAddress<'a> {
+    /// The address of a socket that isn't bound, such as either end of a
+    /// pair created by [`crate::socketpair`].
+    Unnamed,
+    /// A path in the filesystem.
+    Pathname(&'a CStr),
+    /// A name in the abstract namespace, excluding the leading null byte
+    /// that distinguishes it from a path.
+    Abstract(&'a [u8]),
+}
+
+unsafe impl super::SockAddr for SockAddrUnix {
+    #[inline(always)]
+    unsafe fn sockaddr_raw_const(
+        &self,
+    ) -> (*const linux_unsafe::sockaddr, linux_unsafe::socklen_t) {
+        (self as *const Self as *const _, self.len)
+    }
+
+    /// Returns a zero length, so that the kernel won't write an address here.
+    ///
+    /// The meaning of a Unix domain address depends on the length that the
+    /// kernel reports alongside it, which this type has no way to learn
+    /// through a raw pointer. Retrieve addresses from the kernel using the
+    /// [`SockAddrFromRaw`](super::SockAddrFromRaw) implementation instead,
+    /// such as through [`crate::File::local_addr_as`].
+    #[inline(always)]
+    unsafe fn sockaddr_raw_mut(
+        &mut self,
+    ) -> (*mut linux_unsafe::sockaddr, linux_unsafe::socklen_t) {
+        (self as *mut Self as *mut _, 0)
+    }
+}
+
+impl super::SockAddrFromRaw for SockAddrUnix {
+    fn from_sockaddr_raw(
+        raw: &linux_unsafe::sockaddr_storage,
+        len: linux_unsafe::socklen_t,
+    ) -> Option<Self> {
+        // The kernel reports the address of an unbound peer as having
+        // zero length, without even a family field.
+        if (len as usize) <= SUN_PATH_OFFSET {
+            return (len >= 0).then(Self::unnamed);
+        }
+        if raw.family != AF_UNIX {
+            return None;
+        }
+        let path_len = len as usize - SUN_PATH_OFFSET;
+        let path_len = if path_len > SUN_PATH_LEN {
+            SUN_PATH_LEN
+        } else {
+            path_len
+        };
+        let mut ret = Self::unnamed();
+        ret.sun_path[..path_len].copy_from_slice(&raw.data[..path_len]);
+        ret.len = (SUN_PATH_OFFSET + path_len) as linux_unsafe::socklen_t;
+        Some(ret)
+    }
+}
+
+/// Device type marker for [`crate::File`] instances that represent Unix
+/// domain sockets of any type.
+#[derive(Clone, Copy)]
+pub struct UnixSocketDevice;
+
+impl IoDevice for UnixSocketDevice {}
+unsafe impl SubDevice<super::SocketDevice> for UnixSocketDevice {}
+
+/// Device type marker for [`crate::File`] instances that represent Unix
+/// domain stream sockets.
+#[derive(Clone, Copy)]
+pub struct UnixStreamDevice;
+
+impl IoDevice for UnixStreamDevice {}
+unsafe impl SubDevice<UnixSocketDevice> for UnixStreamDevice {}
+unsafe impl SubDevice<super::SocketDevice> for UnixStreamDevice {}
+
+/// Device type marker for [`crate::File`] instances that represent Unix
+/// domain datagram sockets.
+#[derive(Clone, Copy)]
+pub struct UnixDatagramDevice;
+
+impl IoDevice for UnixDatagramDevice {}
+unsafe impl SubDevice<UnixSocketDevice> for UnixDatagramDevice {}
+unsafe impl SubDevice<super::SocketDevice> for UnixDatagramDevice {}
+
+/// Device type marker for [`crate::File`] instances that represent Unix
+/// domain sequenced-packet sockets.
+#[derive(Clone, Copy)]
+pub struct UnixSeqpacketDevice;
+
+impl IoDevice for UnixSeqpacketDevice {}
+unsafe impl SubDevice<UnixSocketDevice> for UnixSeqpacketDevice {}
+unsafe impl SubDevice<super::SocketDevice> for UnixSeqpacketDevice {}
+
+/// The protocol for Unix domain sockets of type
+/// [`SOCK_STREAM`](super::sock_type::SOCK_STREAM).
+pub const UNIX_STREAM: super::SocketProtocolFixed<UnixStreamDevice> =
+    unsafe { super::socket_protocol(0) };
+
+/// The protocol for Unix domain sockets of type
+/// [`SOCK_DGRAM`](super::sock_type::SOCK_DGRAM).
+pub const UNIX_DGRAM: super::SocketProtocolFixed<UnixDatagramDevice> =
+    unsafe { super::socket_protocol(0) };
+
+/// The protocol for Unix domain sockets of type
+/// [`SOCK_SEQPACKET`](super::sock_type::SOCK_SEQPACKET).
+pub const UNIX_SEQPACKET: super::SocketProtocolFixed<UnixSeqpacketDevice> =
+    unsafe { super::socket_protocol(0) };
+
+/// Returns the amount of queued unread data in the receive buffer, or for a
+/// datagram socket the size of the next pending datagram.
+///
+/// The socket must not be in listen state, otherwise an error (`EINVAL`) is
+/// returned.
+pub const SIOCINQ: IoctlReqRead<UnixSocketDevice, int> = unsafe { ioctl_read(0x541B) };
+
+/// Returns the amount of data that has been sent but not yet read by the
+/// peer.
+///
+/// The socket must not be in listen state, otherwise an error (`EINVAL`) is
+/// returned.
+pub const SIOCOUTQ: IoctlReqRead<UnixSocketDevice, int> = unsafe { ioctl_read(0x5411) };
+
+/// Returns the credentials of the peer process, as they were when it called
+/// `connect` or created the socket pair.
+pub const SO_PEERCRED: DirectSockOptReadOnly<super::msg::Credentials> =
+    unsafe { sockopt_readonly(linux_unsafe::SOL_SOCKET, linux_unsafe::SO_PEERCRED) };
+
+/// Returns a new pidfd referring to the peer process, as identified when it
+/// called `connect` or created the socket pair.
+///
+/// Unlike the process ID in [`SO_PEERCRED`], the pidfd cannot be confused
+/// with an unrelated process that later reuses the same process ID. This
+/// requires Linux 6.5 or later.
+pub const SO_PEERPIDFD: PeerPidFdSockOpt = PeerPidFdSockOpt;
+
+/// Implementation of [`GetSockOpt`] for [`SO_PEERPIDFD`], which takes
+/// ownership of the returned file descriptor.
+#[derive(Clone, Copy, Debug)]
+pub struct PeerPidFdSockOpt;
+
+unsafe impl<'a> GetSockOpt<'a> for PeerPidFdSockOpt {
+    type OptVal = int;
+    type Result = File<PidFdDevice>;
+
+    fn prepare_getsockopt_args(&self) -> (int, int) {
+        (linux_unsafe::SOL_SOCKET, linux_unsafe::SO_PEERPIDFD)
+    }
+
+    fn prepare_getsockopt_result(&self, _: int, optval: int) -> Self::Result {
+        // Safe because the kernel just created this file descriptor for us.
+        unsafe { File::from_raw_fd(optval) }
+    }
+}
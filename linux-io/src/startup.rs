@@ -0,0 +1,231 @@
+use linux_unsafe::{auxv_t, ulong};
+
+use crate::result::Result;
+use crate::File;
+
+pub use linux_unsafe::startup::{CStrArray, StartupInfo};
+
+/// The auxiliary vector that the kernel passed to the current program,
+/// describing its execution environment.
+///
+/// A `no_std` program that has its initial stack pointer can obtain this
+/// using [`AuxVec::from_startup`]. Other programs can read a copy of it from
+/// procfs using [`AuxVec::read_proc_self`].
+#[derive(Clone, Copy, Debug)]
+pub struct AuxVec<'a> {
+    entries: &'a [auxv_t],
+}
+
+impl<'a> AuxVec<'a> {
+    /// Wraps the auxiliary vector found on the program's initial stack.
+    #[inline]
+    pub fn from_startup(info: &StartupInfo) -> AuxVec<'static> {
+        AuxVec {
+            entries: info.auxv(),
+        }
+    }
+
+    /// Wraps a slice of raw auxiliary vector entries, which may or may not
+    /// include the terminating `AT_NULL` entry.
+    #[inline]
+    pub const fn from_entries(entries: &'a [auxv_t]) -> Self {
+        Self { entries }
+    }
+
+    /// Reads the auxiliary vector of the current process from
+    /// `/proc/self/auxv` into the given buffer.
+    ///
+    /// Returns [`EOVERFLOW`](crate::result::EOVERFLOW) if the buffer is too
+    /// small. A buffer of 64 entries is more than enough for current kernels.
+    pub fn read_proc_self(buf: &'a mut [auxv_t]) -> Result<Self> {
+        let f = File::open(c"/proc/self/auxv", crate::OPEN_READ_ONLY.close_on_exec())?;
+        Self::read(&f, buf)
+    }
+
+    /// Reads an auxiliary vector in the format used by `/proc/<pid>/auxv`
+    /// from the given file into the given buffer.
+    ///
+    /// Returns [`EOVERFLOW`](crate::result::EOVERFLOW) if the buffer is too
+    /// small.
+    pub fn read<Device>(f: &File<Device>, buf: &'a mut [auxv_t]) -> Result<Self> {
+        const ENTRY_SIZE: usize = core::mem::size_of::<auxv_t>();
+        let buf_size = core::mem::size_of_val(buf);
+        let mut filled = 0;
+        let len = loop {
+            // The kernel always terminates the vector with an AT_NULL entry,
+            // so we can stop as soon as we've seen it.
+            let entries = &buf[..filled / ENTRY_SIZE];
+            if let Some(end) = entries
+                .iter()
+                .position(|e| e.a_type == linux_unsafe::AT_NULL)
+            {
+                break end;
+            }
+            if filled == buf_size {
+                // The buffer is exactly full, so we'll only know whether
+                // it was big enough by checking whether there's more to read.
+                let mut scratch = [0_u8; ENTRY_SIZE];
+                let n = unsafe {
+                    f.read_raw(scratch.as_mut_ptr() as *mut linux_unsafe::void, ENTRY_SIZE)
+                }?;
+                if n != 0 {
+                    return Err(crate::result::EOVERFLOW);
+                }
+                break filled / ENTRY_SIZE;
+            }
+            let n = unsafe {
+                f.read_raw(
+                    (buf.as_mut_ptr() as *mut u8).add(filled) as *mut linux_unsafe::void,
+                    buf_size - filled,
+                )
+            }?;
+            if n == 0 {
+                break filled / ENTRY_SIZE;
+            }
+            filled += n;
+        };
+        Ok(Self {
+            entries: &buf[..len],
+        })
+    }
+
+    /// Returns the raw entries of the auxiliary vector.
+    #[inline(always)]
+    pub const fn entries(&self) -> &'a [auxv_t] {
+        self.entries
+    }
+
+    /// Returns an iterator over the entries, interpreted as [`AuxEntry`]
+    /// values.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = AuxEntry> + 'a {
+        self.entries
+            .iter()
+            .take_while(|e| e.a_type != linux_unsafe::AT_NULL)
+            .map(|e| AuxEntry::from(*e))
+    }
+
+    /// Returns the value of the first entry of the given type, or `None` if
+    /// there is no such entry.
+    pub fn get(&self, typ: ulong) -> Option<ulong> {
+        self.entries
+            .iter()
+            .take_while(|e| e.a_type != linux_unsafe::AT_NULL)
+            .find(|e| e.a_type == typ)
+            .map(|e| e.a_val)
+    }
+
+    /// Returns the system page size.
+    #[inline]
+    pub fn page_size(&self) -> Option<usize> {
+        self.get(linux_unsafe::AT_PAGESZ).map(|v| v as usize)
+    }
+
+    /// Returns the architecture-specific hardware capability bits.
+    #[inline]
+    pub fn hwcap(&self) -> Option<ulong> {
+        self.get(linux_unsafe::AT_HWCAP)
+    }
+
+    /// Returns the architecture-specific extended hardware capability bits.
+    #[inline]
+    pub fn hwcap2(&self) -> Option<ulong> {
+        self.get(linux_unsafe::AT_HWCAP2)
+    }
+
+    /// Returns the address of the vDSO, for use with
+    /// [`linux_unsafe::vdso::init`].
+    #[inline]
+    pub fn vdso_ehdr(&self) -> Option<*const linux_unsafe::void> {
+        self.get(linux_unsafe::AT_SYSINFO_EHDR)
+            .map(|v| v as *const linux_unsafe::void)
+    }
+
+    /// Returns true if the program is running in "secure mode", such as
+    /// when it's setuid, and so should not trust its environment.
+    #[inline]
+    pub fn secure(&self) -> bool {
+        self.get(linux_unsafe::AT_SECURE).is_some_and(|v| v != 0)
+    }
+}
+
+/// An entry from the auxiliary vector, interpreted according to its type.
+///
+/// Pointers in these values refer to memory in the process whose auxiliary
+/// vector was read, which is valid to dereference only if that's the
+/// current process.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AuxEntry {
+    /// `AT_PHDR`: the address of the program headers of the executable.
+    ProgramHeaders(*const linux_unsafe::void),
+    /// `AT_PHENT`: the size of each program header entry.
+    ProgramHeaderSize(usize),
+    /// `AT_PHNUM`: the number of program headers.
+    ProgramHeaderCount(usize),
+    /// `AT_PAGESZ`: the system page size.
+    PageSize(usize),
+    /// `AT_BASE`: the base address of the program interpreter.
+    InterpreterBase(*const linux_unsafe::void),
+    /// `AT_ENTRY`: the entry point address of the executable.
+    Entry(*const linux_unsafe::void),
+    /// `AT_UID`: the real user id of the process.
+    Uid(linux_unsafe::uid_t),
+    /// `AT_EUID`: the effective user id of the process.
+    Euid(linux_unsafe::uid_t),
+    /// `AT_GID`: the real group id of the process.
+    Gid(linux_unsafe::gid_t),
+    /// `AT_EGID`: the effective group id of the process.
+    Egid(linux_unsafe::gid_t),
+    /// `AT_PLATFORM`: the address of a string identifying the hardware
+    /// platform.
+    Platform(*const linux_unsafe::char),
+    /// `AT_HWCAP`: architecture-specific hardware capability bits.
+    HwCap(ulong),
+    /// `AT_HWCAP2`: architecture-specific extended hardware capability bits.
+    HwCap2(ulong),
+    /// `AT_CLKTCK`: the frequency of the clock used by `times`.
+    ClockTicks(ulong),
+    /// `AT_SECURE`: whether the program is running in "secure mode".
+    Secure(bool),
+    /// `AT_RANDOM`: the address of 16 random bytes.
+    Random(*const [u8; 16]),
+    /// `AT_EXECFN`: the address of the path used to execute the program.
+    ExecFn(*const linux_unsafe::char),
+    /// `AT_SYSINFO_EHDR`: the address of the vDSO.
+    VdsoEhdr(*const linux_unsafe::void),
+    /// `AT_MINSIGSTKSZ`: the minimum stack size needed for signal delivery.
+    MinSigStackSize(usize),
+    /// Any other entry type, given verbatim.
+    Other(auxv_t),
+}
+
+impl From<auxv_t> for AuxEntry {
+    fn from(raw: auxv_t) -> Self {
+        use linux_unsafe::*;
+
+        let v = raw.a_val;
+        match raw.a_type {
+            AT_PHDR => Self::ProgramHeaders(v as *const void),
+            AT_PHENT => Self::ProgramHeaderSize(v as usize),
+            AT_PHNUM => Self::ProgramHeaderCount(v as usize),
+            AT_PAGESZ => Self::PageSize(v as usize),
+            AT_BASE => Self::InterpreterBase(v as *const void),
+            AT_ENTRY => Self::Entry(v as *const void),
+            AT_UID => Self::Uid(v as uid_t),
+            AT_EUID => Self::Euid(v as uid_t),
+            AT_GID => Self::Gid(v as gid_t),
+            AT_EGID => Self::Egid(v as gid_t),
+            AT_PLATFORM => Self::Platform(v as *const char),
+            AT_HWCAP => Self::HwCap(v),
+            AT_HWCAP2 => Self::HwCap2(v),
+            AT_CLKTCK => Self::ClockTicks(v),
+            AT_SECURE => Self::Secure(v != 0),
+            AT_RANDOM => Self::Random(v as *const [u8; 16]),
+            AT_EXECFN => Self::ExecFn(v as *const char),
+            AT_SYSINFO_EHDR => Self::VdsoEhdr(v as *const void),
+            AT_MINSIGSTKSZ => Self::MinSigStackSize(v as usize),
+            _ => Self::Other(raw),
+        }
+    }
+}
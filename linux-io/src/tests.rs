@@ -504,3 +504,1789 @@ fn futex_mutex() {
     let g = m3.lock();
     std::assert_eq!(*g, 2, "wrong final value");
 }
+
+#[test]
+fn fork_and_wait() {
+    use crate::process::{Child, ChildStatus};
+
+    let child = unsafe { Child::fork() }
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to fork");
+    let child = match child {
+        Some(child) => child,
+        None => {
+            // We're in the child process, so we must exit without returning
+            // into the test harness.
+            unsafe { linux_unsafe::exit_group(3) };
+        }
+    };
+    assert!(child.pid() > 0, "child pid is not positive");
+
+    let mut reqs = [child.poll_request()];
+    crate::poll::poll(&mut reqs[..], 5000)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to poll");
+    assert!(
+        reqs[0].response().readable(),
+        "pidfd did not become readable"
+    );
+
+    let status = child
+        .wait()
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to wait");
+    assert_eq!(status, ChildStatus::Exited(3));
+}
+
+#[test]
+fn fork_and_kill() {
+    use crate::process::{Child, ChildStatus};
+
+    let child = unsafe { Child::fork() }
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to fork");
+    let child = match child {
+        Some(child) => child,
+        // Not all architectures have pause, so wait using ppoll with no
+        // files and no timeout instead.
+        None => loop {
+            unsafe {
+                linux_unsafe::ppoll(
+                    core::ptr::null_mut(),
+                    0,
+                    core::ptr::null(),
+                    core::ptr::null(),
+                    0,
+                )
+            }
+            .ok();
+        },
+    };
+
+    child
+        .send_signal(linux_unsafe::SIGKILL)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to send signal");
+    let status = child
+        .wait()
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to wait");
+    assert_eq!(status, ChildStatus::Killed(linux_unsafe::SIGKILL));
+    let status = child.try_wait();
+    assert!(status.is_err(), "child was reaped twice");
+}
+
+#[test]
+fn signalfd() {
+    use crate::signal::{block_signals, pending_signals, set_signal_mask, SignalFdDevice};
+    use linux_unsafe::SIGUSR1;
+
+    let mut mask = linux_unsafe::sigset_t::new_empty();
+    mask.sigaddset(SIGUSR1).unwrap();
+    // Signal masks are per-thread, and each test runs in its own thread.
+    let old_mask = block_signals(&mask)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to block SIGUSR1");
+
+    let f = crate::File::<SignalFdDevice>::signalfd(&mask)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to create signalfd");
+
+    unsafe {
+        let pid = linux_unsafe::getpid();
+        let tid = linux_unsafe::gettid();
+        linux_unsafe::tgkill(pid, tid, SIGUSR1).expect("failed to send SIGUSR1");
+    }
+    let pending = pending_signals()
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to get pending signals");
+    assert!(
+        pending.sigismember(SIGUSR1).unwrap(),
+        "SIGUSR1 is not pending"
+    );
+
+    let info = f
+        .read_signal()
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to read from signalfd");
+    assert_eq!(info.ssi_signo, SIGUSR1 as u32);
+
+    set_signal_mask(&old_mask)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to restore signal mask");
+}
+
+#[test]
+fn monotonic_sleep() {
+    use crate::time::{sleep, ClockId, Instant};
+    use core::time::Duration;
+
+    let res = ClockId::MONOTONIC
+        .resolution()
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to get clock resolution");
+    assert!(res > Duration::ZERO, "clock resolution is zero");
+
+    let start = Instant::now();
+    sleep(Duration::from_millis(10))
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to sleep");
+    let elapsed = start.elapsed();
+    assert!(
+        elapsed >= Duration::from_millis(10),
+        "slept for only {elapsed:?}"
+    );
+    assert_eq!((start + elapsed) - start, elapsed);
+    assert_eq!(start - (start + elapsed), Duration::ZERO);
+}
+
+#[test]
+fn timespec_duration_conversion() {
+    use crate::time::{duration_from_timespec, timespec_from_duration};
+    use core::time::Duration;
+
+    let d = Duration::new(5, 123_456_789);
+    let ts = timespec_from_duration(d).unwrap();
+    assert_eq!(ts.tv_sec, 5);
+    assert_eq!(ts.tv_nsec, 123_456_789);
+    assert_eq!(duration_from_timespec(&ts).unwrap(), d);
+
+    let bad = linux_unsafe::timespec {
+        tv_sec: -1,
+        tv_nsec: 0,
+    };
+    assert_eq!(duration_from_timespec(&bad), Err(crate::result::EINVAL));
+}
+
+#[test]
+fn read_proc_self_auxv() {
+    use crate::startup::{AuxEntry, AuxVec};
+
+    let mut buf = [linux_unsafe::auxv_t {
+        a_type: 0,
+        a_val: 0,
+    }; 64];
+    let auxv = AuxVec::read_proc_self(&mut buf)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to read auxv");
+    let page_size = auxv.page_size().expect("no AT_PAGESZ");
+    assert!(page_size.is_power_of_two(), "invalid page size {page_size}");
+    assert!(auxv.iter().any(|e| e == AuxEntry::PageSize(page_size)));
+    assert!(!auxv.secure(), "test program is in secure mode");
+
+    let mut tiny = [linux_unsafe::auxv_t {
+        a_type: 0,
+        a_val: 0,
+    }; 1];
+    let result = AuxVec::read_proc_self(&mut tiny);
+    assert_eq!(result.err(), Some(crate::result::EOVERFLOW));
+
+    // A buffer exactly big enough for the entries and the AT_NULL
+    // terminator must not be reported as too small.
+    let len = auxv.entries().len();
+    let mut exact = [linux_unsafe::auxv_t {
+        a_type: 0,
+        a_val: 0,
+    }; 64];
+    let exact = &mut exact[..len + 1];
+    let result = AuxVec::read_proc_self(exact)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to read auxv into exact-size buffer");
+    assert_eq!(result.entries().len(), len);
+}
+
+#[test]
+fn anonymous_mapping() {
+    use crate::mem::{MapOptions, Mapping};
+
+    let mut m = Mapping::anonymous(8192, MapOptions::read_write())
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to create mapping");
+    assert_eq!(m.len(), 8192);
+    assert!(m.as_slice().iter().all(|b| *b == 0));
+    m.as_mut_slice()[4096] = 0xfe;
+    assert_eq!(m.as_slice()[4096], 0xfe);
+
+    m.protect(MapOptions::read_only())
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to change protection");
+    assert_eq!(m.as_slice()[4096], 0xfe);
+}
+
+#[test]
+fn memfd_mapping() {
+    use crate::mem::MapOptions;
+
+    let f = File::memfd_create(c"linux-io-test", linux_unsafe::MFD_CLOEXEC)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to create memfd");
+    f.write(b"hello")
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to write");
+
+    let mut m = unsafe { f.mmap(0, 5, MapOptions::read_write().shared()) }
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to map memfd");
+    assert_eq!(m.as_slice(), b"hello");
+    m.as_mut_slice()[0] = b'j';
+    m.sync()
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to sync");
+
+    let mut buf = [0_u8; 5];
+    f.seek(crate::seek::SeekFrom::Start(0))
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to seek");
+    f.read(&mut buf)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to read");
+    assert_eq!(&buf, b"jello");
+}
+
+#[test]
+fn tree_manipulation_relative() {
+    let dir = tempdir().unwrap();
+    let dir_raw = CString::new(dir.path().as_os_str().as_bytes()).unwrap();
+    let dirf = File::open(&dir_raw, OpenOptions::read_only().directory())
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to open temporary directory");
+
+    dirf.mkdir_relative(c"sub", 0o755)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to create directory");
+    assert_eq!(
+        dirf.mkdir_relative(c"sub", 0o755),
+        Err(crate::result::EEXIST)
+    );
+    dirf.mknod_relative(c"sub/fifo", linux_unsafe::S_IFIFO | 0o600, 0)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to create fifo");
+    dirf.symlink_relative(c"sub/fifo", c"link")
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to create symlink");
+    let mut buf = [0_u8; 32];
+    assert_eq!(
+        dirf.readlink_relative(c"link", &mut buf).unwrap(),
+        b"sub/fifo"
+    );
+
+    let subf = dirf
+        .open_relative(c"sub", OpenOptions::read_only().directory())
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to open subdirectory");
+    subf.mkdir_relative(c"other", 0o700)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to create nested directory");
+    assert_eq!(
+        dirf.rename_relative(c"link", &subf, c"other", linux_unsafe::RENAME_NOREPLACE),
+        Err(crate::result::EEXIST)
+    );
+    dirf.rename_relative(c"link", &subf, c"link2", 0)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to rename symlink");
+    assert!(!dirf.exists_relative(c"link").unwrap());
+    // The link target is now dangling, so we check using readlink because
+    // exists_relative would follow the link.
+    assert_eq!(
+        subf.readlink_relative(c"link2", &mut buf).unwrap(),
+        b"sub/fifo"
+    );
+
+    dirf.chmod_relative(c"sub/other", 0o750, 0)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to chmod");
+    let epoch = linux_unsafe::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    dirf.set_times_relative(c"sub/other", Some(&[epoch, epoch]), 0)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to set times");
+    let meta = std::fs::metadata(dir.path().join("sub/other")).unwrap();
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+    assert_eq!(meta.permissions().mode() & 0o777, 0o750);
+    assert_eq!(meta.mtime(), 0);
+
+    assert_eq!(dirf.rmdir_relative(c"sub"), Err(crate::result::ENOTEMPTY));
+    subf.unlink_relative(c"link2")
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to unlink symlink");
+    subf.unlink_relative(c"fifo")
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to unlink fifo");
+    subf.rmdir_relative(c"other")
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to remove nested directory");
+    dirf.rmdir_relative(c"sub")
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to remove directory");
+
+    dir.close().expect("failed to clean temporary directory");
+}
+
+#[test]
+fn metadata() {
+    let dir = tempdir().unwrap();
+    let dir_raw = CString::new(dir.path().as_os_str().as_bytes()).unwrap();
+    let dirf = File::open(&dir_raw, OpenOptions::read_only().directory())
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to open temporary directory");
+
+    let f = dirf
+        .open_relative_with_mode(c"file", OpenOptions::write_only().create(), 0o640)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to create file");
+    f.write(b"hello")
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to write");
+    dirf.symlink_relative(c"file", c"link")
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to create symlink");
+
+    let meta = f
+        .metadata()
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to get metadata");
+    assert!(meta.has(linux_unsafe::STATX_BASIC_STATS));
+    assert!(meta.is_file());
+    assert_eq!(meta.permissions(), Some(0o640));
+    assert_eq!(meta.size(), Some(5));
+    assert_eq!(meta.nlink(), Some(1));
+    let std_meta = std::fs::metadata(dir.path().join("file")).unwrap();
+    use std::os::unix::fs::MetadataExt;
+    assert_eq!(meta.ino(), Some(std_meta.ino()));
+    assert_eq!(meta.uid(), Some(std_meta.uid()));
+    assert_eq!(meta.modified().map(|ts| ts.tv_sec), Some(std_meta.mtime()));
+
+    let via_link = dirf
+        .metadata_relative(c"link", 0)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to get metadata through link");
+    assert_eq!(via_link.ino(), meta.ino());
+    let link = dirf
+        .metadata_relative(c"link", linux_unsafe::AT_SYMLINK_NOFOLLOW)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to get metadata of link");
+    assert!(link.is_symlink());
+    assert_ne!(link.ino(), meta.ino());
+
+    let only_size = f
+        .metadata_relative_raw(c"", linux_unsafe::AT_EMPTY_PATH, linux_unsafe::STATX_SIZE)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to get size");
+    assert_eq!(only_size.size(), Some(5));
+
+    assert!(dirf.metadata().unwrap().is_dir());
+}
+
+#[test]
+fn positional_and_vectored_io() {
+    use crate::fd::{IoSlice, IoSliceMut, RwFlags};
+
+    let f = File::memfd_create(c"linux-io-test", linux_unsafe::MFD_CLOEXEC)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to create memfd");
+
+    let n = f
+        .write_vectored(&[IoSlice::new(b"hello "), IoSlice::new(b"world")])
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to writev");
+    assert_eq!(n, 11);
+    assert_eq!(f.seek(crate::seek::SeekFrom::Current(0)), Ok(11));
+
+    // Positional writes and reads don't affect the current position.
+    assert_eq!(f.write_at(b"W", 6), Ok(1));
+    let mut buf = [0_u8; 5];
+    assert_eq!(f.read_at(&mut buf, 6), Ok(5));
+    assert_eq!(&buf, b"World");
+    assert_eq!(f.seek(crate::seek::SeekFrom::Current(0)), Ok(11));
+
+    let mut a = [0_u8; 3];
+    let mut b = [0_u8; 4];
+    let n = {
+        let mut bufs = [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)];
+        f.read_vectored_at(&mut bufs, Some(2), RwFlags::NONE)
+            .map_err(|e| e.into_std_io_error())
+            .expect("failed to preadv2")
+    };
+    assert_eq!(n, 7);
+    assert_eq!(&a, b"llo");
+    assert_eq!(&b, b" Wor");
+
+    let n = f
+        .write_vectored_at(&[IoSlice::new(b"!")], None, RwFlags::DSYNC)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to pwritev2");
+    assert_eq!(n, 1);
+    assert_eq!(f.seek(crate::seek::SeekFrom::Current(0)), Ok(12));
+
+    f.seek(crate::seek::SeekFrom::Start(0)).unwrap();
+    let mut all = [0_u8; 16];
+    let n = {
+        let mut bufs = [IoSliceMut::new(&mut all)];
+        f.read_vectored(&mut bufs)
+            .map_err(|e| e.into_std_io_error())
+            .expect("failed to readv")
+    };
+    assert_eq!(&all[..n], b"hello World!");
+
+    assert_eq!(f.read_at(&mut buf, u64::MAX), Err(crate::result::EINVAL));
+}
+
+#[test]
+fn in_kernel_data_movement() {
+    use crate::fd::{transfer_all, IoSlice, SpliceFlags};
+
+    let new_memfd = || {
+        File::memfd_create(c"linux-io-test", linux_unsafe::MFD_CLOEXEC)
+            .map_err(|e| e.into_std_io_error())
+            .expect("failed to create memfd")
+    };
+    let new_pipe = || {
+        crate::pipe(linux_unsafe::O_CLOEXEC)
+            .map_err(|e| e.into_std_io_error())
+            .expect("failed to create pipe")
+    };
+    let read_all = |f: &File| {
+        let mut buf = [0_u8; 64];
+        let n = f.read_at(&mut buf, 0).unwrap();
+        Vec::from(&buf[..n])
+    };
+
+    let src = new_memfd();
+    src.write(b"0123456789").unwrap();
+
+    // copy_file_range with explicit offsets leaves positions unchanged.
+    let dst = new_memfd();
+    let mut off_in = 2_u64;
+    let mut off_out = 0_u64;
+    let n = src
+        .copy_file_range_to(Some(&mut off_in), &dst, Some(&mut off_out), 4)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to copy_file_range");
+    assert_eq!((n, off_in, off_out), (4, 6, 4));
+    assert_eq!(read_all(&dst), b"2345");
+
+    // sendfile to a pipe, then splice from the pipe into a file.
+    let (pipe_r, pipe_w) = new_pipe();
+    let mut off = 5_u64;
+    let n = src
+        .sendfile_to(&pipe_w, Some(&mut off), 5)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to sendfile");
+    assert_eq!((n, off), (5, 10));
+
+    // tee duplicates the pipe contents without consuming them.
+    let (tee_r, tee_w) = new_pipe();
+    let n = pipe_r
+        .tee_to(&tee_w, 5, SpliceFlags::NONE)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to tee");
+    assert_eq!(n, 5);
+    let mut buf = [0_u8; 5];
+    assert_eq!(tee_r.read(&mut buf), Ok(5));
+    assert_eq!(&buf, b"56789");
+
+    let dst = new_memfd();
+    let n = pipe_r
+        .splice_to(&dst, None, 5, SpliceFlags::MOVE)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to splice");
+    assert_eq!(n, 5);
+    assert_eq!(read_all(&dst), b"56789");
+
+    // vmsplice into a pipe, then splice it all out, stopping at EOF.
+    let data = *b"spliced";
+    let n = unsafe { pipe_w.vmsplice(&[IoSlice::new(&data)], SpliceFlags::NONE) }
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to vmsplice");
+    assert_eq!(n, data.len());
+    drop(pipe_w);
+    let mut off_out = 0_u64;
+    let n = pipe_r
+        .splice_all_to(&dst, Some(&mut off_out), 100, SpliceFlags::NONE)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to splice all");
+    assert_eq!((n, off_out), (data.len(), data.len() as u64));
+    assert_eq!(read_all(&dst), b"spliced");
+
+    // transfer_all stops at EAGAIN only after some progress.
+    let mut calls = 0;
+    let n = transfer_all(10, |remain| {
+        calls += 1;
+        match calls {
+            1 => Err(crate::result::EINTR),
+            2 => Ok(remain.min(4)),
+            _ => Err(crate::result::EAGAIN),
+        }
+    });
+    assert_eq!(n, Ok(4));
+    assert_eq!(
+        transfer_all(10, |_| Err(crate::result::EAGAIN)),
+        Err(crate::result::EAGAIN)
+    );
+}
+
+#[test]
+fn pipes() {
+    use crate::fd::SpliceFlags;
+    use crate::pipe::FIONREAD;
+
+    let (r, w) = crate::pipe(linux_unsafe::O_CLOEXEC | linux_unsafe::O_NONBLOCK)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to create pipe");
+
+    let size = w.set_pipe_size(65536).expect("failed to set pipe size");
+    assert!(size >= 65536);
+    assert_eq!(r.pipe_size(), Ok(size));
+
+    assert_eq!(r.ioctl(FIONREAD, ()), Ok(0));
+    w.write(b"hello").unwrap();
+    assert_eq!(r.ioctl(FIONREAD, ()), Ok(5));
+    assert_eq!(w.ioctl(FIONREAD, ()), Ok(5));
+
+    // Splicing from a file into a pipe.
+    let src = File::memfd_create(c"linux-io-test", linux_unsafe::MFD_CLOEXEC).unwrap();
+    src.write(b" world").unwrap();
+    let mut off = 0_u64;
+    assert_eq!(
+        src.splice_all_to_pipe(Some(&mut off), &w, 100, SpliceFlags::NONE),
+        Ok(6)
+    );
+    let mut buf = [0_u8; 16];
+    let n = r.read(&mut buf).unwrap();
+    assert_eq!(&buf[..n], b"hello world");
+    assert_eq!(r.read(&mut buf), Err(crate::result::EAGAIN));
+
+    // In packet mode each write is read back separately.
+    let (r, w) = crate::pipe(linux_unsafe::O_CLOEXEC | linux_unsafe::O_DIRECT)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to create packet-mode pipe");
+    w.write(b"one").unwrap();
+    w.write(b"two").unwrap();
+    let n = r.read(&mut buf).unwrap();
+    assert_eq!(&buf[..n], b"one");
+    let n = r.read(&mut buf).unwrap();
+    assert_eq!(&buf[..n], b"two");
+
+    // Non-pipes reject the pipe size fcntl.
+    assert_eq!(src.pipe_size(), Err(crate::result::EBADF));
+}
+
+#[test]
+fn fcntl_commands() {
+    use crate::fd::fcntl::*;
+
+    let f = File::memfd_create(
+        c"linux-io-test",
+        linux_unsafe::MFD_CLOEXEC | linux_unsafe::MFD_ALLOW_SEALING,
+    )
+    .map_err(|e| e.into_std_io_error())
+    .expect("failed to create memfd");
+
+    assert_eq!(f.fcntl(F_GETFD, ()), Ok(FdFlags::CLOEXEC));
+    f.fcntl(F_SETFD, FdFlags::NONE).unwrap();
+    assert_eq!(f.fcntl(F_GETFD, ()), Ok(FdFlags::NONE));
+    f.fcntl(F_SETFD, FdFlags::CLOEXEC).unwrap();
+
+    // Sealing
+    f.write(b"hello").unwrap();
+    assert_eq!(f.fcntl(F_GET_SEALS, ()), Ok(SealFlags::NONE));
+    f.fcntl(F_ADD_SEALS, SealFlags::WRITE | SealFlags::SHRINK)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to add seals");
+    let seals = f.fcntl(F_GET_SEALS, ()).unwrap();
+    assert!(seals.contains(SealFlags::WRITE | SealFlags::SHRINK));
+    assert!(!seals.contains(SealFlags::GROW));
+    assert_eq!(f.write(b"world"), Err(crate::result::EPERM));
+    f.fcntl(F_ADD_SEALS, SealFlags::SEAL).unwrap();
+    assert_eq!(
+        f.fcntl(F_ADD_SEALS, SealFlags::GROW),
+        Err(crate::result::EPERM)
+    );
+
+    // Signal ownership
+    let pid = unsafe { linux_unsafe::getpid() };
+    let owner = linux_unsafe::f_owner_ex {
+        type_: linux_unsafe::F_OWNER_PID,
+        pid,
+    };
+    f.fcntl(F_SETOWN_EX, &owner)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to set owner");
+    let mut got = linux_unsafe::f_owner_ex { type_: -1, pid: 0 };
+    f.fcntl(F_GETOWN_EX, &mut got).unwrap();
+    assert_eq!(got.type_, linux_unsafe::F_OWNER_PID);
+    assert_eq!(got.pid, pid);
+    assert_eq!(f.fcntl(F_GETOWN, ()), Ok(pid));
+    f.fcntl(F_SETSIG, linux_unsafe::SIGUSR1).unwrap();
+    assert_eq!(f.fcntl(F_GETSIG, ()), Ok(linux_unsafe::SIGUSR1));
+    f.fcntl(F_SETSIG, 0).unwrap();
+    assert_eq!(f.fcntl(F_GETSIG, ()), Ok(0));
+
+    // Leases and hints
+    assert_eq!(
+        f.fcntl(F_GETLEASE, ()),
+        Ok(linux_unsafe::F_UNLCK as linux_unsafe::int)
+    );
+    let hint = linux_unsafe::RWH_WRITE_LIFE_SHORT;
+    f.fcntl(F_SET_RW_HINT, &hint)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to set write hint");
+    let mut hint = 0_u64;
+    f.fcntl(F_GET_RW_HINT, &mut hint).unwrap();
+    assert_eq!(hint, linux_unsafe::RWH_WRITE_LIFE_SHORT);
+
+    // Directory notifications
+    let dir = tempdir().unwrap();
+    let dir_raw = CString::new(dir.path().as_os_str().as_bytes()).unwrap();
+    let dirf = File::open(&dir_raw, OpenOptions::read_only().directory()).unwrap();
+    dirf.fcntl(F_NOTIFY, linux_unsafe::DN_CREATE | linux_unsafe::DN_DELETE)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to request notifications");
+    dirf.fcntl(F_NOTIFY, 0).unwrap();
+}
+
+#[test]
+fn advisory_locks() {
+    use crate::fd::{LockKind, Wait};
+
+    let dir = tempdir().unwrap();
+    let mut filename: PathBuf = dir.path().into();
+    filename.push("locks");
+    let filename_raw = CString::new(filename.as_os_str().as_bytes()).unwrap();
+    let mut a = File::create_raw(&filename_raw, 0o600)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to create file");
+    let mut b = File::open(&filename_raw, OpenOptions::read_write())
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to open file");
+
+    // Open file description locks conflict between separate opens.
+    let lock = a
+        .lock_range(0..10, LockKind::Exclusive, Wait::No)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to lock range");
+    assert_eq!(lock.kind(), LockKind::Exclusive);
+    assert_eq!(
+        b.lock_range(5..15, LockKind::Shared, Wait::No).err(),
+        Some(crate::result::EAGAIN)
+    );
+    let conflict = b
+        .query_lock(5..15, LockKind::Shared)
+        .unwrap()
+        .expect("no conflicting lock");
+    assert_eq!(conflict.kind, LockKind::Exclusive);
+    assert_eq!(conflict.start, 0);
+    assert_eq!(conflict.len, Some(10));
+    assert_eq!(conflict.pid, None);
+    assert_eq!(b.query_lock(10.., LockKind::Exclusive), Ok(None));
+    assert!(b.lock_range(10.., LockKind::Exclusive, Wait::No).is_ok());
+    drop(lock);
+    let lock = b
+        .lock_range(..10, LockKind::Shared, Wait::No)
+        .expect("failed to lock released range");
+    lock.unlock().expect("failed to unlock");
+    assert_eq!(
+        a.lock_range(5..5, LockKind::Shared, Wait::No).err(),
+        Some(crate::result::EINVAL)
+    );
+
+    // POSIX record locks report the owning process.
+    let lock = b
+        .lock_range_process(20..=29, LockKind::Shared, Wait::Yes)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to lock range");
+    let conflict = a
+        .query_lock(25..26, LockKind::Exclusive)
+        .unwrap()
+        .expect("no conflicting lock");
+    assert_eq!(conflict.kind, LockKind::Shared);
+    assert_eq!(conflict.start, 20);
+    assert_eq!(conflict.len, Some(10));
+    assert_eq!(conflict.pid, Some(unsafe { linux_unsafe::getpid() }));
+    drop(lock);
+    assert_eq!(a.query_lock(25..26, LockKind::Exclusive), Ok(None));
+
+    // Whole-file locks.
+    let lock = a
+        .flock(LockKind::Exclusive, Wait::No)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to lock file");
+    assert_eq!(
+        b.flock(LockKind::Shared, Wait::No).err(),
+        Some(crate::result::EAGAIN)
+    );
+    drop(lock);
+    let lock_b = b.flock(LockKind::Shared, Wait::No).unwrap();
+    let lock_a = a.flock(LockKind::Shared, Wait::No).unwrap();
+    lock_a.unlock().unwrap();
+    lock_b.unlock().unwrap();
+}
+
+#[test]
+fn epoll() {
+    use crate::epoll::{Epoll, EpollEvent, EpollEvents};
+    use core::time::Duration;
+
+    let ep = Epoll::epoll_create()
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to create epoll instance");
+    let (r, w) = crate::pipe(linux_unsafe::O_CLOEXEC).unwrap();
+    let (r2, w2) = crate::pipe(linux_unsafe::O_CLOEXEC).unwrap();
+    ep.add(&r, EpollEvents::IN | EpollEvents::ONESHOT, 7)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to add pipe");
+    ep.add(&r2, EpollEvents::IN | EpollEvents::EDGE_TRIGGERED, 8)
+        .unwrap();
+    assert_eq!(ep.add(&r, EpollEvents::IN, 9), Err(crate::result::EEXIST));
+
+    let mut buf = [EpollEvent::default(); 4];
+    assert_eq!(ep.wait(&mut buf, Some(Duration::ZERO)).unwrap().len(), 0);
+
+    // One-shot registrations report only once until re-armed.
+    w.write(b"a").unwrap();
+    let events = ep.wait(&mut buf, None).unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].token(), 7);
+    assert!(events[0].events().contains(EpollEvents::IN));
+    assert_eq!(ep.wait(&mut buf, Some(Duration::ZERO)).unwrap().len(), 0);
+    ep.modify(&r, EpollEvents::IN | EpollEvents::ONESHOT, 10)
+        .unwrap();
+    let events = ep.wait(&mut buf, Some(Duration::ZERO)).unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].token(), 10);
+    ep.delete(&r).unwrap();
+
+    // Edge-triggered registrations report only on changes.
+    w2.write(b"b").unwrap();
+    let events = ep
+        .wait_with_signal_mask(&mut buf, Some(Duration::from_millis(1)), None)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to wait");
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].token(), 8);
+    let mask = crate::signal::sigset_t::new_empty();
+    let events = ep
+        .wait_with_signal_mask(&mut buf, Some(Duration::ZERO), Some(&mask))
+        .unwrap();
+    assert_eq!(events.len(), 0);
+    w2.write(b"c").unwrap();
+    assert_eq!(ep.wait(&mut buf, Some(Duration::ZERO)).unwrap().len(), 1);
+
+    // Exclusive wakeups can be requested only when adding.
+    let ep2 = Epoll::epoll_create().unwrap();
+    ep2.add(&r, EpollEvents::IN | EpollEvents::EXCLUSIVE, 1)
+        .unwrap();
+    assert_eq!(
+        ep2.modify(&r, EpollEvents::IN | EpollEvents::EXCLUSIVE, 1),
+        Err(crate::result::EINVAL)
+    );
+}
+
+#[test]
+fn eventfd() {
+    use crate::epoll::{Epoll, EpollEvent, EpollEvents};
+    use crate::eventfd::EventFdFlags;
+
+    let efd = EventFd::new(2, EventFdFlags::CLOEXEC | EventFdFlags::NONBLOCK)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to create eventfd");
+    efd.signal(3).unwrap();
+    assert_eq!(efd.wait(), Ok(5));
+    assert_eq!(efd.wait(), Err(crate::result::EAGAIN));
+    assert_eq!(efd.signal(u64::MAX), Err(crate::result::EINVAL));
+
+    let sem = EventFd::new(
+        0,
+        EventFdFlags::CLOEXEC | EventFdFlags::NONBLOCK | EventFdFlags::SEMAPHORE,
+    )
+    .unwrap();
+    let ep = Epoll::epoll_create().unwrap();
+    ep.add(&sem, EpollEvents::IN, 1).unwrap();
+    let mut buf = [EpollEvent::default(); 1];
+    assert_eq!(
+        ep.wait(&mut buf, Some(core::time::Duration::ZERO))
+            .unwrap()
+            .len(),
+        0
+    );
+    sem.signal(2).unwrap();
+    assert_eq!(ep.wait(&mut buf, None).unwrap().len(), 1);
+    assert_eq!(sem.wait(), Ok(1));
+    assert_eq!(sem.wait(), Ok(1));
+    assert_eq!(sem.wait(), Err(crate::result::EAGAIN));
+
+    // Cross-thread wakeup.
+    let blocking = std::sync::Arc::new(EventFd::new(0, EventFdFlags::CLOEXEC).unwrap());
+    let other = blocking.clone();
+    let handle = std::thread::spawn(move || other.signal(1).unwrap());
+    assert_eq!(blocking.wait(), Ok(1));
+    handle.join().unwrap();
+}
+
+#[test]
+fn timerfd() {
+    use crate::poll::PollRequest;
+    use crate::time::ClockId;
+    use crate::timerfd::{TimerFdFlags, TimerSetFlags};
+    use core::time::Duration;
+
+    let t = TimerFd::new(
+        ClockId::MONOTONIC,
+        TimerFdFlags::CLOEXEC | TimerFdFlags::NONBLOCK,
+    )
+    .map_err(|e| e.into_std_io_error())
+    .expect("failed to create timerfd");
+    let setting = t.get().unwrap();
+    assert_eq!(setting.remaining, Duration::ZERO);
+    assert_eq!(t.read_expirations(), Err(crate::result::EAGAIN));
+
+    let old = t
+        .set(
+            Duration::from_millis(1),
+            Duration::from_millis(1),
+            TimerSetFlags::NONE,
+        )
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to arm timer");
+    assert_eq!(old.remaining, Duration::ZERO);
+    assert_eq!(t.get().unwrap().interval, Duration::from_millis(1));
+
+    let mut reqs = [unsafe { PollRequest::for_device(&t).events_raw(linux_unsafe::POLLIN) }];
+    crate::poll::poll_timeout(&mut reqs, None).unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    assert!(t.read_expirations().unwrap() >= 2);
+
+    let old = t.disarm().unwrap();
+    assert_eq!(old.interval, Duration::from_millis(1));
+    assert_eq!(t.get().unwrap().remaining, Duration::ZERO);
+
+    // Absolute expiration times, with cancellation when the clock is set.
+    let rt = TimerFd::new(ClockId::REALTIME, TimerFdFlags::CLOEXEC).unwrap();
+    let now = ClockId::REALTIME.now().unwrap();
+    rt.set(
+        now + Duration::from_millis(1),
+        Duration::ZERO,
+        TimerSetFlags::ABSTIME | TimerSetFlags::CANCEL_ON_SET,
+    )
+    .map_err(|e| e.into_std_io_error())
+    .expect("failed to arm absolute timer");
+    assert_eq!(rt.read_expirations(), Ok(1));
+    assert_eq!(rt.get().unwrap().remaining, Duration::ZERO);
+}
+
+#[test]
+fn inotify() {
+    use crate::inotify::{InotifyEvents, InotifyFlags, WatchMask};
+
+    let dir = tempdir().unwrap();
+    let dir_raw = CString::new(dir.path().as_os_str().as_bytes()).unwrap();
+    let dirf = File::open(&dir_raw, OpenOptions::read_only().directory()).unwrap();
+    let ino = Inotify::new(InotifyFlags::CLOEXEC | InotifyFlags::NONBLOCK)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to create inotify instance");
+    let wd = ino
+        .add_watch(
+            &dir_raw,
+            WatchMask::CREATE | WatchMask::MOVE | WatchMask::ONLYDIR,
+        )
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to add watch");
+    assert_eq!(
+        ino.add_watch(&dir_raw, WatchMask::CREATE | WatchMask::MASK_CREATE),
+        Err(crate::result::EEXIST)
+    );
+
+    let mut buf = [0_u8; 1024];
+    assert!(matches!(
+        ino.read_events(&mut buf),
+        Err(crate::result::EAGAIN)
+    ));
+
+    dirf.mkdir_relative(c"sub", 0o755).unwrap();
+    dirf.rename_relative(c"sub", &dirf, c"renamed", 0).unwrap();
+    let events: Vec<_> = ino.read_events(&mut buf).unwrap().collect();
+    assert_eq!(events.len(), 3);
+    assert_eq!(events[0].wd, wd);
+    assert_eq!(events[0].mask, WatchMask::CREATE | WatchMask::ISDIR);
+    assert_eq!(events[0].name, Some(c"sub"));
+    assert_eq!(events[1].mask, WatchMask::MOVED_FROM | WatchMask::ISDIR);
+    assert_eq!(events[1].name, Some(c"sub"));
+    assert_eq!(events[2].mask, WatchMask::MOVED_TO | WatchMask::ISDIR);
+    assert_eq!(events[2].name, Some(c"renamed"));
+    assert_ne!(events[1].cookie, 0);
+    assert_eq!(events[1].cookie, events[2].cookie);
+
+    ino.remove_watch(wd).unwrap();
+    let mut events = ino.read_events(&mut buf).unwrap();
+    let event = events.next().unwrap();
+    assert!(event.mask.contains(WatchMask::IGNORED));
+    assert_eq!(event.name, None);
+    assert_eq!(events.to_remaining_bytes().len(), 0);
+
+    // A truncated buffer yields no partial events.
+    assert_eq!(InotifyEvents::from_buffer(&[0_u8; 8]).count(), 0);
+}
+
+#[test]
+fn fanotify() {
+    use crate::fanotify::{FanotifyFlags, FanotifyInfo, FanotifyMask, FanotifyResponse, MarkFlags};
+
+    let group = match Fanotify::new(
+        FanotifyFlags::CLOEXEC | FanotifyFlags::REPORT_DFID_NAME,
+        linux_unsafe::O_RDONLY | linux_unsafe::O_CLOEXEC,
+    ) {
+        Ok(group) => group,
+        // fanotify requires privileges that tests might not have.
+        Err(crate::result::EPERM) => return,
+        Err(e) => panic!("failed to create fanotify group: {:?}", e),
+    };
+
+    let dir = tempdir().unwrap();
+    let dir_raw = CString::new(dir.path().as_os_str().as_bytes()).unwrap();
+    let dirf = File::open(&dir_raw, OpenOptions::read_only().directory()).unwrap();
+    group
+        .mark_file(MarkFlags::ADD, FanotifyMask::CREATE, &dirf)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to add mark");
+    dirf.mknod_relative(c"fifo", linux_unsafe::S_IFIFO | 0o600, 0)
+        .unwrap();
+
+    let mut buf = [0_u8; 1024];
+    let mut events = group.read_events(&mut buf).unwrap();
+    let event = events.next().expect("no event");
+    assert!(event.mask().contains(FanotifyMask::CREATE));
+    assert_eq!(event.raw_fd(), None);
+    assert_eq!(event.pid(), unsafe { linux_unsafe::getpid() });
+    let mut records = event.info_records();
+    match records.next() {
+        Some(FanotifyInfo::FidName(fid, name)) => {
+            assert_eq!(fid.info_type, linux_unsafe::FAN_EVENT_INFO_TYPE_DFID_NAME);
+            assert!(!fid.handle.is_empty());
+            assert_eq!(name, c"fifo");
+        }
+        other => panic!("unexpected info record {:?}", other),
+    }
+    assert_eq!(records.next(), None);
+    assert!(events.next().is_none());
+
+    // Permission events, with responses.
+    let perm = Fanotify::new(
+        FanotifyFlags::CLOEXEC | FanotifyFlags::CLASS_CONTENT,
+        linux_unsafe::O_RDONLY | linux_unsafe::O_CLOEXEC,
+    )
+    .unwrap();
+    let mut filename: PathBuf = dir.path().into();
+    filename.push("guarded");
+    let filename_raw = CString::new(filename.as_os_str().as_bytes()).unwrap();
+    drop(File::create_raw(&filename_raw, 0o600).unwrap());
+    perm.mark(MarkFlags::ADD, FanotifyMask::OPEN_PERM, &filename_raw)
+        .map_err(|e| e.into_std_io_error())
+        .expect("failed to add permission mark");
+
+    let opener_path = filename_raw.clone();
+    let opener = std::thread::spawn(move || {
+        let first = File::open(&opener_path, OpenOptions::read_only()).map(|_| ());
+        let second = File::open(&opener_path, OpenOptions::read_only()).map(|_| ());
+        (first, second)
+    });
+    for response in [FanotifyResponse::ALLOW, FanotifyResponse::DENY] {
+        let mut events = perm.read_events(&mut buf).unwrap();
+        let event = events.next().expect("no permission event");
+        assert!(event.mask().contains(FanotifyMask::OPEN_PERM));
+        perm.respond(&event, response).unwrap();
+        drop(unsafe { event.take_file() }.expect("no event file"));
+    }
+    let (first, second) = opener.join().unwrap();
+    assert_eq!(first, Ok(()));
+    assert_eq!(second, Err(crate::result::EPERM));
+}
+
+#[test]
+fn io_uring() {
+    use crate::io_uring::{EntryFlags, SubmissionEntry, Target, TimeoutFlags, CURRENT_POSITION};
+    use std::time::Duration;
+
+    let mut ring = match IoUring::new(8) {
+        Ok(ring) => ring,
+        // io_uring might be disabled by the kernel or a seccomp filter.
+        Err(crate::result::ENOSYS) | Err(crate::result::EPERM) => return,
+        Err(e) => panic!("failed to create io_uring: {:?}", e),
+    };
+    assert!(ring.submission_queue_size() >= 8);
+
+    let (r, w) = crate::pipe(linux_unsafe::O_CLOEXEC).unwrap();
+    let msg = b"hello";
+    let mut buf = [0_u8; 16];
+    unsafe {
+        ring.push(
+            &SubmissionEntry::write(&w, msg, CURRENT_POSITION)
+                .with_user_data(1)
+                .with_flags(EntryFlags::LINK),
+        )
+        .unwrap();
+        ring.push(&SubmissionEntry::read(&r, &mut buf, CURRENT_POSITION).with_user_data(2))
+            .unwrap();
+    }
+    assert_eq!(ring.submit_and_wait(2), Ok(2));
+    let mut results = [None, None];
+    while let Some(c) = ring.pop_completion() {
+        results[(c.user_data() - 1) as usize] = Some(c.result());
+    }
+    assert_eq!(results, [Some(Ok(5)), Some(Ok(5))]);
+    assert_eq!(&buf[..5], msg);
+    assert!(ring.pop_completion().is_none());
+
+    // Operations on registered files and buffers.
+    ring.register_files(&[r.fd(), w.fd()]).unwrap();
+    let mut fixed = [0_u8; 32];
+    fixed[..3].copy_from_slice(b"abc");
+    unsafe {
+        ring.register_buffers(&[crate::fd::IoSliceMut::new(&mut fixed)])
+            .unwrap();
+        let (src, dst) = fixed.split_at_mut(16);
+        ring.push(
+            &SubmissionEntry::write_fixed(Target::Fixed(1), &src[..3], CURRENT_POSITION, 0)
+                .with_user_data(3)
+                .with_flags(EntryFlags::LINK),
+        )
+        .unwrap();
+        ring.push(
+            &SubmissionEntry::read_fixed(Target::Fixed(0), dst, CURRENT_POSITION, 0)
+                .with_user_data(4),
+        )
+        .unwrap();
+    }
+    ring.submit_and_wait(2).unwrap();
+    let a = ring.pop_completion().unwrap();
+    let b = ring.pop_completion().unwrap();
+    assert_eq!((a.user_data(), a.result()), (3, Ok(3)));
+    assert_eq!((b.user_data(), b.result()), (4, Ok(3)));
+    assert_eq!(&fixed[16..19], b"abc");
+    ring.unregister_buffers().unwrap();
+    assert_eq!(ring.update_files(1, &[-1]), Ok(1));
+    ring.unregister_files().unwrap();
+
+    // A timeout with no other operations expires with ETIME.
+    let ts = crate::time::kernel_timespec_from_duration(Duration::from_millis(1)).unwrap();
+    unsafe {
+        ring.push(&SubmissionEntry::timeout(&ts, 0, TimeoutFlags::NONE).with_user_data(5))
+            .unwrap();
+    }
+    ring.submit_and_wait(1).unwrap();
+    let c = ring.pop_completion().unwrap();
+    assert_eq!((c.user_data(), c.result()), (5, Err(crate::result::ETIME)));
+
+    // Opening, inspecting, and closing a file.
+    let dir = tempdir().unwrap();
+    let dir_raw = CString::new(dir.path().as_os_str().as_bytes()).unwrap();
+    let dirf = File::open(&dir_raw, OpenOptions::read_only().directory()).unwrap();
+    dirf.mknod_relative(c"file", linux_unsafe::S_IFREG | 0o600, 0)
+        .unwrap();
+    let mut stx: linux_unsafe::statx = unsafe { core::mem::zeroed() };
+    unsafe {
+        ring.push(
+            &SubmissionEntry::openat(&dirf, c"file", OpenOptions::read_only().close_on_exec())
+                .with_user_data(6),
+        )
+        .unwrap();
+        ring.push(
+            &SubmissionEntry::statx(&dirf, c"file", 0, linux_unsafe::STATX_BASIC_STATS, &mut stx)
+                .with_user_data(7),
+        )
+        .unwrap();
+    }
+    ring.submit_and_wait(2).unwrap();
+    let mut opened = None;
+    while let Some(c) = ring.pop_completion() {
+        match c.user_data() {
+            6 => opened = Some(c.result().unwrap()),
+            7 => assert_eq!(c.result(), Ok(0)),
+            other => panic!("unexpected completion {}", other),
+        }
+    }
+    let metadata = crate::fd::Metadata::from_raw(stx);
+    assert!(metadata.is_file());
+    let opened: File = unsafe { File::from_raw_fd(opened.unwrap() as linux_unsafe::int) };
+    unsafe {
+        ring.push(&SubmissionEntry::close(opened).with_user_data(8))
+            .unwrap();
+    }
+    ring.submit_and_wait(1).unwrap();
+    let c = ring.pop_completion().unwrap();
+    assert_eq!((c.user_data(), c.result()), (8, Ok(0)));
+}
+
+#[test]
+fn io_uring_submission_polling() {
+    use crate::io_uring::{IoUringOptions, SubmissionEntry, CURRENT_POSITION};
+    use std::time::Duration;
+
+    let options = IoUringOptions::new().submission_polling(Duration::from_millis(1));
+    let mut ring = match IoUring::with_options(4, options) {
+        Ok(ring) => ring,
+        // Older kernels require CAP_SYS_NICE for a polling thread.
+        Err(crate::result::ENOSYS) | Err(crate::result::EPERM) => return,
+        Err(e) => panic!("failed to create io_uring: {:?}", e),
+    };
+
+    let (r, w) = crate::pipe(linux_unsafe::O_CLOEXEC).unwrap();
+    let mut buf = [0_u8; 8];
+    for (i, msg) in [b"one", b"two"].into_iter().enumerate() {
+        if i > 0 {
+            // Let the polling thread go idle, so that submitting must
+            // wake it again.
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        unsafe {
+            ring.push(&SubmissionEntry::write(&w, msg, CURRENT_POSITION).with_user_data(i as u64))
+                .unwrap();
+        }
+        assert_eq!(ring.submit(), Ok(1));
+        let c = loop {
+            if let Some(c) = ring.pop_completion() {
+                break c;
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        };
+        assert_eq!((c.user_data(), c.result()), (i as u64, Ok(3)));
+        assert_eq!(r.read(&mut buf), Ok(3));
+        assert_eq!(&buf[..3], msg);
+    }
+}
+
+#[test]
+fn io_uring_socket() {
+    use crate::io_uring::SubmissionEntry;
+    use crate::socket::ip::{Ipv4Addr, SockAddrIpv4, AF_INET, IPPROTO_TCP};
+    use crate::socket::sock_type;
+
+    let mut ring = match IoUring::new(8) {
+        Ok(ring) => ring,
+        // io_uring might be disabled by the kernel or a seccomp filter.
+        Err(crate::result::ENOSYS) | Err(crate::result::EPERM) => return,
+        Err(e) => panic!("failed to create io_uring: {:?}", e),
+    };
+
+    let listener = File::socket(AF_INET, sock_type::SOCK_STREAM, IPPROTO_TCP).unwrap();
+    listener
+        .bind(SockAddrIpv4::new(Ipv4Addr::LOOPBACK, 0))
+        .unwrap();
+    listener.listen(1).unwrap();
+    let local = listener.local_addr_as::<SockAddrIpv4>().unwrap();
+    let client = File::socket(AF_INET, sock_type::SOCK_STREAM, IPPROTO_TCP).unwrap();
+
+    unsafe {
+        ring.push(
+            &SubmissionEntry::accept(&listener, linux_unsafe::SOCK_CLOEXEC).with_user_data(1),
+        )
+        .unwrap();
+        ring.push(&SubmissionEntry::connect(&client, &local).with_user_data(2))
+            .unwrap();
+    }
+    ring.submit_and_wait(2).unwrap();
+    let mut accepted = None;
+    while let Some(c) = ring.pop_completion() {
+        match c.user_data() {
+            1 => accepted = Some(c.result().unwrap()),
+            2 => assert_eq!(c.result(), Ok(0)),
+            other => panic!("unexpected completion {}", other),
+        }
+    }
+    let conn: File = unsafe { File::from_raw_fd(accepted.unwrap() as linux_unsafe::int) };
+
+    let mut buf = [0_u8; 8];
+    unsafe {
+        ring.push(&SubmissionEntry::send(&client, b"hello", 0).with_user_data(3))
+            .unwrap();
+        ring.push(&SubmissionEntry::recv(&conn, &mut buf, 0).with_user_data(4))
+            .unwrap();
+    }
+    ring.submit_and_wait(2).unwrap();
+    let mut results = [None, None];
+    while let Some(c) = ring.pop_completion() {
+        results[(c.user_data() - 3) as usize] = Some(c.result());
+    }
+    assert_eq!(results, [Some(Ok(5)), Some(Ok(5))]);
+    assert_eq!(&buf[..5], b"hello");
+}
+
+#[test]
+fn socket_tcp_accept() {
+    use crate::socket::ip::{IpAddr, Ipv4Addr, SockAddrIp, SockAddrIpv4, AF_INET, IPPROTO_TCP};
+    use crate::socket::{sock_type, Shutdown, SocketFlags};
+
+    let listener = File::socket(AF_INET, sock_type::SOCK_STREAM, IPPROTO_TCP).unwrap();
+    listener
+        .bind(SockAddrIpv4::new(Ipv4Addr::LOOPBACK, 0))
+        .unwrap();
+    listener.listen(1).unwrap();
+    let local = listener.local_addr().unwrap();
+    assert_eq!(local.address_family(), AF_INET);
+    assert_ne!(local.port(), 0);
+
+    let client = File::socket(AF_INET, sock_type::SOCK_STREAM, IPPROTO_TCP).unwrap();
+    client.connect(local).unwrap();
+    let (conn, peer) = listener.accept::<SockAddrIp>(SocketFlags::CLOEXEC).unwrap();
+    let peer = peer.expect("peer address not decoded");
+    assert_eq!(peer.port(), client.local_addr().unwrap().port());
+    assert_eq!(client.peer_addr().unwrap().port(), local.port());
+    let conn_peer = conn.peer_addr_as::<SockAddrIpv4>().unwrap();
+    assert_eq!(conn_peer.host_address().as_octets(), [127, 0, 0, 1]);
+    match peer.host_address() {
+        IpAddr::V4(addr) => assert_eq!(addr.as_octets(), [127, 0, 0, 1]),
+        IpAddr::V6(addr) => panic!("unexpected IPv6 peer {:?}", addr),
+    }
+    // The connection has the same device type as the listener.
+    conn.ioctl(crate::socket::ip::tcp::SIOCATMARK, ()).unwrap();
+
+    // An IPv4 address cannot be decoded as IPv6.
+    assert_eq!(
+        conn.local_addr_as::<crate::socket::ip::SockAddrIpv6>()
+            .map(|_| ()),
+        Err(crate::result::EAFNOSUPPORT)
+    );
+
+    client.write(b"hi").unwrap();
+    client.shutdown(Shutdown::Write).unwrap();
+    let mut buf = [0_u8; 8];
+    assert_eq!(conn.read(&mut buf), Ok(2));
+    assert_eq!(conn.read(&mut buf), Ok(0));
+
+    // A peer address that can't be decoded doesn't lose the connection.
+    let client = File::socket(AF_INET, sock_type::SOCK_STREAM, IPPROTO_TCP).unwrap();
+    client.connect(local).unwrap();
+    let (conn, peer) = listener
+        .accept::<crate::socket::ip::SockAddrIpv6>(SocketFlags::CLOEXEC)
+        .unwrap();
+    assert!(peer.is_none());
+    client.write(b"hi").unwrap();
+    assert_eq!(conn.read(&mut buf), Ok(2));
+}
+
+#[test]
+fn socket_msg_rights_and_credentials() {
+    use crate::fd::sockopt::SO_PASSCRED;
+    use crate::fd::{IoSlice, IoSliceMut};
+    use crate::socket::msg::{
+        cmsg_space, ControlBuffer, ControlMessage, ControlMessageWriter, Credentials, MsgFlags,
+    };
+    use crate::socket::sock_type;
+    use crate::socket::unix::{AF_UNIX, UNIX_DGRAM};
+
+    let (a, b) = crate::socketpair(AF_UNIX, sock_type::SOCK_DGRAM, UNIX_DGRAM).unwrap();
+    b.setsockopt(SO_PASSCRED, 1).unwrap();
+
+    let (pipe_r, pipe_w) = crate::pipe(linux_unsafe::O_CLOEXEC).unwrap();
+    let creds = Credentials::current();
+    const SPACE: usize = cmsg_space(core::mem::size_of::<linux_unsafe::int>())
+        + cmsg_space(core::mem::size_of::<linux_unsafe::ucred>());
+    let mut cbuf = ControlBuffer::<SPACE>::new();
+    let mut w = ControlMessageWriter::new(cbuf.as_mut_slice());
+    w.rights(&[&pipe_w]).unwrap();
+    w.credentials(creds).unwrap();
+    assert_eq!(w.rights(&[&pipe_w]), Err(crate::result::ENOBUFS));
+    assert_eq!(w.as_bytes().len(), SPACE);
+    let sent = a
+        .send_msg(&[IoSlice::new(b"msg")], w.as_bytes(), MsgFlags::NONE)
+        .unwrap();
+    assert_eq!(sent, 3);
+    drop(pipe_w);
+
+    let mut data = [0_u8; 8];
+    let mut rbuf = ControlBuffer::<64>::new();
+    let mut msg = b
+        .recv_msg(
+            &mut [IoSliceMut::new(&mut data)],
+            rbuf.as_mut_slice(),
+            MsgFlags::CMSG_CLOEXEC,
+        )
+        .unwrap();
+    assert_eq!(msg.len(), 3);
+    assert!(!msg.flags().contains(MsgFlags::CTRUNC));
+    let mut received = None;
+    let mut got_creds = None;
+    for cmsg in msg.control_messages() {
+        match cmsg {
+            ControlMessage::Rights(mut files) => {
+                received = files.next();
+                assert!(files.next().is_none());
+            }
+            ControlMessage::Credentials(c) => got_creds = Some(c),
+            other => panic!("unexpected control message {:?}", other),
+        }
+    }
+    drop(msg);
+    assert_eq!(&data[..3], b"msg");
+    assert_eq!(got_creds, Some(creds));
+
+    let received = received.expect("no SCM_RIGHTS message");
+    received.write(b"via fd").unwrap();
+    drop(received);
+    let mut buf = [0_u8; 8];
+    assert_eq!(pipe_r.read(&mut buf), Ok(6));
+    assert_eq!(&buf[..6], b"via fd");
+    // The received file was the only remaining writer, so the pipe is now
+    // closed.
+    assert_eq!(pipe_r.read(&mut buf), Ok(0));
+}
+
+#[test]
+fn socket_msg_udp_ancillary() {
+    use crate::fd::{IoSlice, IoSliceMut};
+    use crate::socket::ip::{Ipv4Addr, SockAddrIpv4, AF_INET, IPPROTO_UDP, IP_PKTINFO};
+    use crate::socket::msg::{ControlBuffer, ControlMessage, MsgFlags};
+    use crate::socket::sock_type;
+
+    let udp = || File::socket(AF_INET, sock_type::SOCK_DGRAM, IPPROTO_UDP).unwrap();
+    let rx = udp();
+    rx.bind(SockAddrIpv4::new(Ipv4Addr::LOOPBACK, 0)).unwrap();
+    rx.setsockopt(IP_PKTINFO, 1).unwrap();
+    rx.setsockopt(crate::fd::sockopt::SO_TIMESTAMPNS, 1)
+        .unwrap();
+    let rx_addr = rx.local_addr().unwrap();
+
+    let tx = udp();
+    tx.send_msg_to(rx_addr, &[IoSlice::new(b"ping")], &[], MsgFlags::NONE)
+        .unwrap();
+
+    let mut data = [0_u8; 8];
+    let mut cbuf = ControlBuffer::<128>::new();
+    let mut msg = rx
+        .recv_msg(
+            &mut [IoSliceMut::new(&mut data)],
+            cbuf.as_mut_slice(),
+            MsgFlags::NONE,
+        )
+        .unwrap();
+    assert_eq!(msg.len(), 4);
+    let from = msg.addr::<SockAddrIpv4>().unwrap();
+    assert_eq!(from.port(), tx.local_addr().unwrap().port());
+    let mut got_info = None;
+    let mut got_time = None;
+    for cmsg in msg.control_messages() {
+        match cmsg {
+            ControlMessage::PacketInfo(info) => got_info = Some(info),
+            ControlMessage::TimestampNs(t) => got_time = Some(t),
+            other => panic!("unexpected control message {:?}", other),
+        }
+    }
+    let info = got_info.expect("no IP_PKTINFO message");
+    assert_eq!(info.destination.as_octets(), [127, 0, 0, 1]);
+    assert_ne!(info.interface_index, 0);
+    assert!(!got_time.expect("no SCM_TIMESTAMPNS message").is_zero());
+}
+
+#[test]
+fn socket_udp() {
+    use crate::socket::ip::udp::{SIOCINQ, UDP_SEGMENT};
+    use crate::socket::ip::{
+        Ipv4Addr, Ipv4MulticastRequest, Ipv6Addr, Ipv6MulticastRequest, SockAddrIp, SockAddrIpv4,
+        SockAddrIpv6, AF_INET, AF_INET6, IPPROTO_UDP, IPV6_JOIN_GROUP, IPV6_LEAVE_GROUP,
+        IPV6_MULTICAST_HOPS, IPV6_MULTICAST_IF, IP_ADD_MEMBERSHIP, IP_DROP_MEMBERSHIP,
+        IP_MULTICAST_LOOP, IP_MULTICAST_TTL,
+    };
+    use crate::socket::msg::MsgFlags;
+    use crate::socket::sock_type;
+
+    let rx = File::socket(AF_INET, sock_type::SOCK_DGRAM, IPPROTO_UDP).unwrap();
+    rx.bind(SockAddrIpv4::new(Ipv4Addr::LOOPBACK, 0)).unwrap();
+    let rx_addr = rx.local_addr().unwrap();
+    let tx = File::socket(AF_INET, sock_type::SOCK_DGRAM, IPPROTO_UDP).unwrap();
+
+    assert_eq!(tx.send_to(b"hello", rx_addr, MsgFlags::NONE), Ok(5));
+    assert_eq!(rx.ioctl(SIOCINQ, ()), Ok(5));
+    let mut buf = [0_u8; 3];
+    let (len, from) = rx.recv_from(&mut buf, MsgFlags::TRUNC).unwrap();
+    assert_eq!(len, 5);
+    assert_eq!(&buf, b"hel");
+    let from = from.expect("sender address not decoded");
+    assert_eq!(from.port(), tx.local_addr().unwrap().port());
+
+    // With segmentation offload, one send produces several datagrams.
+    tx.setsockopt(UDP_SEGMENT, 2).unwrap();
+    assert_eq!(tx.send_to(b"abcde", rx_addr, MsgFlags::NONE), Ok(5));
+    let mut buf = [0_u8; 8];
+    for expected in [&b"ab"[..], b"cd", b"e"] {
+        let (len, _) = rx
+            .recv_from_as::<SockAddrIp>(&mut buf, MsgFlags::NONE)
+            .unwrap();
+        assert_eq!(&buf[..len], expected);
+    }
+
+    rx.setsockopt(IP_MULTICAST_TTL, 4).unwrap();
+    assert_eq!(rx.getsockopt(IP_MULTICAST_TTL), Ok(4));
+    rx.setsockopt(IP_MULTICAST_LOOP, 0).unwrap();
+    assert_eq!(rx.getsockopt(IP_MULTICAST_LOOP), Ok(0));
+    // Interface 1 is always the loopback interface.
+    let group = Ipv4MulticastRequest::new(Ipv4Addr::from_octets([239, 1, 2, 3]), 1);
+    rx.setsockopt(IP_ADD_MEMBERSHIP, group).unwrap();
+    rx.setsockopt(IP_DROP_MEMBERSHIP, group).unwrap();
+
+    let rx6 = File::socket(AF_INET6, sock_type::SOCK_DGRAM, IPPROTO_UDP).unwrap();
+    rx6.bind(SockAddrIpv6::new(Ipv6Addr::LOOPBACK, 0)).unwrap();
+    rx6.setsockopt(IPV6_MULTICAST_IF, 1).unwrap();
+    assert_eq!(rx6.getsockopt(IPV6_MULTICAST_IF), Ok(1));
+    rx6.setsockopt(IPV6_MULTICAST_HOPS, 4).unwrap();
+    assert_eq!(rx6.getsockopt(IPV6_MULTICAST_HOPS), Ok(4));
+    let mut group = [0_u8; 16];
+    group[..2].copy_from_slice(&[0xff, 0x02]);
+    group[15] = 0x7b;
+    let group = Ipv6MulticastRequest::new(Ipv6Addr::from_octets(group), 1);
+    rx6.setsockopt(IPV6_JOIN_GROUP, group).unwrap();
+    // Joining the same group twice is an error, which shows that the kernel
+    // actually recorded the membership.
+    assert_eq!(
+        rx6.setsockopt(IPV6_JOIN_GROUP, group),
+        Err(crate::result::EADDRINUSE)
+    );
+    rx6.setsockopt(IPV6_LEAVE_GROUP, group).unwrap();
+    assert_eq!(
+        rx6.setsockopt(IPV6_LEAVE_GROUP, group),
+        Err(crate::result::EADDRNOTAVAIL)
+    );
+}
+
+#[test]
+fn socket_unix() {
+    use crate::socket::msg::{Credentials, MsgFlags};
+    use crate::socket::sock_type;
+    use crate::socket::unix::{
+        SockAddrUnix, UnixAddress, AF_UNIX, SIOCINQ, SO_PEERCRED, SO_PEERPIDFD, UNIX_DGRAM,
+        UNIX_SEQPACKET, UNIX_STREAM,
+    };
+    use crate::socket::SocketFlags;
+
+    assert_eq!(SockAddrUnix::unnamed().address(), UnixAddress::Unnamed);
+    // The kernel must not write into an address that has no way to record
+    // the length that the kernel reports alongside it.
+    let mut raw = SockAddrUnix::unnamed();
+    assert_eq!(
+        unsafe { crate::socket::SockAddr::sockaddr_raw_mut(&mut raw) }.1,
+        0
+    );
+    assert_eq!(
+        SockAddrUnix::from_abstract(b"a\0b").unwrap().address(),
+        UnixAddress::Abstract(b"a\0b")
+    );
+    assert_eq!(
+        SockAddrUnix::from_abstract(&[b'x'; 108]),
+        Err(crate::result::ENAMETOOLONG)
+    );
+    assert_eq!(SockAddrUnix::from_pathname(c""), Err(crate::result::EINVAL));
+
+    // Stream sockets bound to a filesystem path.
+    let dir = tempdir().unwrap();
+    let path = CString::new(dir.path().join("sock").as_os_str().as_bytes()).unwrap();
+    let addr = SockAddrUnix::from_pathname(&path).unwrap();
+    assert_eq!(addr.address(), UnixAddress::Pathname(&path));
+    let listener = File::socket(AF_UNIX, sock_type::SOCK_STREAM, UNIX_STREAM).unwrap();
+    listener.bind(addr).unwrap();
+    listener.listen(1).unwrap();
+    assert_eq!(listener.local_addr_as::<SockAddrUnix>(), Ok(addr));
+    let client = File::socket(AF_UNIX, sock_type::SOCK_STREAM, UNIX_STREAM).unwrap();
+    client.connect(addr).unwrap();
+    let (conn, peer) = listener
+        .accept::<SockAddrUnix>(SocketFlags::CLOEXEC)
+        .unwrap();
+    let peer = peer.expect("peer address not decoded");
+    assert_eq!(peer.address(), UnixAddress::Unnamed);
+    assert_eq!(client.peer_addr_as::<SockAddrUnix>(), Ok(addr));
+    client.write(b"hi").unwrap();
+    assert_eq!(conn.ioctl(SIOCINQ, ()), Ok(2));
+    assert_eq!(conn.getsockopt(SO_PEERCRED), Ok(Credentials::current()));
+
+    // Datagram sockets bound to abstract names.
+    let name = std::format!("linux-io-test-{}", std::process::id());
+    let rx_addr = SockAddrUnix::from_abstract(name.as_bytes()).unwrap();
+    let rx = File::socket(AF_UNIX, sock_type::SOCK_DGRAM, UNIX_DGRAM).unwrap();
+    rx.bind(rx_addr).unwrap();
+    let tx = File::socket(AF_UNIX, sock_type::SOCK_DGRAM, UNIX_DGRAM).unwrap();
+    tx.send_to(b"unbound", rx_addr, MsgFlags::NONE).unwrap();
+    let mut buf = [0_u8; 16];
+    let (len, from) = rx
+        .recv_from_as::<SockAddrUnix>(&mut buf, MsgFlags::NONE)
+        .unwrap();
+    assert_eq!(&buf[..len], b"unbound");
+    let from = from.expect("sender address not decoded");
+    assert_eq!(from.address(), UnixAddress::Unnamed);
+    // An address from the Unix domain cannot be decoded as an IP address,
+    // but the datagram is still received.
+    tx.send_to(b"again", rx_addr, MsgFlags::NONE).unwrap();
+    let (len, from) = rx.recv_from(&mut buf, MsgFlags::NONE).unwrap();
+    assert_eq!(&buf[..len], b"again");
+    assert!(from.is_none());
+
+    // Socket pairs.
+    let (a, b) = crate::socketpair(AF_UNIX, sock_type::SOCK_SEQPACKET, UNIX_SEQPACKET).unwrap();
+    a.write(b"packet").unwrap();
+    assert_eq!(b.read(&mut buf), Ok(6));
+    assert_eq!(a.getsockopt(SO_PEERCRED), Ok(Credentials::current()));
+    match b.getsockopt(SO_PEERPIDFD) {
+        Ok(pidfd) => {
+            // Signal zero only checks that the pidfd refers to a live process.
+            let child = unsafe { crate::process::Child::from_raw_pidfd(pidfd, 0) };
+            child.send_signal(0).unwrap();
+        }
+        // SO_PEERPIDFD requires Linux 6.5.
+        Err(e) => assert_eq!(e, crate::result::ENOPROTOOPT),
+    }
+}
+
+#[test]
+fn socket_netlink_route() {
+    // Run in a separate thread so that entering a new network namespace
+    // can't affect other tests.
+    std::thread::spawn(netlink_route_loopback).join().unwrap();
+}
+
+fn netlink_route_loopback() {
+    use crate::socket::ip::{IpAddr, Ipv4Addr, AF_INET};
+    use crate::socket::netlink::{
+        MessageBuilder, MessageFlags, Messages, SockAddrNetlink, AF_NETLINK, NETLINK_ROUTE,
+    };
+    use crate::socket::sock_type;
+
+    // Configuring interfaces is only safe in a private network namespace,
+    // and so we only read the existing configuration when that fails.
+    let private = unsafe { linux_unsafe::unshare(linux_unsafe::CLONE_NEWNET as _) }.is_ok();
+
+    let sock = File::socket(AF_NETLINK, sock_type::SOCK_RAW, NETLINK_ROUTE).unwrap();
+    sock.bind(SockAddrNetlink::new(0, 0)).unwrap();
+    let local = sock.local_addr_as::<SockAddrNetlink>().unwrap();
+    assert_ne!(local.port_id(), 0);
+
+    let mut req = [0_u8; 256];
+    let mut buf = [0_u8; 32768];
+
+    // Multipart dump of all links.
+    let mut b = MessageBuilder::new(
+        &mut req,
+        linux_unsafe::RTM_GETLINK,
+        MessageFlags::REQUEST | MessageFlags::DUMP,
+        1,
+    )
+    .unwrap();
+    b.header(&linux_unsafe::ifinfomsg::default()).unwrap();
+    let mut lo = None;
+    let mut count = 0;
+    sock.netlink_request(b.as_bytes(), &mut buf, |msg| {
+        assert!(msg.flags().contains(MessageFlags::MULTI));
+        let link = msg.link().unwrap();
+        count += 1;
+        if link.flags() & linux_unsafe::IFF_LOOPBACK != 0 {
+            assert_eq!(link.name(), Some(c"lo"));
+            assert!(link.mtu().is_some());
+            lo = Some((link.index(), link.flags()));
+        }
+        Ok(())
+    })
+    .unwrap();
+    let (lo_index, lo_flags) = lo.expect("no loopback interface");
+    if private {
+        // A new namespace contains only the loopback interface, which is
+        // initially down.
+        assert_eq!(count, 1);
+        assert_eq!(lo_flags & linux_unsafe::IFF_UP, 0);
+    }
+
+    // A request for a nonexistent object reports the kernel's error code.
+    let mut b = MessageBuilder::new(
+        &mut req,
+        linux_unsafe::RTM_GETLINK,
+        MessageFlags::REQUEST,
+        2,
+    )
+    .unwrap();
+    b.header(&linux_unsafe::ifinfomsg {
+        ifi_index: 0x7fff_0000,
+        ..Default::default()
+    })
+    .unwrap();
+    assert_eq!(
+        sock.netlink_request(b.as_bytes(), &mut buf, |_| Ok(())),
+        Err(crate::result::ENODEV)
+    );
+
+    if !private {
+        return;
+    }
+
+    // Bring the loopback interface up, requesting an acknowledgement.
+    let mut b = MessageBuilder::new(
+        &mut req,
+        linux_unsafe::RTM_NEWLINK,
+        MessageFlags::REQUEST | MessageFlags::ACK,
+        3,
+    )
+    .unwrap();
+    b.header(&linux_unsafe::ifinfomsg {
+        ifi_index: lo_index,
+        ifi_flags: linux_unsafe::IFF_UP,
+        ifi_change: linux_unsafe::IFF_UP,
+        ..Default::default()
+    })
+    .unwrap();
+    sock.netlink_request(b.as_bytes(), &mut buf, |_| Ok(()))
+        .unwrap();
+
+    // A single-object request gets a single response.
+    let mut b = MessageBuilder::new(
+        &mut req,
+        linux_unsafe::RTM_GETLINK,
+        MessageFlags::REQUEST,
+        4,
+    )
+    .unwrap();
+    b.header(&linux_unsafe::ifinfomsg::default()).unwrap();
+    b.attr_cstr(linux_unsafe::IFLA_IFNAME, c"lo").unwrap();
+    let mut flags = 0;
+    sock.netlink_request(b.as_bytes(), &mut buf, |msg| {
+        flags = msg.link().unwrap().flags();
+        Ok(())
+    })
+    .unwrap();
+    assert_ne!(flags & linux_unsafe::IFF_UP, 0);
+
+    // Add an address, which then can't be added again exclusively.
+    let addr = Ipv4Addr::from_octets([10, 11, 12, 13]);
+    let mut b = MessageBuilder::new(
+        &mut req,
+        linux_unsafe::RTM_NEWADDR,
+        MessageFlags::REQUEST | MessageFlags::ACK | MessageFlags::CREATE | MessageFlags::EXCL,
+        5,
+    )
+    .unwrap();
+    b.header(&linux_unsafe::ifaddrmsg {
+        ifa_family: AF_INET as u8,
+        ifa_prefixlen: 24,
+        ifa_index: lo_index as u32,
+        ..Default::default()
+    })
+    .unwrap();
+    b.attr_ip_addr(linux_unsafe::IFA_LOCAL, addr).unwrap();
+    b.attr_ip_addr(linux_unsafe::IFA_ADDRESS, addr).unwrap();
+    let add_addr = b.as_bytes();
+    sock.netlink_request(add_addr, &mut buf, |_| Ok(()))
+        .unwrap();
+    assert_eq!(
+        sock.netlink_request(add_addr, &mut buf, |_| Ok(())),
+        Err(crate::result::EEXIST)
+    );
+
+    let mut b = MessageBuilder::new(
+        &mut req,
+        linux_unsafe::RTM_GETADDR,
+        MessageFlags::REQUEST | MessageFlags::DUMP,
+        6,
+    )
+    .unwrap();
+    b.header(&linux_unsafe::ifaddrmsg {
+        ifa_family: AF_INET as u8,
+        ..Default::default()
+    })
+    .unwrap();
+    let mut found = false;
+    sock.netlink_request(b.as_bytes(), &mut buf, |msg| {
+        let a = msg.address().unwrap();
+        assert_eq!(a.family(), AF_INET);
+        if let Some(IpAddr::V4(local)) = a.local() {
+            if local.as_octets() == addr.as_octets() {
+                assert_eq!(a.prefix_len(), 24);
+                assert_eq!(a.index(), lo_index as u32);
+                assert_eq!(a.label(), Some(c"lo"));
+                found = true;
+            }
+        }
+        Ok(())
+    })
+    .unwrap();
+    assert!(found, "added address not found");
+
+    // Add a route through the loopback interface. The request doesn't ask
+    // for an acknowledgement, but must still complete rather than waiting
+    // for a response that the kernel would otherwise never send.
+    let dst = Ipv4Addr::from_octets([10, 20, 0, 0]);
+    let mut b = MessageBuilder::new(
+        &mut req,
+        linux_unsafe::RTM_NEWROUTE,
+        MessageFlags::REQUEST | MessageFlags::CREATE,
+        7,
+    )
+    .unwrap();
+    b.header(&linux_unsafe::rtmsg {
+        rtm_family: AF_INET as u8,
+        rtm_dst_len: 16,
+        rtm_table: linux_unsafe::RT_TABLE_MAIN,
+        rtm_protocol: linux_unsafe::RTPROT_STATIC,
+        rtm_scope: linux_unsafe::RT_SCOPE_LINK,
+        rtm_type: linux_unsafe::RTN_UNICAST,
+        ..Default::default()
+    })
+    .unwrap();
+    b.attr_ip_addr(linux_unsafe::RTA_DST, dst).unwrap();
+    b.attr_u32(linux_unsafe::RTA_OIF, lo_index as u32).unwrap();
+    b.attr_u32(linux_unsafe::RTA_PRIORITY, 42).unwrap();
+    sock.netlink_request(b.as_bytes(), &mut buf, |_| Ok(()))
+        .unwrap();
+
+    let mut b = MessageBuilder::new(
+        &mut req,
+        linux_unsafe::RTM_GETROUTE,
+        MessageFlags::REQUEST | MessageFlags::DUMP,
+        8,
+    )
+    .unwrap();
+    b.header(&linux_unsafe::rtmsg {
+        rtm_family: AF_INET as u8,
+        ..Default::default()
+    })
+    .unwrap();
+    let mut found = false;
+    sock.netlink_request(b.as_bytes(), &mut buf, |msg| {
+        let r = msg.route().unwrap();
+        if r.destination_len() == 16 && r.table() == linux_unsafe::RT_TABLE_MAIN as u32 {
+            match r.destination() {
+                Some(IpAddr::V4(d)) => assert_eq!(d.as_octets(), dst.as_octets()),
+                other => panic!("unexpected destination {:?}", other),
+            }
+            assert_eq!(r.output_interface(), Some(lo_index as u32));
+            assert_eq!(r.priority(), Some(42));
+            assert_eq!(r.protocol(), linux_unsafe::RTPROT_STATIC);
+            assert!(r.gateway().is_none());
+            found = true;
+        }
+        Ok(())
+    })
+    .unwrap();
+    assert!(found, "added route not found");
+
+    // The raw message decoding works on any buffer.
+    let mut b =
+        MessageBuilder::new(&mut req, linux_unsafe::RTM_NEWLINK, MessageFlags::NONE, 9).unwrap();
+    b.header(&linux_unsafe::ifinfomsg::default()).unwrap();
+    b.nested(linux_unsafe::IFLA_LINKINFO, |b| b.attr_u8(1, 7))
+        .unwrap();
+    assert_eq!(b.seq(), 9);
+    let mut msgs = Messages::new(b.as_bytes());
+    let msg = msgs.next().unwrap().unwrap();
+    assert!(msgs.next().is_none());
+    assert_eq!(msg.result(), None);
+    let info = msg
+        .link()
+        .unwrap()
+        .attrs()
+        .get(linux_unsafe::IFLA_LINKINFO)
+        .unwrap();
+    assert!(info.is_nested());
+    let inner = info.nested().collect::<Vec<_>>();
+    assert_eq!(inner.len(), 1);
+    assert_eq!((inner[0].typ(), inner[0].as_u8()), (1, Some(7)));
+    assert_eq!(
+        Messages::new(&b.as_bytes()[..10])
+            .next()
+            .map(|r| r.map(|_| ())),
+        Some(Err(crate::result::EPROTO))
+    );
+}
@@ -0,0 +1,273 @@
+use core::time::Duration;
+
+use crate::result::Result;
+
+/// Identifies one of the kernel's clocks.
+///
+/// The associated constants represent the clocks that are always available.
+/// Use [`ClockId::for_posix_clock`] to refer to a dynamic clock provided by
+/// a device, such as a PTP hardware clock.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct ClockId(linux_unsafe::clockid_t);
+
+impl ClockId {
+    /// System-wide wall-clock time, which can jump when the time is changed.
+    pub const REALTIME: Self = Self(linux_unsafe::CLOCK_REALTIME);
+    /// Monotonic time since an unspecified starting point, which does not
+    /// advance while the system is suspended.
+    pub const MONOTONIC: Self = Self(linux_unsafe::CLOCK_MONOTONIC);
+    /// CPU time consumed by all threads in the calling process.
+    pub const PROCESS_CPUTIME: Self = Self(linux_unsafe::CLOCK_PROCESS_CPUTIME_ID);
+    /// CPU time consumed by the calling thread.
+    pub const THREAD_CPUTIME: Self = Self(linux_unsafe::CLOCK_THREAD_CPUTIME_ID);
+    /// Like [`Self::MONOTONIC`], but not subject to frequency adjustments
+    /// made by NTP.
+    pub const MONOTONIC_RAW: Self = Self(linux_unsafe::CLOCK_MONOTONIC_RAW);
+    /// A faster but less precise version of [`Self::REALTIME`].
+    pub const REALTIME_COARSE: Self = Self(linux_unsafe::CLOCK_REALTIME_COARSE);
+    /// A faster but less precise version of [`Self::MONOTONIC`].
+    pub const MONOTONIC_COARSE: Self = Self(linux_unsafe::CLOCK_MONOTONIC_COARSE);
+    /// Like [`Self::MONOTONIC`], but also advances while the system is
+    /// suspended.
+    pub const BOOTTIME: Self = Self(linux_unsafe::CLOCK_BOOTTIME);
+    /// International Atomic Time, which is like [`Self::REALTIME`] but
+    /// without leap seconds.
+    pub const TAI: Self = Self(linux_unsafe::CLOCK_TAI);
+
+    /// Wrap a raw clock id value.
+    #[inline(always)]
+    pub const fn from_raw(raw: linux_unsafe::clockid_t) -> Self {
+        Self(raw)
+    }
+
+    /// Returns the clock id for the dynamic clock provided by the given
+    /// posix clock device, such as `/dev/ptp0`.
+    ///
+    /// The result is valid only for as long as the file remains open.
+    #[inline(always)]
+    pub fn for_posix_clock<Device>(file: &crate::File<Device>) -> Self {
+        Self(linux_unsafe::fd_to_clockid(file.fd))
+    }
+
+    /// Returns the raw clock id value, for use with the functions in
+    /// [`linux_unsafe`].
+    #[inline(always)]
+    pub const fn raw(self) -> linux_unsafe::clockid_t {
+        self.0
+    }
+
+    /// Returns the current time of the clock, as a duration since the
+    /// clock's epoch.
+    ///
+    /// This uses the vDSO where possible, to avoid the overhead of a system
+    /// call.
+    #[inline]
+    pub fn now(self) -> Result<Duration> {
+        let mut ts = ZERO_TIMESPEC;
+        let result = unsafe { linux_unsafe::vdso::clock_gettime(self.0, &mut ts) };
+        result
+            .map_err(|e| e.into())
+            .and_then(|_| duration_from_timespec(&ts))
+    }
+
+    /// Returns the resolution of the clock.
+    #[inline]
+    pub fn resolution(self) -> Result<Duration> {
+        let mut ts = ZERO_TIMESPEC;
+        let result = unsafe { linux_unsafe::clock_getres(self.0, &mut ts) };
+        result
+            .map_err(|e| e.into())
+            .and_then(|_| duration_from_timespec(&ts))
+    }
+
+    /// Sets the current time of the clock, as a duration since the clock's
+    /// epoch. Most clocks cannot be set, and those that can require special
+    /// privileges.
+    #[inline]
+    pub fn set(self, time: Duration) -> Result<()> {
+        let ts = timespec_from_duration(time)?;
+        let result = unsafe { linux_unsafe::clock_settime(self.0, &ts) };
+        result.map(|_| ()).map_err(|e| e.into())
+    }
+
+    /// Sleeps for the given duration as measured by the clock.
+    ///
+    /// If the sleep is interrupted by a signal handler then this returns
+    /// [`EINTR`](crate::result::EINTR).
+    #[inline]
+    pub fn sleep(self, duration: Duration) -> Result<()> {
+        let ts = timespec_from_duration(duration)?;
+        let result =
+            unsafe { linux_unsafe::clock_nanosleep(self.0, 0, &ts, core::ptr::null_mut()) };
+        result.map(|_| ()).map_err(|e| e.into())
+    }
+
+    /// Sleeps until the clock reaches the given time, given as a duration
+    /// since the clock's epoch.
+    ///
+    /// If the sleep is interrupted by a signal handler then this returns
+    /// [`EINTR`](crate::result::EINTR).
+    #[inline]
+    pub fn sleep_until(self, time: Duration) -> Result<()> {
+        let ts = timespec_from_duration(time)?;
+        let result = unsafe {
+            linux_unsafe::clock_nanosleep(
+                self.0,
+                linux_unsafe::TIMER_ABSTIME,
+                &ts,
+                core::ptr::null_mut(),
+            )
+        };
+        result.map(|_| ()).map_err(|e| e.into())
+    }
+}
+
+/// A measurement of the monotonic clock, [`ClockId::MONOTONIC`].
+///
+/// This is similar to the standard library's `Instant`, but available in
+/// `no_std` environments.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Instant(Duration);
+
+impl Instant {
+    /// Returns the current time of the monotonic clock.
+    #[inline]
+    pub fn now() -> Self {
+        // The monotonic clock is always available and its values are always
+        // in range, so this can fail only if the kernel misbehaves.
+        Self(
+            ClockId::MONOTONIC
+                .now()
+                .expect("failed to read monotonic clock"),
+        )
+    }
+
+    /// Returns the amount of time elapsed from `earlier` to `self`, or zero
+    /// if `earlier` is later than `self`.
+    #[inline]
+    pub fn duration_since(&self, earlier: Self) -> Duration {
+        self.0.saturating_sub(earlier.0)
+    }
+
+    /// Returns the amount of time elapsed since `self`.
+    #[inline]
+    pub fn elapsed(&self) -> Duration {
+        Self::now().duration_since(*self)
+    }
+
+    /// Returns the instant that is `duration` after `self`, or `None` if
+    /// the result cannot be represented.
+    #[inline]
+    pub fn checked_add(&self, duration: Duration) -> Option<Self> {
+        self.0.checked_add(duration).map(Self)
+    }
+
+    /// Returns the instant that is `duration` before `self`, or `None` if
+    /// the result would be before the clock's epoch.
+    #[inline]
+    pub fn checked_sub(&self, duration: Duration) -> Option<Self> {
+        self.0.checked_sub(duration).map(Self)
+    }
+
+    /// Returns the time of the instant as a duration since the monotonic
+    /// clock's unspecified epoch, for use with [`ClockId::sleep_until`] and
+    /// similar.
+    #[inline(always)]
+    pub const fn since_clock_epoch(&self) -> Duration {
+        self.0
+    }
+
+    /// Sleeps until the monotonic clock reaches this instant.
+    #[inline]
+    pub fn sleep_until(&self) -> Result<()> {
+        ClockId::MONOTONIC.sleep_until(self.0)
+    }
+}
+
+impl core::ops::Add<Duration> for Instant {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Duration) -> Self {
+        self.checked_add(rhs)
+            .expect("overflow when adding duration to instant")
+    }
+}
+
+impl core::ops::Sub<Duration> for Instant {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Duration) -> Self {
+        self.checked_sub(rhs)
+            .expect("overflow when subtracting duration from instant")
+    }
+}
+
+impl core::ops::Sub<Instant> for Instant {
+    type Output = Duration;
+
+    #[inline]
+    fn sub(self, rhs: Instant) -> Duration {
+        self.duration_since(rhs)
+    }
+}
+
+/// Sleeps for the given duration.
+///
+/// If the sleep is interrupted by a signal handler then this returns
+/// [`EINTR`](crate::result::EINTR).
+#[inline]
+pub fn sleep(duration: Duration) -> Result<()> {
+    ClockId::MONOTONIC.sleep(duration)
+}
+
+/// Converts a duration to the kernel's `timespec` representation.
+///
+/// Returns [`EINVAL`](crate::result::EINVAL) if the duration is too long to represent on
+/// the current platform.
+#[inline]
+pub fn timespec_from_duration(duration: Duration) -> Result<linux_unsafe::timespec> {
+    let tv_sec: linux_unsafe::long = match duration.as_secs().try_into() {
+        Ok(v) => v,
+        Err(_) => return Err(crate::result::EINVAL),
+    };
+    Ok(linux_unsafe::timespec {
+        tv_sec,
+        tv_nsec: duration.subsec_nanos() as linux_unsafe::long,
+    })
+}
+
+/// Converts a duration to the kernel's `__kernel_timespec` representation,
+/// which uses 64-bit fields on all platforms.
+///
+/// Returns [`EINVAL`](crate::result::EINVAL) if the duration is too long to represent.
+#[inline]
+pub fn kernel_timespec_from_duration(duration: Duration) -> Result<linux_unsafe::kernel_timespec> {
+    let tv_sec: i64 = match duration.as_secs().try_into() {
+        Ok(v) => v,
+        Err(_) => return Err(crate::result::EINVAL),
+    };
+    Ok(linux_unsafe::kernel_timespec {
+        tv_sec,
+        tv_nsec: duration.subsec_nanos() as linux_unsafe::longlong,
+    })
+}
+
+/// Converts the kernel's `timespec` representation to a duration.
+///
+/// Returns [`EINVAL`](crate::result::EINVAL) if the timespec is negative or its nanoseconds
+/// field is out of range.
+#[inline]
+pub fn duration_from_timespec(ts: &linux_unsafe::timespec) -> Result<Duration> {
+    if ts.tv_sec < 0 || ts.tv_nsec < 0 || ts.tv_nsec >= 1_000_000_000 {
+        return Err(crate::result::EINVAL);
+    }
+    Ok(Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32))
+}
+
+const ZERO_TIMESPEC: linux_unsafe::timespec = linux_unsafe::timespec {
+    tv_sec: 0,
+    tv_nsec: 0,
+};
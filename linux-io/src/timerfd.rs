@@ -0,0 +1,165 @@
+//! A timerfd is a timer that reports its expirations through a file
+//! descriptor, which becomes readable whenever the timer has expired at
+//! least once since it was last read. This allows timers to be handled in
+//! the same event loop as other I/O, using [`crate::poll`] or
+//! [`crate::epoll`].
+
+use core::time::Duration;
+
+use crate::fd::ioctl::IoDevice;
+use crate::result::Result;
+use crate::time::{duration_from_timespec, timespec_from_duration, ClockId};
+use crate::File;
+
+/// A marker type for [`File`] objects that represent timerfd instances.
+#[derive(Clone, Copy, Debug)]
+pub struct TimerFdDevice;
+
+impl IoDevice for TimerFdDevice {}
+
+/// A timerfd instance.
+pub type TimerFd = File<TimerFdDevice>;
+
+impl File<TimerFdDevice> {
+    /// Create a new timer that measures time using the given clock.
+    ///
+    /// The timer is initially disarmed. Only [`ClockId::REALTIME`],
+    /// [`ClockId::MONOTONIC`], [`ClockId::BOOTTIME`], and the alarm variants
+    /// of the realtime and boot time clocks are supported.
+    ///
+    /// Most callers should include [`TimerFdFlags::CLOEXEC`] in `flags`.
+    #[inline]
+    pub fn new(clock: ClockId, flags: TimerFdFlags) -> Result<Self> {
+        let result = unsafe { linux_unsafe::timerfd_create(clock.raw(), flags.raw()) };
+        result
+            .map(|fd| unsafe { File::from_raw_fd(fd) })
+            .map_err(|e| e.into())
+    }
+
+    /// Arm the timer so that it first expires after `initial` and then
+    /// repeatedly after each `interval`, returning the previous setting.
+    ///
+    /// If `flags` includes [`TimerSetFlags::ABSTIME`] then `initial` is
+    /// instead an absolute time on the timer's clock, as a duration since
+    /// the clock's epoch. An `interval` of zero makes the timer expire only
+    /// once, while an `initial` of zero disarms the timer.
+    #[inline]
+    pub fn set(
+        &self,
+        initial: Duration,
+        interval: Duration,
+        flags: TimerSetFlags,
+    ) -> Result<TimerSetting> {
+        let new = linux_unsafe::itimerspec {
+            it_interval: timespec_from_duration(interval)?,
+            it_value: timespec_from_duration(initial)?,
+        };
+        let mut old = ZERO_ITIMERSPEC;
+        let result = unsafe {
+            linux_unsafe::timerfd_settime(
+                self.fd,
+                flags.raw(),
+                &new as *const _,
+                &mut old as *mut _,
+            )
+        };
+        result
+            .map_err(|e| e.into())
+            .and_then(|_| TimerSetting::from_raw(&old))
+    }
+
+    /// Disarm the timer, returning the previous setting.
+    #[inline]
+    pub fn disarm(&self) -> Result<TimerSetting> {
+        self.set(Duration::ZERO, Duration::ZERO, TimerSetFlags::NONE)
+    }
+
+    /// Returns the current setting of the timer.
+    ///
+    /// The initial value in the result is always relative to the current
+    /// time, even if the timer was armed with [`TimerSetFlags::ABSTIME`].
+    #[inline]
+    pub fn get(&self) -> Result<TimerSetting> {
+        let mut curr = ZERO_ITIMERSPEC;
+        let result = unsafe { linux_unsafe::timerfd_gettime(self.fd, &mut curr as *mut _) };
+        result
+            .map_err(|e| e.into())
+            .and_then(|_| TimerSetting::from_raw(&curr))
+    }
+
+    /// Wait until the timer has expired at least once since it was last
+    /// read, and then return the number of expirations.
+    ///
+    /// If the timer is in non-blocking mode and has not expired then this
+    /// fails with [`EAGAIN`](crate::result::EAGAIN). If the timer was armed
+    /// with [`TimerSetFlags::CANCEL_ON_SET`] and its clock was changed
+    /// discontinuously then this fails with
+    /// [`ECANCELED`](crate::result::ECANCELED).
+    #[inline]
+    pub fn read_expirations(&self) -> Result<u64> {
+        let mut buf = [0_u8; 8];
+        self.read(&mut buf)?;
+        Ok(u64::from_ne_bytes(buf))
+    }
+}
+
+/// The setting of a timer, as returned by [`TimerFd::get`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimerSetting {
+    /// The time remaining until the next expiration, or zero if the timer
+    /// is disarmed.
+    pub remaining: Duration,
+    /// The interval between subsequent expirations, or zero if the timer
+    /// expires only once.
+    pub interval: Duration,
+}
+
+impl TimerSetting {
+    #[inline]
+    fn from_raw(raw: &linux_unsafe::itimerspec) -> Result<Self> {
+        Ok(Self {
+            remaining: duration_from_timespec(&raw.it_value)?,
+            interval: duration_from_timespec(&raw.it_interval)?,
+        })
+    }
+}
+
+const ZERO_ITIMERSPEC: linux_unsafe::itimerspec = linux_unsafe::itimerspec {
+    it_interval: linux_unsafe::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    },
+    it_value: linux_unsafe::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    },
+};
+
+flags_type! {
+    /// Flags for [`TimerFd::new`].
+    pub struct TimerFdFlags(linux_unsafe::int);
+
+    /// No flags.
+    const NONE = 0;
+    /// Set the close-on-exec flag on the new file descriptor.
+    const CLOEXEC = linux_unsafe::TFD_CLOEXEC;
+    /// Put the new file descriptor in non-blocking mode.
+    const NONBLOCK = linux_unsafe::TFD_NONBLOCK;
+}
+
+flags_type! {
+    /// Flags for [`TimerFd::set`].
+    pub struct TimerSetFlags(linux_unsafe::int);
+
+    /// No flags, so the initial expiration time is relative to the current
+    /// time.
+    const NONE = 0;
+    /// Treat the initial expiration time as an absolute time on the timer's
+    /// clock.
+    const ABSTIME = linux_unsafe::TFD_TIMER_ABSTIME;
+    /// When used with [`Self::ABSTIME`] on a timer using one of the
+    /// realtime clocks, make reads fail with
+    /// [`ECANCELED`](crate::result::ECANCELED) if the clock is changed
+    /// discontinuously, such as by setting the system time.
+    const CANCEL_ON_SET = linux_unsafe::TFD_TIMER_CANCEL_ON_SET;
+}
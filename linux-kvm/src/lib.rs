@@ -222,8 +222,8 @@ impl<'a> VirtualCpuRunner<'a> {
                 0,
                 mmap_size,
                 core::ptr::null_mut(),
-                0x1 | 0x2, // PROT_READ | PROT_WRITE
-                0x1,       // MAP_SHARED
+                linux_unsafe::PROT_READ | linux_unsafe::PROT_WRITE,
+                linux_unsafe::MAP_SHARED,
             )
         }? as *mut raw::kvm_run;
 
@@ -298,9 +298,9 @@ impl MemoryRegion {
             linux_unsafe::mmap(
                 core::ptr::null_mut(),
                 length,
-                0x1 | 0x2,  // PROT_READ | PROT_WRITE
-                0x1 | 0x20, // MAP_SHARED | MAP_ANONYMOUS
-                -1,         // no fd, because MAP_ANONYMOUS
+                linux_unsafe::PROT_READ | linux_unsafe::PROT_WRITE,
+                linux_unsafe::MAP_SHARED | linux_unsafe::MAP_ANONYMOUS,
+                -1, // no fd, because MAP_ANONYMOUS
                 0,
             )
         }?;
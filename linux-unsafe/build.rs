@@ -57,6 +57,27 @@ fn main() {
         out_file.push(format!("errnos_{}.rs", &arch));
         generate_errno_constants_rs(out_file, &raw_vars).unwrap();
     }
+    {
+        let mut header_filename = std::path::PathBuf::from_str("data").unwrap();
+        header_filename.push(&arch);
+        header_filename.push("signal.h");
+        println!(
+            "cargo:rerun-if-changed={}",
+            header_filename.to_string_lossy()
+        );
+        let raw_vars = match parse_header(&header_filename) {
+            Ok(v) => v,
+            Err(err) => {
+                eprintln!("Error reading {:?}: {}.\n", &header_filename, err);
+                eprintln!("This crate does not currently support {}.", &arch);
+                std::process::exit(1);
+            }
+        };
+
+        let mut out_file = std::path::PathBuf::from_str(&env::var("OUT_DIR").unwrap()).unwrap();
+        out_file.push(format!("signals_{}.rs", &arch));
+        generate_signal_constants_rs(out_file, &raw_vars).unwrap();
+    }
 }
 
 fn parse_header(filename: impl AsRef<Path>) -> std::io::Result<BTreeMap<String, u64>> {
@@ -149,6 +170,28 @@ fn generate_errno_constants_rs(
     Ok(())
 }
 
+fn generate_signal_constants_rs(
+    filename: impl AsRef<Path>,
+    raw_vars: &BTreeMap<String, u64>,
+) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut f = File::create(filename)?;
+    let nsig = raw_vars.get("_NSIG").expect("signal.h must define _NSIG");
+    writeln!(
+        f,
+        "/// The number of signals supported by the kernel on this platform."
+    )?;
+    writeln!(f, "pub const _NSIG: usize = {};", nsig)?;
+    for (k, v) in raw_vars.iter() {
+        if k.starts_with("SIG") {
+            writeln!(f, "/// The signal number for `{}` on this platform.", k)?;
+            writeln!(f, "pub const {}: crate::int = {};", k, v)?;
+        }
+    }
+    Ok(())
+}
+
 enum NumOrAlias {
     Num(u64),
     Alias(String),
@@ -0,0 +1 @@
+../generic-errno.h
\ No newline at end of file
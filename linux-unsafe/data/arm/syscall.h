@@ -399,6 +399,7 @@
 #define __NR_landlock_create_ruleset 444
 #define __NR_landlock_add_rule 445
 #define __NR_landlock_restrict_self 446
+#define __NR_fchmodat2 452
 
 #define __ARM_NR_breakpoint 983041
 #define __ARM_NR_cacheflush 983042
@@ -298,4 +298,6 @@
 #define __NR_landlock_create_ruleset 444
 #define __NR_landlock_add_rule 445
 #define __NR_landlock_restrict_self 446
+#define __NR_memfd_secret 447
+#define __NR_fchmodat2 452
 #define __NR_riscv_flush_icache 259
@@ -0,0 +1 @@
+../generic-signal.h
\ No newline at end of file
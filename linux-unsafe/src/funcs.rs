@@ -43,6 +43,17 @@ macro_rules! syscall {
     };
 }
 
+/// Splits a 64-bit file offset into the low and high halves expected by
+/// system calls that take the offset as two separate arguments.
+///
+/// On 64-bit platforms the kernel ignores the high half for these calls,
+/// because the low half is already big enough for the whole offset.
+#[inline(always)]
+fn split_offset(offset: loff_t) -> (ulong, ulong) {
+    let offset = offset as u64;
+    (offset as ulong, (offset >> 32) as ulong)
+}
+
 /// Accept a connection on a socket.
 #[cfg(have_syscall = "accept")]
 #[inline(always)]
@@ -136,6 +147,125 @@ pub unsafe fn chroot(path: *const char) -> Result<int> {
     syscall!(raw::CHROOT, path)
 }
 
+/// Find the resolution (precision) of the specified clock.
+#[cfg(have_syscall = "clock_getres")]
+#[inline(always)]
+pub unsafe fn clock_getres(clockid: clockid_t, res: *mut timespec) -> Result<int> {
+    syscall!(raw::CLOCK_GETRES, clockid, res)
+}
+
+/// Find the resolution (precision) of the specified clock.
+///
+/// On this platform this is actually a wrapper around the `clock_getres_time32` system
+/// call, which uses a 32-bit `time_t` and so cannot represent times after
+/// the year 2038.
+#[cfg(all(
+    have_syscall = "clock_getres_time32",
+    not(have_syscall = "clock_getres")
+))]
+#[inline(always)]
+pub unsafe fn clock_getres(clockid: clockid_t, res: *mut timespec) -> Result<int> {
+    syscall!(raw::CLOCK_GETRES_TIME32, clockid, res)
+}
+
+/// Retrieve the time of the specified clock.
+#[cfg(have_syscall = "clock_gettime")]
+#[inline(always)]
+pub unsafe fn clock_gettime(clockid: clockid_t, tp: *mut timespec) -> Result<int> {
+    syscall!(raw::CLOCK_GETTIME, clockid, tp)
+}
+
+/// Retrieve the time of the specified clock.
+///
+/// On this platform this is actually a wrapper around the `clock_gettime32` system
+/// call, which uses a 32-bit `time_t` and so cannot represent times after
+/// the year 2038.
+#[cfg(all(have_syscall = "clock_gettime32", not(have_syscall = "clock_gettime")))]
+#[inline(always)]
+pub unsafe fn clock_gettime(clockid: clockid_t, tp: *mut timespec) -> Result<int> {
+    syscall!(raw::CLOCK_GETTIME32, clockid, tp)
+}
+
+/// High-resolution sleep with a specifiable clock.
+///
+/// If `flags` includes [`TIMER_ABSTIME`] then `request` is an absolute time
+/// on the given clock, and otherwise it's an interval relative to the
+/// current time. If interrupted by a signal handler during a relative sleep,
+/// the remaining time is written to `remain` unless it's null.
+#[cfg(have_syscall = "clock_nanosleep")]
+#[inline(always)]
+pub unsafe fn clock_nanosleep(
+    clockid: clockid_t,
+    flags: int,
+    request: *const timespec,
+    remain: *mut timespec,
+) -> Result<int> {
+    syscall!(raw::CLOCK_NANOSLEEP, clockid, flags, request, remain)
+}
+
+/// High-resolution sleep with a specifiable clock.
+///
+/// If `flags` includes [`TIMER_ABSTIME`] then `request` is an absolute time
+/// on the given clock, and otherwise it's an interval relative to the
+/// current time. If interrupted by a signal handler during a relative sleep,
+/// the remaining time is written to `remain` unless it's null.
+///
+/// On this platform this is actually a wrapper around the `clock_nanosleep_time32` system
+/// call, which uses a 32-bit `time_t` and so cannot represent times after
+/// the year 2038.
+#[cfg(all(
+    have_syscall = "clock_nanosleep_time32",
+    not(have_syscall = "clock_nanosleep")
+))]
+#[inline(always)]
+pub unsafe fn clock_nanosleep(
+    clockid: clockid_t,
+    flags: int,
+    request: *const timespec,
+    remain: *mut timespec,
+) -> Result<int> {
+    syscall!(raw::CLOCK_NANOSLEEP_TIME32, clockid, flags, request, remain)
+}
+
+/// Set the time of the specified clock.
+#[cfg(have_syscall = "clock_settime")]
+#[inline(always)]
+pub unsafe fn clock_settime(clockid: clockid_t, tp: *const timespec) -> Result<int> {
+    syscall!(raw::CLOCK_SETTIME, clockid, tp)
+}
+
+/// Set the time of the specified clock.
+///
+/// On this platform this is actually a wrapper around the `clock_settime32` system
+/// call, which uses a 32-bit `time_t` and so cannot represent times after
+/// the year 2038.
+#[cfg(all(have_syscall = "clock_settime32", not(have_syscall = "clock_settime")))]
+#[inline(always)]
+pub unsafe fn clock_settime(clockid: clockid_t, tp: *const timespec) -> Result<int> {
+    syscall!(raw::CLOCK_SETTIME32, clockid, tp)
+}
+
+/// Create a child process, with detailed control over which resources it
+/// shares with the parent.
+///
+/// `size` must be the size of the object that `cl_args` points to, which
+/// is typically `size_of::<clone_args>()`.
+///
+/// Returns the child's thread ID in the parent, and zero in the child.
+///
+/// **Warning:** If `cl_args` specifies a new stack then the child begins
+/// executing on that stack immediately after the system call returns, and so
+/// the calling Rust function cannot safely return in the child. When no new
+/// stack is specified the child runs on a copy-on-write duplicate of the
+/// parent's stack, similar to `fork`. In a multithreaded program the child
+/// contains only a copy of the calling thread, so it must avoid any operation
+/// that might depend on state owned by other threads, such as locks.
+#[cfg(have_syscall = "clone3")]
+#[inline(always)]
+pub unsafe fn clone3(cl_args: *mut clone_args, size: size_t) -> Result<pid_t> {
+    syscall!(raw::CLONE3, cl_args, size)
+}
+
 /// Close a file.
 #[cfg(have_syscall = "close")]
 #[inline(always)]
@@ -157,6 +287,34 @@ pub unsafe fn connect(sockfd: int, addr: *const sockaddr, addrlen: socklen_t) ->
     syscall!(raw::CONNECT, sockfd, addr as *const void, addrlen)
 }
 
+/// Copy a range of data from one file to another without passing it
+/// through userspace.
+///
+/// If `off_in` or `off_out` is null then the corresponding file's current
+/// position is used and updated. Otherwise the offset it points to is used
+/// and updated, and the file's current position is unchanged. `flags` is
+/// currently unused and must be zero.
+#[cfg(have_syscall = "copy_file_range")]
+#[inline(always)]
+pub unsafe fn copy_file_range(
+    fd_in: int,
+    off_in: *mut loff_t,
+    fd_out: int,
+    off_out: *mut loff_t,
+    len: size_t,
+    flags: uint,
+) -> Result<ssize_t> {
+    syscall!(
+        raw::COPY_FILE_RANGE,
+        fd_in,
+        off_in,
+        fd_out,
+        off_out,
+        len,
+        flags
+    )
+}
+
 /// Create a file.
 #[cfg(have_syscall = "creat")]
 #[inline(always)]
@@ -206,6 +364,60 @@ pub unsafe fn epoll_ctl(epfd: int, op: int, fd: int, event: *const epoll_event)
     syscall!(raw::EPOLL_CTL, epfd, op, fd, event)
 }
 
+/// Wait for an I/O event on an epoll file descriptor, while temporarily
+/// replacing the calling thread's signal mask.
+///
+/// Set `sigmask` to null to leave the signal mask unchanged, making this
+/// equivalent to `epoll_wait`. `sigsetsize` is the size of the signal set
+/// type in bytes.
+#[cfg(have_syscall = "epoll_pwait")]
+#[inline(always)]
+pub unsafe fn epoll_pwait(
+    epfd: int,
+    events: *mut epoll_event,
+    maxevents: int,
+    timeout: int,
+    sigmask: *const sigset_t,
+    sigsetsize: size_t,
+) -> Result<int> {
+    syscall!(
+        raw::EPOLL_PWAIT,
+        epfd,
+        events,
+        maxevents,
+        timeout,
+        sigmask,
+        sigsetsize
+    )
+}
+
+/// Wait for an I/O event on an epoll file descriptor with a timeout of
+/// nanosecond precision, while temporarily replacing the calling thread's
+/// signal mask.
+///
+/// Set `timeout` to null to wait indefinitely, and `sigmask` to null to
+/// leave the signal mask unchanged.
+#[cfg(have_syscall = "epoll_pwait2")]
+#[inline(always)]
+pub unsafe fn epoll_pwait2(
+    epfd: int,
+    events: *mut epoll_event,
+    maxevents: int,
+    timeout: *const kernel_timespec,
+    sigmask: *const sigset_t,
+    sigsetsize: size_t,
+) -> Result<int> {
+    syscall!(
+        raw::EPOLL_PWAIT2,
+        epfd,
+        events,
+        maxevents,
+        timeout,
+        sigmask,
+        sigsetsize
+    )
+}
+
 /// Wait for an I/O event on an epoll file descriptor.
 #[cfg(have_syscall = "epoll_wait")]
 #[inline(always)]
@@ -232,6 +444,43 @@ pub unsafe fn eventfd2(initval: uint, flags: int) -> Result<int> {
     syscall!(raw::EVENTFD2, initval, flags)
 }
 
+/// Execute a program.
+///
+/// `argv` and `envp` must both be pointers to null-terminated arrays of
+/// pointers to null-terminated strings.
+///
+/// This function returns only if it fails. On success the calling process
+/// is replaced by the new program.
+#[cfg(have_syscall = "execve")]
+#[inline(always)]
+pub unsafe fn execve(
+    pathname: *const char,
+    argv: *const *const char,
+    envp: *const *const char,
+) -> Result<int> {
+    syscall!(raw::EXECVE, pathname, argv, envp)
+}
+
+/// Execute a program relative to a directory file descriptor.
+///
+/// `argv` and `envp` must both be pointers to null-terminated arrays of
+/// pointers to null-terminated strings. Set `flags` to `AT_EMPTY_PATH` and
+/// `pathname` to an empty string to execute the file that `dirfd` refers to.
+///
+/// This function returns only if it fails. On success the calling process
+/// is replaced by the new program.
+#[cfg(have_syscall = "execveat")]
+#[inline(always)]
+pub unsafe fn execveat(
+    dirfd: int,
+    pathname: *const char,
+    argv: *const *const char,
+    envp: *const *const char,
+    flags: int,
+) -> Result<int> {
+    syscall!(raw::EXECVEAT, dirfd, pathname, argv, envp, flags)
+}
+
 /// Immediately terminate the current thread, without giving Rust or libc
 /// any opportunity to run destructors or other cleanup code.
 #[cfg(have_syscall = "exit")]
@@ -265,6 +514,56 @@ pub unsafe fn faccessat2(dirfd: int, pathname: *const char, mode: int, flags: in
     syscall!(raw::FACCESSAT2, dirfd, pathname, mode, flags)
 }
 
+/// Create and initialize a fanotify group, returning a file descriptor for
+/// its event queue.
+///
+/// `event_f_flags` are the file status flags to use for the file
+/// descriptors that the kernel creates for each event.
+#[cfg(have_syscall = "fanotify_init")]
+#[inline(always)]
+pub unsafe fn fanotify_init(flags: uint, event_f_flags: uint) -> Result<int> {
+    syscall!(raw::FANOTIFY_INIT, flags, event_f_flags)
+}
+
+/// Add, remove, or modify a mark on a filesystem object for a fanotify group.
+///
+/// On 32-bit platforms this splits the 64-bit mask across two arguments
+/// as the kernel's calling convention for this system call requires.
+#[cfg(have_syscall = "fanotify_mark")]
+#[inline(always)]
+pub unsafe fn fanotify_mark(
+    fanotify_fd: int,
+    flags: uint,
+    mask: u64,
+    dirfd: int,
+    pathname: *const char,
+) -> Result<int> {
+    #[cfg(target_pointer_width = "64")]
+    {
+        syscall!(
+            raw::FANOTIFY_MARK,
+            fanotify_fd,
+            flags,
+            mask,
+            dirfd,
+            pathname
+        )
+    }
+    #[cfg(target_pointer_width = "32")]
+    {
+        let (low, high) = split_offset(mask as loff_t);
+        syscall!(
+            raw::FANOTIFY_MARK,
+            fanotify_fd,
+            flags,
+            low,
+            high,
+            dirfd,
+            pathname
+        )
+    }
+}
+
 /// Change working directory.
 #[cfg(have_syscall = "fchdir")]
 #[inline(always)]
@@ -286,6 +585,21 @@ pub unsafe fn fchmodat(dirfd: int, pathname: *const char, mode: mode_t) -> Resul
     syscall!(raw::FCHMODAT, dirfd, pathname, mode)
 }
 
+/// Change permissions of a file, with additional flags.
+///
+/// Unlike [`fchmodat`], this supports `AT_SYMLINK_NOFOLLOW` and
+/// `AT_EMPTY_PATH` in `flags`.
+#[cfg(have_syscall = "fchmodat2")]
+#[inline(always)]
+pub unsafe fn fchmodat2(
+    dirfd: int,
+    pathname: *const char,
+    mode: mode_t,
+    flags: int,
+) -> Result<int> {
+    syscall!(raw::FCHMODAT2, dirfd, pathname, mode, flags)
+}
+
 /// Change ownership of a file.
 #[cfg(have_syscall = "fchown")]
 #[inline(always)]
@@ -324,6 +638,24 @@ pub unsafe fn fcntl(fd: int, cmd: int, arg: impl crate::args::AsRawV) -> Result<
     }
 }
 
+/// Manipulate characteristics of a file descriptor, using 64-bit file
+/// offsets for the locking commands on 32-bit platforms.
+///
+/// This is equivalent to [`fcntl`] except that the locking commands take
+/// a pointer to [`flock64`](crate::flock64) rather than
+/// [`flock`](crate::flock), and the open file description locking commands
+/// are available only through this variant on 32-bit platforms. The same
+/// caveats about the type of `arg` apply.
+#[cfg(have_syscall = "fcntl64")]
+#[inline(always)]
+pub unsafe fn fcntl64(fd: int, cmd: int, arg: impl crate::args::AsRawV) -> Result<int> {
+    if arg.raw_is_void() {
+        syscall!(raw::FCNTL64, fd, cmd)
+    } else {
+        syscall!(raw::FCNTL64, fd, cmd, arg)
+    }
+}
+
 /// Synchronize a file's in-core state with storage device.
 #[cfg(have_syscall = "fdatasync")]
 #[inline(always)]
@@ -331,6 +663,16 @@ pub unsafe fn fdatasync(fd: int) -> Result<int> {
     syscall!(raw::FDATASYNC, fd)
 }
 
+/// Apply or remove an advisory lock on an open file.
+///
+/// `operation` is one of `LOCK_SH`, `LOCK_EX`, or `LOCK_UN`, optionally
+/// combined with `LOCK_NB`.
+#[cfg(have_syscall = "flock")]
+#[inline(always)]
+pub unsafe fn flock(fd: int, operation: int) -> Result<int> {
+    syscall!(raw::FLOCK, fd, operation)
+}
+
 /// Synchronize a file's in-core state with storage device.
 #[cfg(have_syscall = "fsync")]
 #[inline(always)]
@@ -552,6 +894,36 @@ pub unsafe fn gettid() -> pid_t {
     raw::syscall0(raw::GETTID) as pid_t
 }
 
+/// Get the current wall-clock time as seconds and microseconds since the
+/// Unix epoch.
+///
+/// The `tz` argument is obsolete and should be set to null.
+///
+/// The [`crate::vdso::gettimeofday`] function can often retrieve the same
+/// information without entering the kernel.
+#[cfg(have_syscall = "gettimeofday")]
+#[inline(always)]
+pub unsafe fn gettimeofday(tv: *mut timeval, tz: *mut timezone) -> Result<int> {
+    syscall!(raw::GETTIMEOFDAY, tv, tz)
+}
+
+/// Get the current wall-clock time as seconds and microseconds since the
+/// Unix epoch.
+///
+/// The `tz` argument is obsolete and should be set to null.
+///
+/// On this platform this is actually a wrapper around the `gettimeofday_time32`
+/// system call, which uses a 32-bit `time_t` and so cannot represent times
+/// after the year 2038.
+#[cfg(all(
+    have_syscall = "gettimeofday_time32",
+    not(have_syscall = "gettimeofday")
+))]
+#[inline(always)]
+pub unsafe fn gettimeofday(tv: *mut timeval, tz: *mut timezone) -> Result<int> {
+    syscall!(raw::GETTIMEOFDAY_TIME32, tv, tz)
+}
+
 /// Get the real user ID of the current process.
 #[cfg(all(have_syscall = "getuid", not(have_syscall = "getuid32")))]
 #[inline(always)]
@@ -742,6 +1114,91 @@ pub unsafe fn lseek(fd: int, offset: off_t, whence: int) -> Result<off_t> {
     syscall!(raw::LSEEK, fd, offset, whence)
 }
 
+/// Give advice about the expected use of a range of memory.
+///
+/// `advice` is one of the `MADV_*` constants.
+#[cfg(have_syscall = "madvise")]
+#[inline(always)]
+pub unsafe fn madvise(addr: *mut void, length: size_t, advice: int) -> Result<int> {
+    syscall!(raw::MADVISE, addr, length, advice)
+}
+
+/// Create an anonymous file that lives only in memory.
+///
+/// `flags` is a combination of the `MFD_*` flags.
+#[cfg(have_syscall = "memfd_create")]
+#[inline(always)]
+pub unsafe fn memfd_create(name: *const char, flags: uint) -> Result<int> {
+    syscall!(raw::MEMFD_CREATE, name, flags)
+}
+
+/// Create an anonymous file whose memory is inaccessible to the kernel and
+/// to all other processes, once mapped using [`mmap`].
+///
+/// The only supported flag is [`O_CLOEXEC`]. The kernel rejects this call
+/// unless it was booted with `secretmem.enable=1`.
+#[cfg(have_syscall = "memfd_secret")]
+#[inline(always)]
+pub unsafe fn memfd_secret(flags: uint) -> Result<int> {
+    syscall!(raw::MEMFD_SECRET, flags)
+}
+
+/// Determine which pages of a mapping are resident in memory.
+///
+/// `vec` must point to an array with one byte for each page in the range,
+/// and the least significant bit of each byte will be set if the
+/// corresponding page is resident.
+#[cfg(have_syscall = "mincore")]
+#[inline(always)]
+pub unsafe fn mincore(addr: *mut void, length: size_t, vec: *mut uchar) -> Result<int> {
+    syscall!(raw::MINCORE, addr, length, vec)
+}
+
+/// Create a directory relative to a directory file descriptor.
+#[cfg(have_syscall = "mkdirat")]
+#[inline(always)]
+pub unsafe fn mkdirat(dirfd: int, pathname: *const char, mode: mode_t) -> Result<int> {
+    syscall!(raw::MKDIRAT, dirfd, pathname, mode)
+}
+
+/// Create a filesystem node, such as a device special file or a named pipe,
+/// relative to a directory file descriptor.
+///
+/// `mode` must include one of the `S_IF*` file type constants. `dev` is
+/// used only for device special files; use [`makedev`] to construct it.
+#[cfg(have_syscall = "mknodat")]
+#[inline(always)]
+pub unsafe fn mknodat(dirfd: int, pathname: *const char, mode: mode_t, dev: dev_t) -> Result<int> {
+    syscall!(raw::MKNODAT, dirfd, pathname, mode, dev)
+}
+
+/// Lock a range of memory so that it cannot be paged out.
+#[cfg(have_syscall = "mlock")]
+#[inline(always)]
+pub unsafe fn mlock(addr: *const void, length: size_t) -> Result<int> {
+    syscall!(raw::MLOCK, addr, length)
+}
+
+/// Lock a range of memory so that it cannot be paged out, with additional
+/// flags.
+///
+/// `flags` can be either zero or [`MLOCK_ONFAULT`].
+#[cfg(have_syscall = "mlock2")]
+#[inline(always)]
+pub unsafe fn mlock2(addr: *const void, length: size_t, flags: uint) -> Result<int> {
+    syscall!(raw::MLOCK2, addr, length, flags)
+}
+
+/// Lock all of the memory of the calling process so that it cannot be
+/// paged out.
+///
+/// `flags` is a combination of the `MCL_*` flags.
+#[cfg(have_syscall = "mlockall")]
+#[inline(always)]
+pub unsafe fn mlockall(flags: int) -> Result<int> {
+    syscall!(raw::MLOCKALL, flags)
+}
+
 /// Map a file or device into memory.
 #[cfg(all(have_syscall = "mmap", not(have_syscall = "mmap2")))]
 #[inline(always)]
@@ -777,6 +1234,15 @@ pub unsafe fn mmap(
     syscall!(raw::MMAP2, addr, length, prot, flags, fd, offset / 4096)
 }
 
+/// Change the access protections of a range of memory.
+///
+/// `prot` is a combination of the `PROT_*` flags.
+#[cfg(have_syscall = "mprotect")]
+#[inline(always)]
+pub unsafe fn mprotect(addr: *mut void, length: size_t, prot: int) -> Result<int> {
+    syscall!(raw::MPROTECT, addr, length, prot)
+}
+
 /// Remove a mapping previously created with [`mmap`].
 #[cfg(have_syscall = "munmap")]
 #[inline(always)]
@@ -807,6 +1273,39 @@ pub unsafe fn mremap(
     )
 }
 
+/// Synchronize a file-backed mapping with the underlying file.
+///
+/// `flags` is a combination of the `MS_*` flags.
+#[cfg(have_syscall = "msync")]
+#[inline(always)]
+pub unsafe fn msync(addr: *mut void, length: size_t, flags: int) -> Result<int> {
+    syscall!(raw::MSYNC, addr, length, flags)
+}
+
+/// Unlock a range of memory previously locked with [`mlock`] or [`mlock2`].
+#[cfg(have_syscall = "munlock")]
+#[inline(always)]
+pub unsafe fn munlock(addr: *const void, length: size_t) -> Result<int> {
+    syscall!(raw::MUNLOCK, addr, length)
+}
+
+/// Unlock all of the memory of the calling process.
+#[cfg(have_syscall = "munlockall")]
+#[inline(always)]
+pub unsafe fn munlockall() -> Result<int> {
+    syscall!(raw::MUNLOCKALL)
+}
+
+/// High-resolution sleep.
+///
+/// If interrupted by a signal handler, the remaining time is written to
+/// `rem` unless it's null.
+#[cfg(have_syscall = "nanosleep")]
+#[inline(always)]
+pub unsafe fn nanosleep(req: *const timespec, rem: *mut timespec) -> Result<int> {
+    syscall!(raw::NANOSLEEP, req, rem)
+}
+
 /// Pause the current process until a signal is delivered.
 #[cfg(have_syscall = "pause")]
 #[inline(always)]
@@ -839,6 +1338,21 @@ pub unsafe fn pidfd_open(pid: pid_t, flags: uint) -> Result<int> {
     syscall!(raw::PIDFD_OPEN, pid, flags)
 }
 
+/// Send a signal to a process represented by a file descriptor.
+///
+/// Set `info` to null to send a signal with the same information that
+/// [`kill`] would provide.
+#[cfg(have_syscall = "pidfd_send_signal")]
+#[inline(always)]
+pub unsafe fn pidfd_send_signal(
+    pidfd: int,
+    sig: int,
+    info: *mut siginfo_t,
+    flags: uint,
+) -> Result<int> {
+    syscall!(raw::PIDFD_SEND_SIGNAL, pidfd, sig, info, flags)
+}
+
 /// Create pipe.
 #[cfg(have_syscall = "pipe")]
 #[inline(always)]
@@ -868,6 +1382,9 @@ pub unsafe fn poll(fds: *mut pollfd, nfds: nfds_t, timeout: int) -> Result<int>
 }
 
 /// Wait for events on one or more file descriptors while also awaiting signals.
+///
+/// `sigsetsize` must be the size of [`sigset_t`] in bytes. It's ignored by
+/// the kernel if `sigmask` is null.
 #[cfg(have_syscall = "ppoll")]
 #[inline(always)]
 pub unsafe fn ppoll(
@@ -875,8 +1392,9 @@ pub unsafe fn ppoll(
     nfds: nfds_t,
     tmo_p: *const timespec,
     sigmask: *const sigset_t,
+    sigsetsize: size_t,
 ) -> Result<int> {
-    syscall!(raw::PPOLL, fds, nfds, tmo_p, sigmask)
+    syscall!(raw::PPOLL, fds, nfds, tmo_p, sigmask, sigsetsize)
 }
 
 /// Manipulates various aspects of the behavior of the calling thread or process.
@@ -892,6 +1410,118 @@ pub unsafe fn prctl(
     syscall!(raw::PRCTL, option, arg2, arg3, arg4, arg5)
 }
 
+/// Read from a file descriptor at a given offset, without changing the
+/// file's current position.
+///
+/// On 32-bit platforms this splits the 64-bit offset across two arguments
+/// as the kernel's calling convention for this system call requires.
+#[cfg(have_syscall = "pread64")]
+#[inline(always)]
+pub unsafe fn pread64(fd: int, buf: *mut void, count: size_t, offset: loff_t) -> Result<ssize_t> {
+    #[cfg(target_pointer_width = "64")]
+    {
+        syscall!(raw::PREAD64, fd, buf, count, offset)
+    }
+    #[cfg(all(target_pointer_width = "32", target_arch = "arm"))]
+    {
+        // ARM EABI requires 64-bit arguments to start at an even-numbered
+        // register, so there's a padding argument before the offset.
+        let (low, high) = split_offset(offset);
+        syscall!(raw::PREAD64, fd, buf, count, 0, low, high)
+    }
+    #[cfg(all(target_pointer_width = "32", not(target_arch = "arm")))]
+    {
+        let (low, high) = split_offset(offset);
+        syscall!(raw::PREAD64, fd, buf, count, low, high)
+    }
+}
+
+/// Read from a file descriptor at a given offset into multiple buffers,
+/// without changing the file's current position.
+#[cfg(have_syscall = "preadv")]
+#[inline(always)]
+pub unsafe fn preadv(fd: int, iov: *mut iovec, iovcount: int, offset: loff_t) -> Result<size_t> {
+    let (low, high) = split_offset(offset);
+    syscall!(raw::PREADV, fd, iov, iovcount, low, high)
+}
+
+/// Read from a file descriptor into multiple buffers, with additional
+/// flags.
+///
+/// If `offset` is -1 then this reads from and updates the file's current
+/// position. Otherwise it reads from the given offset without changing the
+/// current position. `flags` is a combination of the `RWF_*` flags.
+#[cfg(have_syscall = "preadv2")]
+#[inline(always)]
+pub unsafe fn preadv2(
+    fd: int,
+    iov: *mut iovec,
+    iovcount: int,
+    offset: loff_t,
+    flags: rwf_t,
+) -> Result<size_t> {
+    let (low, high) = split_offset(offset);
+    syscall!(raw::PREADV2, fd, iov, iovcount, low, high, flags)
+}
+
+/// Write to a file descriptor at a given offset, without changing the
+/// file's current position.
+///
+/// On 32-bit platforms this splits the 64-bit offset across two arguments
+/// as the kernel's calling convention for this system call requires.
+#[cfg(have_syscall = "pwrite64")]
+#[inline(always)]
+pub unsafe fn pwrite64(
+    fd: int,
+    buf: *const void,
+    count: size_t,
+    offset: loff_t,
+) -> Result<ssize_t> {
+    #[cfg(target_pointer_width = "64")]
+    {
+        syscall!(raw::PWRITE64, fd, buf, count, offset)
+    }
+    #[cfg(all(target_pointer_width = "32", target_arch = "arm"))]
+    {
+        // ARM EABI requires 64-bit arguments to start at an even-numbered
+        // register, so there's a padding argument before the offset.
+        let (low, high) = split_offset(offset);
+        syscall!(raw::PWRITE64, fd, buf, count, 0, low, high)
+    }
+    #[cfg(all(target_pointer_width = "32", not(target_arch = "arm")))]
+    {
+        let (low, high) = split_offset(offset);
+        syscall!(raw::PWRITE64, fd, buf, count, low, high)
+    }
+}
+
+/// Write to a file descriptor at a given offset from multiple buffers,
+/// without changing the file's current position.
+#[cfg(have_syscall = "pwritev")]
+#[inline(always)]
+pub unsafe fn pwritev(fd: int, iov: *const iovec, iovcount: int, offset: loff_t) -> Result<size_t> {
+    let (low, high) = split_offset(offset);
+    syscall!(raw::PWRITEV, fd, iov, iovcount, low, high)
+}
+
+/// Write to a file descriptor from multiple buffers, with additional flags.
+///
+/// If `offset` is -1 then this writes at and updates the file's current
+/// position. Otherwise it writes at the given offset without changing the
+/// current position. `flags` is a combination of the `RWF_*` flags.
+#[cfg(have_syscall = "pwritev2")]
+#[inline(always)]
+pub unsafe fn pwritev2(
+    fd: int,
+    iov: *const iovec,
+    iovcount: int,
+    offset: loff_t,
+    flags: rwf_t,
+) -> Result<size_t> {
+    let (low, high) = split_offset(offset);
+    syscall!(raw::PWRITEV2, fd, iov, iovcount, low, high, flags)
+}
+
 /// Read from a file descriptor.
 #[cfg(have_syscall = "read")]
 #[inline(always)]
@@ -925,6 +1555,136 @@ pub unsafe fn readv(fd: int, iov: *mut iovec, iovcount: int) -> Result<size_t> {
     syscall!(raw::READV, fd, iov, iovcount)
 }
 
+/// Receive a message from a socket, optionally retrieving the address of
+/// the sender.
+///
+/// `addr` and `addrlen` can both be null if the caller doesn't need the
+/// sender address.
+#[cfg(have_syscall = "recvfrom")]
+#[inline(always)]
+pub unsafe fn recvfrom(
+    sockfd: int,
+    buf: *mut void,
+    len: size_t,
+    flags: int,
+    addr: *mut sockaddr,
+    addrlen: *mut socklen_t,
+) -> Result<size_t> {
+    syscall!(raw::RECVFROM, sockfd, buf, len, flags, addr, addrlen)
+}
+
+/// Receive multiple messages from a socket in a single call.
+///
+/// The number of bytes received for each message is written to its
+/// `msg_len` field. The result is the number of messages received.
+#[cfg(have_syscall = "recvmmsg")]
+#[inline(always)]
+pub unsafe fn recvmmsg(
+    sockfd: int,
+    msgvec: *mut mmsghdr,
+    vlen: uint,
+    flags: int,
+    timeout: *mut timespec,
+) -> Result<int> {
+    syscall!(raw::RECVMMSG, sockfd, msgvec, vlen, flags, timeout)
+}
+
+/// Receive a message from a socket, along with its sender address and any
+/// ancillary data, as described by `msg`.
+#[cfg(have_syscall = "recvmsg")]
+#[inline(always)]
+pub unsafe fn recvmsg(sockfd: int, msg: *mut msghdr, flags: int) -> Result<size_t> {
+    syscall!(raw::RECVMSG, sockfd, msg, flags)
+}
+
+/// Rename a file relative to directory file descriptors.
+#[cfg(have_syscall = "renameat")]
+#[inline(always)]
+pub unsafe fn renameat(
+    olddirfd: int,
+    oldpath: *const char,
+    newdirfd: int,
+    newpath: *const char,
+) -> Result<int> {
+    syscall!(raw::RENAMEAT, olddirfd, oldpath, newdirfd, newpath)
+}
+
+/// Rename a file relative to directory file descriptors, with additional
+/// flags.
+///
+/// `flags` is a combination of `RENAME_NOREPLACE`, `RENAME_EXCHANGE` and
+/// `RENAME_WHITEOUT`.
+#[cfg(have_syscall = "renameat2")]
+#[inline(always)]
+pub unsafe fn renameat2(
+    olddirfd: int,
+    oldpath: *const char,
+    newdirfd: int,
+    newpath: *const char,
+    flags: uint,
+) -> Result<int> {
+    syscall!(raw::RENAMEAT2, olddirfd, oldpath, newdirfd, newpath, flags)
+}
+
+/// Examine and change the action taken on delivery of a signal.
+///
+/// Set `act` to null to only retrieve the current action, and set `oldact`
+/// to null if the previous action is not needed. `sigsetsize` must be the
+/// size of [`sigset_t`] in bytes.
+///
+/// Use [`sigaction::new`] to prepare an action that includes the
+/// signal return trampoline required on some architectures.
+#[cfg(have_syscall = "rt_sigaction")]
+#[inline(always)]
+pub unsafe fn rt_sigaction(
+    signum: int,
+    act: *const sigaction,
+    oldact: *mut sigaction,
+    sigsetsize: size_t,
+) -> Result<int> {
+    syscall!(raw::RT_SIGACTION, signum, act, oldact, sigsetsize)
+}
+
+/// Examine the set of signals that are pending for delivery to the calling
+/// thread while blocked.
+#[cfg(have_syscall = "rt_sigpending")]
+#[inline(always)]
+pub unsafe fn rt_sigpending(set: *mut sigset_t, sigsetsize: size_t) -> Result<int> {
+    syscall!(raw::RT_SIGPENDING, set, sigsetsize)
+}
+
+/// Examine and change the set of signals that are blocked for the calling
+/// thread.
+///
+/// `how` must be one of [`SIG_BLOCK`], [`SIG_UNBLOCK`], or [`SIG_SETMASK`].
+#[cfg(have_syscall = "rt_sigprocmask")]
+#[inline(always)]
+pub unsafe fn rt_sigprocmask(
+    how: int,
+    set: *const sigset_t,
+    oldset: *mut sigset_t,
+    sigsetsize: size_t,
+) -> Result<int> {
+    syscall!(raw::RT_SIGPROCMASK, how, set, oldset, sigsetsize)
+}
+
+/// Synchronously wait for one of the given signals to become pending,
+/// returning its signal number.
+///
+/// The signals in `set` should be blocked before calling this function,
+/// or else they may be delivered as normal instead. Set `timeout` to null
+/// to wait indefinitely.
+#[cfg(have_syscall = "rt_sigtimedwait")]
+#[inline(always)]
+pub unsafe fn rt_sigtimedwait(
+    set: *const sigset_t,
+    info: *mut siginfo_t,
+    timeout: *const timespec,
+    sigsetsize: size_t,
+) -> Result<int> {
+    syscall!(raw::RT_SIGTIMEDWAIT, set, info, timeout, sigsetsize)
+}
+
 /// Set a socket option.
 #[cfg(have_syscall = "setsockopt")]
 #[inline(always)]
@@ -957,6 +1717,76 @@ pub unsafe fn sendfile64(
     syscall!(raw::SENDFILE64, out_fd, in_fd, offset, count)
 }
 
+/// Send multiple messages on a socket in a single call.
+///
+/// The number of bytes sent for each message is written to its `msg_len`
+/// field. The result is the number of messages sent.
+#[cfg(have_syscall = "sendmmsg")]
+#[inline(always)]
+pub unsafe fn sendmmsg(sockfd: int, msgvec: *mut mmsghdr, vlen: uint, flags: int) -> Result<int> {
+    syscall!(raw::SENDMMSG, sockfd, msgvec, vlen, flags)
+}
+
+/// Send a message on a socket, along with an optional destination address
+/// and any ancillary data, as described by `msg`.
+#[cfg(have_syscall = "sendmsg")]
+#[inline(always)]
+pub unsafe fn sendmsg(sockfd: int, msg: *const msghdr, flags: int) -> Result<size_t> {
+    syscall!(raw::SENDMSG, sockfd, msg, flags)
+}
+
+/// Send a message on a socket, optionally to a specific destination
+/// address.
+///
+/// `addr` can be null with `addrlen` zero for a connected socket.
+#[cfg(have_syscall = "sendto")]
+#[inline(always)]
+pub unsafe fn sendto(
+    sockfd: int,
+    buf: *const void,
+    len: size_t,
+    flags: int,
+    addr: *const sockaddr,
+    addrlen: socklen_t,
+) -> Result<size_t> {
+    syscall!(raw::SENDTO, sockfd, buf, len, flags, addr, addrlen)
+}
+
+/// Shut down part or all of a full-duplex socket connection.
+///
+/// `how` is one of [`SHUT_RD`], [`SHUT_WR`], or [`SHUT_RDWR`].
+#[cfg(have_syscall = "shutdown")]
+#[inline(always)]
+pub unsafe fn shutdown(sockfd: int, how: int) -> Result<int> {
+    syscall!(raw::SHUTDOWN, sockfd, how)
+}
+
+/// Set and/or get the alternate stack used for signal handlers that were
+/// registered with [`SA_ONSTACK`].
+#[cfg(have_syscall = "sigaltstack")]
+#[inline(always)]
+pub unsafe fn sigaltstack(ss: *const stack_t, old_ss: *mut stack_t) -> Result<int> {
+    syscall!(raw::SIGALTSTACK, ss, old_ss)
+}
+
+/// Create or modify a file descriptor for accepting signals.
+///
+/// Set `fd` to -1 to create a new file descriptor, or to an existing signalfd
+/// to change its mask. `sizemask` must be the size of [`sigset_t`] in bytes.
+///
+/// The signals in `mask` should be blocked using [`rt_sigprocmask`] so that
+/// they will remain pending to be read from the file descriptor.
+#[cfg(have_syscall = "signalfd4")]
+#[inline(always)]
+pub unsafe fn signalfd4(
+    fd: int,
+    mask: *const sigset_t,
+    sizemask: size_t,
+    flags: int,
+) -> Result<int> {
+    syscall!(raw::SIGNALFD4, fd, mask, sizemask, flags)
+}
+
 /// Create a socket endpoint for communication.
 #[cfg(have_syscall = "socket")]
 #[inline(always)]
@@ -1004,6 +1834,16 @@ pub unsafe fn statx(
     syscall!(raw::STATX, dirfd, pathname, flags, mask, statxbuf)
 }
 
+/// Create a symbolic link relative to a directory file descriptor.
+///
+/// The link will refer to `target`, which is not interpreted by the kernel
+/// when creating the link.
+#[cfg(have_syscall = "symlinkat")]
+#[inline(always)]
+pub unsafe fn symlinkat(target: *const char, newdirfd: int, linkpath: *const char) -> Result<int> {
+    syscall!(raw::SYMLINKAT, target, newdirfd, linkpath)
+}
+
 /// Commit all filesystem caches to disk.
 #[cfg(have_syscall = "sync")]
 #[inline(always)]
@@ -1025,6 +1865,94 @@ pub unsafe fn tee(fd_in: int, fd_out: int, len: size_t, flags: uint) -> Result<i
     syscall!(raw::TEE, fd_in, fd_out, len, flags)
 }
 
+/// Send a signal to a specific thread in a specific thread group.
+#[cfg(have_syscall = "tgkill")]
+#[inline(always)]
+pub unsafe fn tgkill(tgid: pid_t, tid: pid_t, sig: int) -> Result<int> {
+    syscall!(raw::TGKILL, tgid, tid, sig)
+}
+
+/// Get the current wall-clock time in seconds since the Unix epoch.
+///
+/// If `tloc` is not null then the result is also written to it.
+///
+/// **Warning:** This function is not available on architectures recently added
+/// to the kernel. Use [`clock_gettime`] with [`CLOCK_REALTIME`], or
+/// [`crate::vdso::time`], for better portability.
+#[cfg(have_syscall = "time")]
+#[inline(always)]
+pub unsafe fn time(tloc: *mut time_t) -> Result<time_t> {
+    syscall!(raw::TIME, tloc)
+}
+
+/// Create a timer that delivers timer expiration notifications via a file
+/// descriptor.
+#[cfg(have_syscall = "timerfd_create")]
+#[inline(always)]
+pub unsafe fn timerfd_create(clockid: clockid_t, flags: int) -> Result<int> {
+    syscall!(raw::TIMERFD_CREATE, clockid, flags)
+}
+
+/// Retrieve the current setting of a timer file descriptor.
+#[cfg(have_syscall = "timerfd_gettime")]
+#[inline(always)]
+pub unsafe fn timerfd_gettime(fd: int, curr_value: *mut itimerspec) -> Result<int> {
+    syscall!(raw::TIMERFD_GETTIME, fd, curr_value)
+}
+
+/// Retrieve the current setting of a timer file descriptor.
+///
+/// On this platform this is actually a wrapper around the `timerfd_gettime32` system
+/// call, which uses a 32-bit `time_t` and so cannot represent times after
+/// the year 2038.
+#[cfg(all(
+    have_syscall = "timerfd_gettime32",
+    not(have_syscall = "timerfd_gettime")
+))]
+#[inline(always)]
+pub unsafe fn timerfd_gettime(fd: int, curr_value: *mut itimerspec) -> Result<int> {
+    syscall!(raw::TIMERFD_GETTIME32, fd, curr_value)
+}
+
+/// Arm or disarm a timer file descriptor.
+///
+/// If `flags` includes [`TFD_TIMER_ABSTIME`] then the initial expiration
+/// time in `new_value` is an absolute time on the timer's clock. The
+/// previous setting is written to `old_value` unless it's null.
+#[cfg(have_syscall = "timerfd_settime")]
+#[inline(always)]
+pub unsafe fn timerfd_settime(
+    fd: int,
+    flags: int,
+    new_value: *const itimerspec,
+    old_value: *mut itimerspec,
+) -> Result<int> {
+    syscall!(raw::TIMERFD_SETTIME, fd, flags, new_value, old_value)
+}
+
+/// Arm or disarm a timer file descriptor.
+///
+/// If `flags` includes [`TFD_TIMER_ABSTIME`] then the initial expiration
+/// time in `new_value` is an absolute time on the timer's clock. The
+/// previous setting is written to `old_value` unless it's null.
+///
+/// On this platform this is actually a wrapper around the `timerfd_settime32` system
+/// call, which uses a 32-bit `time_t` and so cannot represent times after
+/// the year 2038.
+#[cfg(all(
+    have_syscall = "timerfd_settime32",
+    not(have_syscall = "timerfd_settime")
+))]
+#[inline(always)]
+pub unsafe fn timerfd_settime(
+    fd: int,
+    flags: int,
+    new_value: *const itimerspec,
+    old_value: *mut itimerspec,
+) -> Result<int> {
+    syscall!(raw::TIMERFD_SETTIME32, fd, flags, new_value, old_value)
+}
+
 /// Truncate a file to a specified length.
 #[cfg(have_syscall = "truncate")]
 #[inline(always)]
@@ -1032,6 +1960,94 @@ pub unsafe fn truncate(path: *const char, length: off_t) -> Result<int> {
     syscall!(raw::TRUNCATE, path, length)
 }
 
+/// Delete a name from the filesystem relative to a directory file
+/// descriptor.
+///
+/// Set `flags` to `AT_REMOVEDIR` to remove a directory, or zero to remove
+/// any other kind of file.
+#[cfg(have_syscall = "unlinkat")]
+#[inline(always)]
+pub unsafe fn unlinkat(dirfd: int, pathname: *const char, flags: int) -> Result<int> {
+    syscall!(raw::UNLINKAT, dirfd, pathname, flags)
+}
+
+/// Disassociate parts of the calling thread's execution context, such as
+/// its namespaces, from other processes and threads.
+///
+/// `flags` is a combination of `CLONE_*` flags, such as `CLONE_NEWNET` to
+/// move the calling thread into a new network namespace.
+#[cfg(have_syscall = "unshare")]
+#[inline(always)]
+pub unsafe fn unshare(flags: ulong) -> Result<int> {
+    syscall!(raw::UNSHARE, flags)
+}
+
+/// Change the access and modification timestamps of a file relative to a
+/// directory file descriptor.
+///
+/// `times` points to two timestamps for access and modification respectively,
+/// or is null to set both to the current time. The `tv_nsec` field of either
+/// can be `UTIME_NOW` or `UTIME_OMIT` to set that timestamp to the current
+/// time or to leave it unchanged.
+#[cfg(have_syscall = "utimensat")]
+#[inline(always)]
+pub unsafe fn utimensat(
+    dirfd: int,
+    pathname: *const char,
+    times: *const timespec,
+    flags: int,
+) -> Result<int> {
+    syscall!(raw::UTIMENSAT, dirfd, pathname, times, flags)
+}
+
+/// Splice user memory into a pipe, or read data from a pipe into user
+/// memory.
+///
+/// `flags` is a combination of the `SPLICE_F_*` flags.
+#[cfg(have_syscall = "vmsplice")]
+#[inline(always)]
+pub unsafe fn vmsplice(fd: int, iov: *const iovec, nr_segs: ulong, flags: uint) -> Result<ssize_t> {
+    syscall!(raw::VMSPLICE, fd, iov, nr_segs, flags)
+}
+
+/// Wait for a child process to change state, and optionally retrieve its
+/// resource usage.
+///
+/// `wstatus` and `rusage` can both be null if the caller doesn't need that
+/// information.
+#[cfg(have_syscall = "wait4")]
+#[inline(always)]
+pub unsafe fn wait4(
+    pid: pid_t,
+    wstatus: *mut int,
+    options: int,
+    rusage: *mut rusage,
+) -> Result<pid_t> {
+    syscall!(raw::WAIT4, pid, wstatus, options, rusage)
+}
+
+/// Wait for a child process to change state, and optionally retrieve its
+/// resource usage.
+///
+/// `idtype` selects how to interpret `id`, using one of the constants whose
+/// names start with `P_`. For example, `P_PIDFD` allows waiting for the
+/// process represented by a file descriptor from [`pidfd_open`] or [`clone3`].
+///
+/// If `options` includes `WNOHANG` and no child has changed state then the
+/// kernel leaves `infop` unmodified, so callers should zero it first and
+/// then check `si_pid` to distinguish that case.
+#[cfg(have_syscall = "waitid")]
+#[inline(always)]
+pub unsafe fn waitid(
+    idtype: idtype_t,
+    id: id_t,
+    infop: *mut siginfo_t,
+    options: int,
+    rusage: *mut rusage,
+) -> Result<int> {
+    syscall!(raw::WAITID, idtype, id, infop, options, rusage)
+}
+
 /// Write to a file descriptor.
 #[cfg(have_syscall = "write")]
 #[inline(always)]
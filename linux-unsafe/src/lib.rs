@@ -10,6 +10,7 @@
 //! - x86 (32-bit)
 //! - arm
 //! - riscv64
+//! - aarch64
 //!
 //! For this initial release, x86_64 has seen some limited testing and the
 //! other platforms have been barely tested at all. Over time I intend to
@@ -58,6 +59,8 @@ pub use types::*;
 pub mod args;
 pub mod result;
 pub(crate) mod sigset;
+pub mod startup;
+pub mod vdso;
 
 #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
 #[path = "raw/x86_64.rs"]
@@ -75,5 +78,9 @@ pub mod raw;
 #[path = "raw/riscv64.rs"]
 pub mod raw;
 
+#[cfg(all(target_os = "linux", target_arch = "aarch64"))]
+#[path = "raw/aarch64.rs"]
+pub mod raw;
+
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,167 @@
+//! Thin wrappers around the CPU instructions for making system calls on aarch64 (64-bit ARM).
+
+use core::arch::asm;
+
+/// The type of all system call arguments and return values on this platform.
+pub type V = u64;
+
+/// Call into a system function with no arguments.
+#[inline(always)]
+pub unsafe fn syscall0(n: V) -> V {
+    let ret: V;
+    asm!(
+        "svc #0",
+        in("x8") n,
+        out("x0") ret,
+    );
+    ret
+}
+
+/// Call into a system function with one argument.
+#[inline(always)]
+pub unsafe fn syscall1(n: V, a0: V) -> V {
+    let ret: V;
+    asm!(
+        "svc #0",
+        in("x8") n,
+        inout("x0") a0 => ret,
+    );
+    ret
+}
+
+/// Call into a system function with two arguments.
+#[inline(always)]
+pub unsafe fn syscall2(n: V, a0: V, a1: V) -> V {
+    let ret: V;
+    asm!(
+        "svc #0",
+        in("x8") n,
+        inout("x0") a0 => ret,
+        in("x1") a1,
+    );
+    ret
+}
+
+/// Call into a system function with three arguments.
+#[inline(always)]
+pub unsafe fn syscall3(n: V, a0: V, a1: V, a2: V) -> V {
+    let ret: V;
+    asm!(
+        "svc #0",
+        in("x8") n,
+        inout("x0") a0 => ret,
+        in("x1") a1,
+        in("x2") a2,
+    );
+    ret
+}
+
+/// Call into a system function with four arguments.
+#[inline(always)]
+pub unsafe fn syscall4(n: V, a0: V, a1: V, a2: V, a3: V) -> V {
+    let ret: V;
+    asm!(
+        "svc #0",
+        in("x8") n,
+        inout("x0") a0 => ret,
+        in("x1") a1,
+        in("x2") a2,
+        in("x3") a3,
+    );
+    ret
+}
+
+/// Call into a system function with five arguments.
+#[inline(always)]
+pub unsafe fn syscall5(n: V, a0: V, a1: V, a2: V, a3: V, a4: V) -> V {
+    let ret: V;
+    asm!(
+        "svc #0",
+        in("x8") n,
+        inout("x0") a0 => ret,
+        in("x1") a1,
+        in("x2") a2,
+        in("x3") a3,
+        in("x4") a4,
+    );
+    ret
+}
+
+/// Call into a system function with six arguments.
+#[inline(always)]
+pub unsafe fn syscall6(n: V, a0: V, a1: V, a2: V, a3: V, a4: V, a5: V) -> V {
+    let ret: V;
+    asm!(
+        "svc #0",
+        in("x8") n,
+        inout("x0") a0 => ret,
+        in("x1") a1,
+        in("x2") a2,
+        in("x3") a3,
+        in("x4") a4,
+        in("x5") a5,
+    );
+    ret
+}
+
+/// Given a result value from a system call that follows the standard error
+/// return convention for this platform, returns either the given value
+/// verbatim or the kernel error code extracted from it.
+///
+/// For aarch64, the standard way to signal an error is to return a result
+/// between -4095 and -1 inclusive, with all other values representing
+/// successful results.
+///
+/// A small number of system calls signal errors in different ways. This
+/// function is not compatible with the results from those calls.
+#[inline]
+pub fn unpack_standard_result(raw: V) -> Result<V, i32> {
+    if (raw as u64) >= ((-4095 as i64) as u64) {
+        let err = -(raw as i32);
+        Err(err)
+    } else {
+        Ok(raw)
+    }
+}
+
+/// Signal return trampoline for use as the `sa_restorer` field of
+/// [`crate::sigaction`], which just calls `rt_sigreturn`.
+///
+/// This must never be called directly. The kernel arranges for signal
+/// handlers to return into this function when `SA_RESTORER` is set.
+#[unsafe(naked)]
+pub unsafe extern "C" fn restore_rt() -> ! {
+    core::arch::naked_asm!(
+        "mov x8, #{n}",
+        "svc #0",
+        n = const RT_SIGRETURN,
+    )
+}
+
+include!(concat!(env!("OUT_DIR"), "/syscall_nrs_aarch64.rs"));
+pub(crate) mod errno {
+    include!(concat!(env!("OUT_DIR"), "/errnos_aarch64.rs"));
+}
+pub(crate) mod signal {
+    include!(concat!(env!("OUT_DIR"), "/signals_aarch64.rs"));
+}
+
+// Names of the functions exported by the vDSO on this platform, and the
+// symbol version they all belong to.
+pub(crate) mod vdso {
+    pub(crate) const VERSION: &[u8] = b"LINUX_2.6.39";
+    pub(crate) const CLOCK_GETTIME: Option<&[u8]> = Some(b"__kernel_clock_gettime");
+    pub(crate) const GETTIMEOFDAY: Option<&[u8]> = Some(b"__kernel_gettimeofday");
+    pub(crate) const TIME: Option<&[u8]> = None;
+    pub(crate) const GETCPU: Option<&[u8]> = None;
+}
+
+// Architecture-specific types and constants
+pub(crate) mod types {
+    use crate::int;
+
+    pub const O_DIRECTORY: int = 0o00040000;
+    pub const O_NOFOLLOW: int = 0o00100000;
+    pub const O_DIRECT: int = 0o00200000;
+    pub const O_LARGEFILE: int = 0o00400000;
+}
@@ -124,10 +124,44 @@ pub fn unpack_standard_result(raw: V) -> Result<V, i32> {
     }
 }
 
+/// Signal return trampoline for use as the `sa_restorer` field of
+/// [`crate::sigaction`], which just calls `rt_sigreturn`.
+///
+/// This must never be called directly. The kernel arranges for signal
+/// handlers to return into this function when `SA_RESTORER` is set.
+#[unsafe(naked)]
+pub unsafe extern "C" fn restore_rt() -> ! {
+    core::arch::naked_asm!(
+        "mov r7, #{n}",
+        "svc #0",
+        n = const RT_SIGRETURN,
+    )
+}
+
 include!(concat!(env!("OUT_DIR"), "/syscall_nrs_arm.rs"));
 pub(crate) mod errno {
     include!(concat!(env!("OUT_DIR"), "/errnos_arm.rs"));
 }
+pub(crate) mod signal {
+    include!(concat!(env!("OUT_DIR"), "/signals_arm.rs"));
+}
+
+// Names of the functions exported by the vDSO on this platform, and the
+// symbol version they all belong to.
+pub(crate) mod vdso {
+    pub(crate) const VERSION: &[u8] = b"LINUX_2.6";
+    pub(crate) const CLOCK_GETTIME: Option<&[u8]> = Some(b"__vdso_clock_gettime");
+    pub(crate) const GETTIMEOFDAY: Option<&[u8]> = Some(b"__vdso_gettimeofday");
+    pub(crate) const TIME: Option<&[u8]> = None;
+    pub(crate) const GETCPU: Option<&[u8]> = None;
+}
 
 // Architecture-specific types and constants
-pub(crate) mod types {}
+pub(crate) mod types {
+    use crate::int;
+
+    pub const O_DIRECTORY: int = 0o00040000;
+    pub const O_NOFOLLOW: int = 0o00100000;
+    pub const O_DIRECT: int = 0o00200000;
+    pub const O_LARGEFILE: int = 0o00400000;
+}
@@ -128,6 +128,19 @@ include!(concat!(env!("OUT_DIR"), "/syscall_nrs_riscv64.rs"));
 pub(crate) mod errno {
     include!(concat!(env!("OUT_DIR"), "/errnos_riscv64.rs"));
 }
+pub(crate) mod signal {
+    include!(concat!(env!("OUT_DIR"), "/signals_riscv64.rs"));
+}
+
+// Names of the functions exported by the vDSO on this platform, and the
+// symbol version they all belong to.
+pub(crate) mod vdso {
+    pub(crate) const VERSION: &[u8] = b"LINUX_4.15";
+    pub(crate) const CLOCK_GETTIME: Option<&[u8]> = Some(b"__vdso_clock_gettime");
+    pub(crate) const GETTIMEOFDAY: Option<&[u8]> = Some(b"__vdso_gettimeofday");
+    pub(crate) const TIME: Option<&[u8]> = None;
+    pub(crate) const GETCPU: Option<&[u8]> = Some(b"__vdso_getcpu");
+}
 
 // Architecture-specific types and constants
 pub(crate) mod types {}
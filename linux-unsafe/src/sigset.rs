@@ -1,19 +1,22 @@
 #![allow(non_camel_case_types)]
 
 /// A set of signals for use with signal blocking functions.
-#[derive(Clone, Copy, Debug)]
+///
+/// The layout matches the kernel's own `sigset_t`, which has one bit for
+/// each of the [`crate::_NSIG`] signals, including the real-time signals.
+/// Use `core::mem::size_of::<sigset_t>()` for the `sigsetsize` argument of
+/// system calls that accept a signal set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(C)]
 pub struct sigset_t {
-    // For now we only support 32-bit and 64-bit architectures, and there are
-    // only 32 signals defined, so it's safe to assume that they all fit in
-    // one element. We'll need to be more clever about this if we ever support
-    // an architecture where sigset_t has a different layout.
-    sig: [crate::ulong; 1],
+    sig: [crate::ulong; sigset_t::ELEMS],
 }
 
+static_assertions::const_assert_eq!(core::mem::size_of::<sigset_t>(), crate::_NSIG / 8);
+
 impl sigset_t {
-    const ELEMS: usize = 1;
     const ELEM_BITS: usize = core::mem::size_of::<crate::ulong>() * 8;
+    const ELEMS: usize = crate::_NSIG / Self::ELEM_BITS;
     const FILLED: crate::ulong = !0;
 
     #[inline]
@@ -47,21 +50,79 @@ impl sigset_t {
     #[inline]
     pub fn sigaddset(&mut self, signum: crate::int) -> crate::result::Result<()> {
         let (elem, bit) = Self::sigpos(signum)?;
-        self.sig[elem] |= (1 << bit) as crate::ulong;
+        self.sig[elem] |= Self::bitmask(bit);
         Ok(())
     }
 
     #[inline]
     pub fn sigdelset(&mut self, signum: crate::int) -> crate::result::Result<()> {
         let (elem, bit) = Self::sigpos(signum)?;
-        self.sig[elem] &= !(1 << bit) as crate::ulong;
+        self.sig[elem] &= !Self::bitmask(bit);
         Ok(())
     }
 
     #[inline]
-    pub fn sigismember(&mut self, signum: crate::int) -> crate::result::Result<bool> {
+    pub fn sigismember(&self, signum: crate::int) -> crate::result::Result<bool> {
         let (elem, bit) = Self::sigpos(signum)?;
-        Ok((self.sig[elem] & (1 << bit)) != 0)
+        Ok((self.sig[elem] & Self::bitmask(bit)) != 0)
+    }
+
+    /// Returns true if the set contains no signals.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        let mut i = 0;
+        while i < Self::ELEMS {
+            if self.sig[i] != 0 {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    /// Returns a set containing all of the signals that are in either
+    /// `self` or `other`.
+    #[inline]
+    pub const fn union(mut self, other: Self) -> Self {
+        let mut i = 0;
+        while i < Self::ELEMS {
+            self.sig[i] |= other.sig[i];
+            i += 1;
+        }
+        self
+    }
+
+    /// Returns a set containing only the signals that are in both `self`
+    /// and `other`.
+    #[inline]
+    pub const fn intersect(mut self, other: Self) -> Self {
+        let mut i = 0;
+        while i < Self::ELEMS {
+            self.sig[i] &= other.sig[i];
+            i += 1;
+        }
+        self
+    }
+
+    /// Returns a set containing all of the signals that are not in `self`.
+    #[inline]
+    pub const fn complement(mut self) -> Self {
+        let mut i = 0;
+        while i < Self::ELEMS {
+            self.sig[i] = !self.sig[i];
+            i += 1;
+        }
+        self
+    }
+
+    /// Returns an iterator over the numbers of the signals in the set, in
+    /// ascending order.
+    #[inline]
+    pub fn iter(&self) -> SigSetIter {
+        SigSetIter {
+            set: *self,
+            next: 1,
+        }
     }
 
     pub fn as_ptr(&self) -> *const Self {
@@ -73,12 +134,88 @@ impl sigset_t {
     }
 
     fn sigpos(signum: crate::int) -> crate::result::Result<(usize, usize)> {
+        if signum < 1 || signum as usize > crate::_NSIG {
+            return Err(crate::result::Error::new(crate::result::EINVAL));
+        }
         let total_bit = (signum - 1) as usize;
         let elem = total_bit / Self::ELEM_BITS;
-        if elem >= Self::ELEMS {
-            return Err(crate::result::Error::new(22 /* EINVAL */));
-        }
         let bit = total_bit % Self::ELEM_BITS;
         Ok((elem, bit))
     }
+
+    #[inline(always)]
+    const fn bitmask(bit: usize) -> crate::ulong {
+        (1 as crate::ulong) << bit
+    }
+}
+
+impl core::ops::BitOr for sigset_t {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self {
+        self.union(rhs)
+    }
+}
+
+impl core::ops::BitAnd for sigset_t {
+    type Output = Self;
+
+    #[inline]
+    fn bitand(self, rhs: Self) -> Self {
+        self.intersect(rhs)
+    }
+}
+
+impl core::ops::Not for sigset_t {
+    type Output = Self;
+
+    #[inline]
+    fn not(self) -> Self {
+        self.complement()
+    }
+}
+
+impl IntoIterator for &sigset_t {
+    type Item = crate::int;
+    type IntoIter = SigSetIter;
+
+    #[inline]
+    fn into_iter(self) -> SigSetIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<crate::int> for sigset_t {
+    /// Collects signal numbers into a set, ignoring any that are out of range.
+    fn from_iter<T: IntoIterator<Item = crate::int>>(iter: T) -> Self {
+        let mut ret = Self::new_empty();
+        for signum in iter {
+            let _ = ret.sigaddset(signum);
+        }
+        ret
+    }
+}
+
+/// Iterator over the signal numbers in a [`sigset_t`], returned by
+/// [`sigset_t::iter`].
+#[derive(Clone, Debug)]
+pub struct SigSetIter {
+    set: sigset_t,
+    next: crate::int,
+}
+
+impl Iterator for SigSetIter {
+    type Item = crate::int;
+
+    fn next(&mut self) -> Option<crate::int> {
+        while self.next as usize <= crate::_NSIG {
+            let signum = self.next;
+            self.next += 1;
+            if let Ok(true) = self.set.sigismember(signum) {
+                return Some(signum);
+            }
+        }
+        None
+    }
 }
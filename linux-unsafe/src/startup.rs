@@ -0,0 +1,186 @@
+//! Access to the arguments, environment, and auxiliary vector that the kernel
+//! passes to a new program on its initial stack.
+//!
+//! Programs linked with libc, including those using the Rust standard library,
+//! should generally use the facilities of those libraries instead. This is
+//! primarily for `no_std` programs that define their own entry point.
+
+use core::ffi::CStr;
+
+use crate::{auxv_t, char, ulong, void};
+
+/// The startup state of a program, as provided by the kernel.
+///
+/// The kernel places this information at the top of the initial stack of
+/// the program's main thread, and it remains there for the life of the
+/// program unless the program itself overwrites it.
+#[derive(Clone, Copy, Debug)]
+pub struct StartupInfo {
+    argc: usize,
+    argv: *const *const char,
+    envp: *const *const char,
+    auxv: *const auxv_t,
+}
+
+impl StartupInfo {
+    /// Find the startup information using the value that the stack pointer
+    /// register had on entry to the program's entry point, before any
+    /// other code modified the stack.
+    ///
+    /// Safety: `sp` must be the initial stack pointer of the current
+    /// process, and the caller must not have modified the data it refers to.
+    pub unsafe fn from_stack(sp: *const void) -> Self {
+        let sp = sp as *const usize;
+        let argc = *sp;
+        let argv = sp.add(1) as *const *const char;
+        // The environment pointers start immediately after the null
+        // pointer that terminates the argument pointers.
+        let envp = argv.add(argc + 1);
+        Self::from_argv_envp(argc, argv, envp)
+    }
+
+    /// Find the startup information using argument and environment pointers
+    /// provided by some other means, such as the arguments to a C-style
+    /// `main` function.
+    ///
+    /// Safety: `argv` and `envp` must be the original argument and environment
+    /// pointer arrays that the kernel placed on the current process's initial
+    /// stack, and `argc` must be the number of non-null elements in `argv`.
+    pub unsafe fn from_argv_envp(
+        argc: usize,
+        argv: *const *const char,
+        envp: *const *const char,
+    ) -> Self {
+        // The auxiliary vector starts immediately after the null pointer
+        // that terminates the environment pointers.
+        let mut p = envp;
+        while !(*p).is_null() {
+            p = p.add(1);
+        }
+        let auxv = p.add(1) as *const auxv_t;
+        Self {
+            argc,
+            argv,
+            envp,
+            auxv,
+        }
+    }
+
+    /// Returns the number of command line arguments, including the program
+    /// name.
+    #[inline(always)]
+    pub const fn argc(&self) -> usize {
+        self.argc
+    }
+
+    /// Returns an iterator over the command line arguments, starting with
+    /// the program name.
+    #[inline]
+    pub fn args(&self) -> CStrArray {
+        CStrArray { next: self.argv }
+    }
+
+    /// Returns an iterator over the environment variables, each of which is
+    /// typically of the form `NAME=value`.
+    #[inline]
+    pub fn env(&self) -> CStrArray {
+        CStrArray { next: self.envp }
+    }
+
+    /// Returns the auxiliary vector, excluding its terminating `AT_NULL`
+    /// entry.
+    pub fn auxv(&self) -> &'static [auxv_t] {
+        let mut len = 0;
+        unsafe {
+            while (*self.auxv.add(len)).a_type != crate::AT_NULL {
+                len += 1;
+            }
+            core::slice::from_raw_parts(self.auxv, len)
+        }
+    }
+
+    /// Returns the value of the first entry in the auxiliary vector of the
+    /// given type, or `None` if there is no such entry.
+    pub fn aux_value(&self, typ: ulong) -> Option<ulong> {
+        self.auxv()
+            .iter()
+            .find(|entry| entry.a_type == typ)
+            .map(|entry| entry.a_val)
+    }
+
+    /// Returns the system page size, from the `AT_PAGESZ` entry.
+    #[inline]
+    pub fn page_size(&self) -> Option<usize> {
+        self.aux_value(crate::AT_PAGESZ).map(|v| v as usize)
+    }
+
+    /// Returns the hardware capability bits from the `AT_HWCAP` entry.
+    ///
+    /// The meaning of the bits is architecture-specific.
+    #[inline]
+    pub fn hwcap(&self) -> Option<ulong> {
+        self.aux_value(crate::AT_HWCAP)
+    }
+
+    /// Returns the extended hardware capability bits from the `AT_HWCAP2`
+    /// entry.
+    ///
+    /// The meaning of the bits is architecture-specific.
+    #[inline]
+    pub fn hwcap2(&self) -> Option<ulong> {
+        self.aux_value(crate::AT_HWCAP2)
+    }
+
+    /// Returns the 16 random bytes that the kernel provides at the address
+    /// given in the `AT_RANDOM` entry.
+    #[inline]
+    pub fn random_bytes(&self) -> Option<&'static [u8; 16]> {
+        self.aux_value(crate::AT_RANDOM)
+            .map(|v| unsafe { &*(v as *const [u8; 16]) })
+    }
+
+    /// Returns the address of the vDSO from the `AT_SYSINFO_EHDR` entry,
+    /// for use with [`crate::vdso::init`].
+    #[inline]
+    pub fn vdso_ehdr(&self) -> Option<*const void> {
+        self.aux_value(crate::AT_SYSINFO_EHDR)
+            .map(|v| v as *const void)
+    }
+
+    /// Returns true if the `AT_SECURE` entry indicates that the program
+    /// is running in "secure mode", such as when it's setuid.
+    ///
+    /// Programs in secure mode should avoid trusting their environment.
+    #[inline]
+    pub fn secure(&self) -> bool {
+        self.aux_value(crate::AT_SECURE).is_some_and(|v| v != 0)
+    }
+
+    /// Returns the path used to execute the program, from the `AT_EXECFN`
+    /// entry.
+    #[inline]
+    pub fn exec_fn(&self) -> Option<&'static CStr> {
+        self.aux_value(crate::AT_EXECFN)
+            .map(|v| unsafe { CStr::from_ptr(v as *const char) })
+    }
+}
+
+/// Iterator over a null-terminated array of pointers to C strings, such
+/// as the command line arguments or environment variables.
+#[derive(Clone, Debug)]
+pub struct CStrArray {
+    next: *const *const char,
+}
+
+impl Iterator for CStrArray {
+    type Item = &'static CStr;
+
+    fn next(&mut self) -> Option<&'static CStr> {
+        let ptr = unsafe { *self.next };
+        if ptr.is_null() {
+            return None;
+        }
+        self.next = unsafe { self.next.add(1) };
+        Some(unsafe { CStr::from_ptr(ptr) })
+    }
+}
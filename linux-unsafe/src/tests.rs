@@ -23,3 +23,120 @@ fn test_getpid() {
         got, want,
     );
 }
+
+#[test]
+fn test_rt_sigaction() {
+    use core::sync::atomic::{AtomicI32, Ordering};
+
+    static CAUGHT: AtomicI32 = AtomicI32::new(0);
+    extern "C" fn handler(sig: int) {
+        CAUGHT.store(sig, Ordering::SeqCst);
+    }
+
+    let sigsetsize = core::mem::size_of::<sigset_t>();
+    let act = sigaction::new(
+        handler as *const () as sighandler_t,
+        0,
+        sigset_t::new_empty(),
+    );
+    let mut old = sigaction::new(SIG_DFL, 0, sigset_t::new_empty());
+    unsafe {
+        rt_sigaction(SIGUSR2, &act, &mut old, sigsetsize).expect("failed to set action");
+        // Sending to only the current thread ensures that the handler has
+        // run by the time tgkill returns.
+        tgkill(getpid(), gettid(), SIGUSR2).expect("failed to send signal");
+        rt_sigaction(SIGUSR2, &old, core::ptr::null_mut(), sigsetsize)
+            .expect("failed to restore action");
+    }
+    assert_eq!(CAUGHT.load(Ordering::SeqCst), SIGUSR2);
+}
+
+#[test]
+fn test_sigset() {
+    let mut set = sigset_t::new_empty();
+    assert!(set.is_empty());
+    set.sigaddset(SIGTERM).unwrap();
+    set.sigaddset(SIGRTMAX).unwrap();
+    assert!(set.sigaddset(SIGRTMAX + 1).is_err());
+    assert!(set.sigaddset(0).is_err());
+    assert_eq!(
+        set.iter().collect::<std::vec::Vec<_>>(),
+        [SIGTERM, SIGRTMAX]
+    );
+
+    let other: sigset_t = [SIGTERM, SIGCHLD].into_iter().collect();
+    let both = set.intersect(other);
+    assert_eq!(both.iter().collect::<std::vec::Vec<_>>(), [SIGTERM]);
+    let either = set.union(other);
+    assert_eq!(
+        either.iter().collect::<std::vec::Vec<_>>(),
+        [SIGTERM, SIGCHLD, SIGRTMAX]
+    );
+    let neither = either.complement();
+    assert_eq!(neither.iter().count(), _NSIG - 3);
+    assert!(!neither.sigismember(SIGCHLD).unwrap());
+    assert!(neither.sigismember(SIGRTMIN).unwrap());
+}
+
+#[test]
+fn test_vdso() {
+    let mut before = timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    let mut fast = before;
+    let mut after = before;
+    unsafe {
+        clock_gettime(CLOCK_MONOTONIC, &mut before).unwrap();
+        vdso::clock_gettime(CLOCK_MONOTONIC, &mut fast).unwrap();
+        clock_gettime(CLOCK_MONOTONIC, &mut after).unwrap();
+    }
+    let nanos = |ts: &timespec| ts.tv_sec as i128 * 1_000_000_000 + ts.tv_nsec as i128;
+    assert!(nanos(&before) <= nanos(&fast), "vDSO time is too early");
+    assert!(nanos(&fast) <= nanos(&after), "vDSO time is too late");
+
+    let t = unsafe { vdso::time(core::ptr::null_mut()) }.unwrap();
+    assert!(t > 0, "vDSO time is not after the epoch");
+
+    #[cfg(target_arch = "x86_64")]
+    {
+        let ehdr = unsafe { vdso::find_ehdr() };
+        assert!(!ehdr.is_null(), "vDSO not found in auxv");
+        let parsed = unsafe { vdso::Vdso::from_ehdr(ehdr) }.expect("failed to parse vDSO");
+        assert!(parsed
+            .lookup(b"__vdso_clock_gettime", b"LINUX_2.6")
+            .is_some());
+        assert!(parsed.lookup(b"__vdso_clock_gettime", b"NOPE").is_none());
+        assert!(parsed.lookup(b"nonexistent", b"LINUX_2.6").is_none());
+    }
+}
+
+#[test]
+fn test_startup_info() {
+    let arg0 = c"prog";
+    let env0 = c"FOO=bar";
+    let random = [7_u8; 16];
+    // A synthetic initial stack in the same layout the kernel uses.
+    let stack: [usize; 11] = [
+        1,
+        arg0.as_ptr() as usize,
+        0,
+        env0.as_ptr() as usize,
+        0,
+        AT_PAGESZ as usize,
+        4096,
+        AT_RANDOM as usize,
+        &random as *const _ as usize,
+        AT_NULL as usize,
+        0,
+    ];
+    let info = unsafe { startup::StartupInfo::from_stack(stack.as_ptr() as *const void) };
+    assert_eq!(info.argc(), 1);
+    assert_eq!(info.args().collect::<std::vec::Vec<_>>(), [arg0]);
+    assert_eq!(info.env().collect::<std::vec::Vec<_>>(), [env0]);
+    assert_eq!(info.auxv().len(), 2);
+    assert_eq!(info.page_size(), Some(4096));
+    assert_eq!(info.random_bytes(), Some(&random));
+    assert_eq!(info.hwcap(), None);
+    assert!(!info.secure());
+}
@@ -21,10 +21,10 @@ pub type long = ffi::c_long;
 pub type ulong = ffi::c_ulong;
 
 /// The signed long long integer type for the current platform.
-pub type longlong = ffi::c_long;
+pub type longlong = ffi::c_longlong;
 
 /// The unsigned long long integer type for the current platform.
-pub type ulonglong = ffi::c_ulong;
+pub type ulonglong = ffi::c_ulonglong;
 
 /// The signed size type (or "pointer difference" type) for the current platform.
 pub type ssize_t = isize;
@@ -44,6 +44,9 @@ pub type void = ffi::c_void;
 /// The type used to represent file modes on the current platform.
 pub type mode_t = uint;
 
+/// The type used to represent device numbers in the raw system calls.
+pub type dev_t = uint;
+
 /// The type used to represent file sizes and offsets into files on the current platform.
 pub type off_t = long;
 
@@ -123,9 +126,15 @@ pub const O_TRUNC: int = 0o00001000;
 pub const O_APPEND: int = 0o00002000;
 pub const O_NONBLOCK: int = 0o00004000;
 pub const O_DSYNC: int = 0o00010000;
+// The ARM architectures use different values for a few of the open flags,
+// so those are defined in the architecture-specific types module instead.
+#[cfg(not(any(target_arch = "arm", target_arch = "aarch64")))]
 pub const O_DIRECT: int = 0o00040000;
+#[cfg(not(any(target_arch = "arm", target_arch = "aarch64")))]
 pub const O_LARGEFILE: int = 0o00100000;
+#[cfg(not(any(target_arch = "arm", target_arch = "aarch64")))]
 pub const O_DIRECTORY: int = 0o00200000;
+#[cfg(not(any(target_arch = "arm", target_arch = "aarch64")))]
 pub const O_NOFOLLOW: int = 0o00400000;
 pub const O_NOATIME: int = 0o01000000;
 pub const O_CLOEXEC: int = 0o02000000;
@@ -149,6 +158,34 @@ pub const AT_STATX_DONT_SYNC: int = 0x4000;
 pub const AT_RECURSIVE: int = 0x8000;
 pub const AT_HANDLE_FID: int = AT_REMOVEDIR;
 
+pub const RENAME_NOREPLACE: uint = 1 << 0;
+pub const RENAME_EXCHANGE: uint = 1 << 1;
+pub const RENAME_WHITEOUT: uint = 1 << 2;
+
+// Special values for the `tv_nsec` field of timestamps passed to `utimensat`.
+pub const UTIME_NOW: long = (1 << 30) - 1;
+pub const UTIME_OMIT: long = (1 << 30) - 2;
+
+// File type bits in a file mode, as used with `mknodat` and in `statx` results.
+pub const S_IFMT: mode_t = 0o170000;
+pub const S_IFSOCK: mode_t = 0o140000;
+pub const S_IFLNK: mode_t = 0o120000;
+pub const S_IFREG: mode_t = 0o100000;
+pub const S_IFBLK: mode_t = 0o060000;
+pub const S_IFDIR: mode_t = 0o040000;
+pub const S_IFCHR: mode_t = 0o020000;
+pub const S_IFIFO: mode_t = 0o010000;
+pub const S_ISUID: mode_t = 0o004000;
+pub const S_ISGID: mode_t = 0o002000;
+pub const S_ISVTX: mode_t = 0o001000;
+
+/// Combine major and minor device numbers into a [`dev_t`] value in the
+/// format expected by [`crate::mknodat`].
+#[inline(always)]
+pub const fn makedev(major: uint, minor: uint) -> dev_t {
+    ((major & 0xfff) << 8) | (minor & 0xff) | ((minor & !0xff) << 12)
+}
+
 /// A file descriptor request object for use with [`crate::poll`].
 #[derive(Clone, Copy, Debug)]
 #[repr(C)]
@@ -176,9 +213,30 @@ pub struct iovec {
     pub iov_len: size_t,
 }
 
+/// The type used for flags passed to [`crate::preadv2`] and [`crate::pwritev2`].
+pub type rwf_t = int;
+
+pub const RWF_HIPRI: rwf_t = 0x00000001;
+pub const RWF_DSYNC: rwf_t = 0x00000002;
+pub const RWF_SYNC: rwf_t = 0x00000004;
+pub const RWF_NOWAIT: rwf_t = 0x00000008;
+pub const RWF_APPEND: rwf_t = 0x00000010;
+pub const RWF_NOAPPEND: rwf_t = 0x00000020;
+pub const RWF_ATOMIC: rwf_t = 0x00000040;
+pub const RWF_DONTCACHE: rwf_t = 0x00000080;
+
+pub const SPLICE_F_MOVE: uint = 0x01;
+pub const SPLICE_F_NONBLOCK: uint = 0x02;
+pub const SPLICE_F_MORE: uint = 0x04;
+pub const SPLICE_F_GIFT: uint = 0x08;
+
 /// A type used with [`crate::epoll_ctl`].
+///
+/// On x86_64 the kernel uses a packed layout for this type, for
+/// compatibility with 32-bit x86.
 #[derive(Clone, Copy, Debug)]
-#[repr(C)]
+#[cfg_attr(target_arch = "x86_64", repr(C, packed))]
+#[cfg_attr(not(target_arch = "x86_64"), repr(C))]
 pub struct epoll_event {
     pub events: u32,
     pub data: epoll_data,
@@ -200,6 +258,30 @@ impl core::fmt::Debug for epoll_data {
     }
 }
 
+pub const EPOLL_CLOEXEC: int = O_CLOEXEC;
+
+pub const EPOLL_CTL_ADD: int = 1;
+pub const EPOLL_CTL_DEL: int = 2;
+pub const EPOLL_CTL_MOD: int = 3;
+
+// Event types and flags for the `events` field of [`epoll_event`].
+pub const EPOLLIN: u32 = 0x00000001;
+pub const EPOLLPRI: u32 = 0x00000002;
+pub const EPOLLOUT: u32 = 0x00000004;
+pub const EPOLLERR: u32 = 0x00000008;
+pub const EPOLLHUP: u32 = 0x00000010;
+pub const EPOLLNVAL: u32 = 0x00000020;
+pub const EPOLLRDNORM: u32 = 0x00000040;
+pub const EPOLLRDBAND: u32 = 0x00000080;
+pub const EPOLLWRNORM: u32 = 0x00000100;
+pub const EPOLLWRBAND: u32 = 0x00000200;
+pub const EPOLLMSG: u32 = 0x00000400;
+pub const EPOLLRDHUP: u32 = 0x00002000;
+pub const EPOLLEXCLUSIVE: u32 = 1 << 28;
+pub const EPOLLWAKEUP: u32 = 1 << 29;
+pub const EPOLLONESHOT: u32 = 1 << 30;
+pub const EPOLLET: u32 = 1 << 31;
+
 /// A type used with some [`crate::fcntl`] commands.
 #[derive(Clone, Copy, Debug)]
 #[repr(C)]
@@ -215,6 +297,71 @@ pub struct flock {
     // Sparc also has padding, but no other extra fields.
 }
 
+/// A type used with the locking commands of `fcntl64`, which has
+/// 64-bit offsets even on 32-bit platforms.
+///
+/// On 64-bit platforms this has the same layout as [`flock`].
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct flock64 {
+    pub l_type: short,
+    pub l_whence: short,
+    pub l_start: loff_t,
+    pub l_len: loff_t,
+    pub l_pid: pid_t,
+}
+
+// Lock types for the `l_type` field of [`flock`], also used as lease types.
+pub const F_RDLCK: short = 0;
+pub const F_WRLCK: short = 1;
+pub const F_UNLCK: short = 2;
+
+// Operations for [`crate::flock`].
+pub const LOCK_SH: int = 1;
+pub const LOCK_EX: int = 2;
+pub const LOCK_NB: int = 4;
+pub const LOCK_UN: int = 8;
+
+/// File descriptor flag for closing the file descriptor on `execve`.
+pub const FD_CLOEXEC: int = 1;
+
+/// A type used with the `F_GETOWN_EX` and `F_SETOWN_EX` [`crate::fcntl`] commands.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct f_owner_ex {
+    pub type_: int,
+    pub pid: pid_t,
+}
+
+pub const F_OWNER_TID: int = 0;
+pub const F_OWNER_PID: int = 1;
+pub const F_OWNER_PGRP: int = 2;
+
+// File seals, for use with memfd.
+pub const F_SEAL_SEAL: int = 0x0001;
+pub const F_SEAL_SHRINK: int = 0x0002;
+pub const F_SEAL_GROW: int = 0x0004;
+pub const F_SEAL_WRITE: int = 0x0008;
+pub const F_SEAL_FUTURE_WRITE: int = 0x0010;
+pub const F_SEAL_EXEC: int = 0x0020;
+
+// Directory change notification events, for use with `F_NOTIFY`.
+pub const DN_ACCESS: int = 0x00000001;
+pub const DN_MODIFY: int = 0x00000002;
+pub const DN_CREATE: int = 0x00000004;
+pub const DN_DELETE: int = 0x00000008;
+pub const DN_RENAME: int = 0x00000010;
+pub const DN_ATTRIB: int = 0x00000020;
+pub const DN_MULTISHOT: int = 0x80000000_u32 as int;
+
+// Write lifetime hints, for use with `F_SET_RW_HINT`.
+pub const RWH_WRITE_LIFE_NOT_SET: u64 = 0;
+pub const RWH_WRITE_LIFE_NONE: u64 = 1;
+pub const RWH_WRITE_LIFE_SHORT: u64 = 2;
+pub const RWH_WRITE_LIFE_MEDIUM: u64 = 3;
+pub const RWH_WRITE_LIFE_LONG: u64 = 4;
+pub const RWH_WRITE_LIFE_EXTREME: u64 = 5;
+
 /// The type for representing socket address families.
 pub type sa_family_t = ushort;
 
@@ -231,6 +378,430 @@ pub enum sock_type {
     SOCK_PACKET = 10,
 }
 
+// Flags that can be combined with the socket type in some system calls, or
+// used alone in the flags argument of accept4.
+pub const SOCK_CLOEXEC: int = O_CLOEXEC;
+pub const SOCK_NONBLOCK: int = O_NONBLOCK;
+
+// Values for the "how" argument of shutdown
+pub const SHUT_RD: int = 0;
+pub const SHUT_WR: int = 1;
+pub const SHUT_RDWR: int = 2;
+
+/// Describes a message for [`crate::sendmsg`] and [`crate::recvmsg`].
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct msghdr {
+    pub msg_name: *mut void,
+    pub msg_namelen: socklen_t,
+    pub msg_iov: *mut iovec,
+    pub msg_iovlen: size_t,
+    pub msg_control: *mut void,
+    pub msg_controllen: size_t,
+    pub msg_flags: int,
+}
+
+/// Describes one of several messages for [`crate::sendmmsg`] and
+/// [`crate::recvmmsg`].
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct mmsghdr {
+    pub msg_hdr: msghdr,
+    pub msg_len: uint,
+}
+
+/// The header of each ancillary data object in the `msg_control` buffer of
+/// a [`msghdr`].
+///
+/// The object's data follows the header after padding to the alignment of
+/// `size_t`, and `cmsg_len` is the length of the header plus that padding
+/// plus the data. Each object starts at a `size_t`-aligned offset.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct cmsghdr {
+    pub cmsg_len: size_t,
+    pub cmsg_level: int,
+    pub cmsg_type: int,
+}
+
+/// Process credentials, as sent and received in [`SCM_CREDENTIALS`]
+/// ancillary data objects.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct ucred {
+    pub pid: pid_t,
+    pub uid: uid_t,
+    pub gid: gid_t,
+}
+
+/// Packet information, as sent and received in [`IP_PKTINFO`] ancillary data
+/// objects. The addresses are in network byte order.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct in_pktinfo {
+    pub ipi_ifindex: int,
+    pub ipi_spec_dst: u32,
+    pub ipi_addr: u32,
+}
+
+/// Timestamps received in [`SCM_TIMESTAMPING_OLD`] ancillary data objects.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct scm_timestamping {
+    pub ts: [timespec; 3],
+}
+
+/// Timestamps received in [`SCM_TIMESTAMPING_NEW`] ancillary data objects.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct scm_timestamping64 {
+    pub ts: [kernel_timespec; 3],
+}
+
+// Flags for the send and receive system calls
+pub const MSG_OOB: int = 0x1;
+pub const MSG_PEEK: int = 0x2;
+pub const MSG_DONTROUTE: int = 0x4;
+pub const MSG_CTRUNC: int = 0x8;
+pub const MSG_PROBE: int = 0x10;
+pub const MSG_TRUNC: int = 0x20;
+pub const MSG_DONTWAIT: int = 0x40;
+pub const MSG_EOR: int = 0x80;
+pub const MSG_WAITALL: int = 0x100;
+pub const MSG_FIN: int = 0x200;
+pub const MSG_SYN: int = 0x400;
+pub const MSG_CONFIRM: int = 0x800;
+pub const MSG_RST: int = 0x1000;
+pub const MSG_ERRQUEUE: int = 0x2000;
+pub const MSG_NOSIGNAL: int = 0x4000;
+pub const MSG_MORE: int = 0x8000;
+pub const MSG_WAITFORONE: int = 0x10000;
+pub const MSG_BATCH: int = 0x40000;
+pub const MSG_ZEROCOPY: int = 0x4000000;
+pub const MSG_FASTOPEN: int = 0x20000000;
+pub const MSG_CMSG_CLOEXEC: int = 0x40000000;
+
+// Socket-level options and ancillary data types
+pub const SOL_SOCKET: int = 1;
+pub const SO_PASSCRED: int = 16;
+pub const SO_PEERCRED: int = 17;
+pub const SO_TIMESTAMP_OLD: int = 29;
+pub const SO_TIMESTAMPNS_OLD: int = 35;
+pub const SO_TIMESTAMPING_OLD: int = 37;
+pub const SO_TIMESTAMP_NEW: int = 63;
+pub const SO_TIMESTAMPNS_NEW: int = 64;
+pub const SO_TIMESTAMPING_NEW: int = 65;
+pub const SO_PEERPIDFD: int = 77;
+pub const SCM_RIGHTS: int = 1;
+pub const SCM_CREDENTIALS: int = 2;
+pub const SCM_TIMESTAMP_OLD: int = SO_TIMESTAMP_OLD;
+pub const SCM_TIMESTAMPNS_OLD: int = SO_TIMESTAMPNS_OLD;
+pub const SCM_TIMESTAMPING_OLD: int = SO_TIMESTAMPING_OLD;
+pub const SCM_TIMESTAMP_NEW: int = SO_TIMESTAMP_NEW;
+pub const SCM_TIMESTAMPNS_NEW: int = SO_TIMESTAMPNS_NEW;
+pub const SCM_TIMESTAMPING_NEW: int = SO_TIMESTAMPING_NEW;
+
+// Flags for SO_TIMESTAMPING
+pub const SOF_TIMESTAMPING_TX_HARDWARE: uint = 1 << 0;
+pub const SOF_TIMESTAMPING_TX_SOFTWARE: uint = 1 << 1;
+pub const SOF_TIMESTAMPING_RX_HARDWARE: uint = 1 << 2;
+pub const SOF_TIMESTAMPING_RX_SOFTWARE: uint = 1 << 3;
+pub const SOF_TIMESTAMPING_SOFTWARE: uint = 1 << 4;
+pub const SOF_TIMESTAMPING_SYS_HARDWARE: uint = 1 << 5;
+pub const SOF_TIMESTAMPING_RAW_HARDWARE: uint = 1 << 6;
+pub const SOF_TIMESTAMPING_OPT_ID: uint = 1 << 7;
+pub const SOF_TIMESTAMPING_TX_SCHED: uint = 1 << 8;
+pub const SOF_TIMESTAMPING_TX_ACK: uint = 1 << 9;
+pub const SOF_TIMESTAMPING_OPT_CMSG: uint = 1 << 10;
+pub const SOF_TIMESTAMPING_OPT_TSONLY: uint = 1 << 11;
+pub const SOF_TIMESTAMPING_OPT_STATS: uint = 1 << 12;
+pub const SOF_TIMESTAMPING_OPT_PKTINFO: uint = 1 << 13;
+pub const SOF_TIMESTAMPING_OPT_TX_SWHW: uint = 1 << 14;
+pub const SOF_TIMESTAMPING_BIND_PHC: uint = 1 << 15;
+pub const SOF_TIMESTAMPING_OPT_ID_TCP: uint = 1 << 16;
+
+// IP-level options and ancillary data types
+pub const SOL_IP: int = 0;
+pub const IP_PKTINFO: int = 8;
+pub const IP_MULTICAST_IF: int = 32;
+pub const IP_MULTICAST_TTL: int = 33;
+pub const IP_MULTICAST_LOOP: int = 34;
+pub const IP_ADD_MEMBERSHIP: int = 35;
+pub const IP_DROP_MEMBERSHIP: int = 36;
+
+// IPv6-level options and ancillary data types
+pub const SOL_IPV6: int = 41;
+pub const IPV6_MULTICAST_IF: int = 17;
+pub const IPV6_MULTICAST_HOPS: int = 18;
+pub const IPV6_MULTICAST_LOOP: int = 19;
+pub const IPV6_JOIN_GROUP: int = 20;
+pub const IPV6_LEAVE_GROUP: int = 21;
+
+// UDP-level options and ancillary data types
+pub const SOL_UDP: int = 17;
+pub const UDP_CORK: int = 1;
+pub const UDP_SEGMENT: int = 103;
+pub const UDP_GRO: int = 104;
+
+/// Socket address for the netlink address family.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct sockaddr_nl {
+    pub nl_family: sa_family_t,
+    pub nl_pad: ushort,
+    pub nl_pid: u32,
+    pub nl_groups: u32,
+}
+
+/// The header at the start of each netlink message.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct nlmsghdr {
+    pub nlmsg_len: u32,
+    pub nlmsg_type: u16,
+    pub nlmsg_flags: u16,
+    pub nlmsg_seq: u32,
+    pub nlmsg_pid: u32,
+}
+
+/// The payload of a netlink message of type [`NLMSG_ERROR`], which reports
+/// either an error or, when `error` is zero, an acknowledgement.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct nlmsgerr {
+    pub error: int,
+    pub msg: nlmsghdr,
+}
+
+/// The header of a netlink attribute. The length includes the header but
+/// not any padding after the attribute's data.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct nlattr {
+    pub nla_len: u16,
+    pub nla_type: u16,
+}
+
+/// The header of a routing netlink attribute, which has the same layout as
+/// [`nlattr`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct rtattr {
+    pub rta_len: ushort,
+    pub rta_type: ushort,
+}
+
+/// The fixed header of `RTM_*LINK` messages, describing a network interface.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct ifinfomsg {
+    pub ifi_family: uchar,
+    pub __ifi_pad: uchar,
+    pub ifi_type: ushort,
+    pub ifi_index: int,
+    pub ifi_flags: uint,
+    pub ifi_change: uint,
+}
+
+/// The fixed header of `RTM_*ADDR` messages, describing a network interface
+/// address.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct ifaddrmsg {
+    pub ifa_family: u8,
+    pub ifa_prefixlen: u8,
+    pub ifa_flags: u8,
+    pub ifa_scope: u8,
+    pub ifa_index: u32,
+}
+
+/// The fixed header of `RTM_*ROUTE` messages, describing a route.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct rtmsg {
+    pub rtm_family: uchar,
+    pub rtm_dst_len: uchar,
+    pub rtm_src_len: uchar,
+    pub rtm_tos: uchar,
+    pub rtm_table: uchar,
+    pub rtm_protocol: uchar,
+    pub rtm_scope: uchar,
+    pub rtm_type: uchar,
+    pub rtm_flags: uint,
+}
+
+// Netlink protocols
+pub const NETLINK_ROUTE: int = 0;
+pub const NETLINK_AUDIT: int = 9;
+pub const NETLINK_KOBJECT_UEVENT: int = 15;
+pub const NETLINK_GENERIC: int = 16;
+
+// Netlink socket options
+pub const SOL_NETLINK: int = 270;
+pub const NETLINK_ADD_MEMBERSHIP: int = 1;
+pub const NETLINK_DROP_MEMBERSHIP: int = 2;
+pub const NETLINK_PKTINFO: int = 3;
+pub const NETLINK_BROADCAST_ERROR: int = 4;
+pub const NETLINK_NO_ENOBUFS: int = 5;
+pub const NETLINK_LISTEN_ALL_NSID: int = 8;
+pub const NETLINK_LIST_MEMBERSHIPS: int = 9;
+pub const NETLINK_CAP_ACK: int = 10;
+pub const NETLINK_EXT_ACK: int = 11;
+pub const NETLINK_GET_STRICT_CHK: int = 12;
+
+// Netlink control message types
+pub const NLMSG_NOOP: u16 = 0x1;
+pub const NLMSG_ERROR: u16 = 0x2;
+pub const NLMSG_DONE: u16 = 0x3;
+pub const NLMSG_OVERRUN: u16 = 0x4;
+pub const NLMSG_MIN_TYPE: u16 = 0x10;
+
+// Netlink message flags
+pub const NLM_F_REQUEST: u16 = 0x01;
+pub const NLM_F_MULTI: u16 = 0x02;
+pub const NLM_F_ACK: u16 = 0x04;
+pub const NLM_F_ECHO: u16 = 0x08;
+pub const NLM_F_DUMP_INTR: u16 = 0x10;
+pub const NLM_F_DUMP_FILTERED: u16 = 0x20;
+pub const NLM_F_ROOT: u16 = 0x100;
+pub const NLM_F_MATCH: u16 = 0x200;
+pub const NLM_F_ATOMIC: u16 = 0x400;
+pub const NLM_F_DUMP: u16 = NLM_F_ROOT | NLM_F_MATCH;
+pub const NLM_F_REPLACE: u16 = 0x100;
+pub const NLM_F_EXCL: u16 = 0x200;
+pub const NLM_F_CREATE: u16 = 0x400;
+pub const NLM_F_APPEND: u16 = 0x800;
+pub const NLM_F_NONREC: u16 = 0x100;
+pub const NLM_F_BULK: u16 = 0x200;
+pub const NLM_F_CAPPED: u16 = 0x100;
+pub const NLM_F_ACK_TLVS: u16 = 0x200;
+
+// Netlink attribute type flags
+pub const NLA_F_NESTED: u16 = 1 << 15;
+pub const NLA_F_NET_BYTEORDER: u16 = 1 << 14;
+pub const NLA_TYPE_MASK: u16 = !(NLA_F_NESTED | NLA_F_NET_BYTEORDER);
+
+// Routing netlink message types
+pub const RTM_NEWLINK: u16 = 16;
+pub const RTM_DELLINK: u16 = 17;
+pub const RTM_GETLINK: u16 = 18;
+pub const RTM_SETLINK: u16 = 19;
+pub const RTM_NEWADDR: u16 = 20;
+pub const RTM_DELADDR: u16 = 21;
+pub const RTM_GETADDR: u16 = 22;
+pub const RTM_NEWROUTE: u16 = 24;
+pub const RTM_DELROUTE: u16 = 25;
+pub const RTM_GETROUTE: u16 = 26;
+
+// Routing netlink multicast groups, for NETLINK_ADD_MEMBERSHIP
+pub const RTNLGRP_LINK: u32 = 1;
+pub const RTNLGRP_IPV4_IFADDR: u32 = 5;
+pub const RTNLGRP_IPV4_ROUTE: u32 = 7;
+pub const RTNLGRP_IPV6_IFADDR: u32 = 9;
+pub const RTNLGRP_IPV6_ROUTE: u32 = 11;
+
+// Link attributes
+pub const IFLA_UNSPEC: u16 = 0;
+pub const IFLA_ADDRESS: u16 = 1;
+pub const IFLA_BROADCAST: u16 = 2;
+pub const IFLA_IFNAME: u16 = 3;
+pub const IFLA_MTU: u16 = 4;
+pub const IFLA_LINK: u16 = 5;
+pub const IFLA_QDISC: u16 = 6;
+pub const IFLA_STATS: u16 = 7;
+pub const IFLA_MASTER: u16 = 10;
+pub const IFLA_TXQLEN: u16 = 13;
+pub const IFLA_OPERSTATE: u16 = 16;
+pub const IFLA_LINKMODE: u16 = 17;
+pub const IFLA_LINKINFO: u16 = 18;
+pub const IFLA_GROUP: u16 = 27;
+pub const IFLA_MIN_MTU: u16 = 50;
+pub const IFLA_MAX_MTU: u16 = 51;
+
+// Interface flags, for ifi_flags and ifi_change
+pub const IFF_UP: uint = 0x1;
+pub const IFF_BROADCAST: uint = 0x2;
+pub const IFF_DEBUG: uint = 0x4;
+pub const IFF_LOOPBACK: uint = 0x8;
+pub const IFF_POINTOPOINT: uint = 0x10;
+pub const IFF_NOTRAILERS: uint = 0x20;
+pub const IFF_RUNNING: uint = 0x40;
+pub const IFF_NOARP: uint = 0x80;
+pub const IFF_PROMISC: uint = 0x100;
+pub const IFF_ALLMULTI: uint = 0x200;
+pub const IFF_MULTICAST: uint = 0x1000;
+pub const IFF_LOWER_UP: uint = 0x10000;
+pub const IFF_DORMANT: uint = 0x20000;
+
+// Interface address attributes
+pub const IFA_UNSPEC: u16 = 0;
+pub const IFA_ADDRESS: u16 = 1;
+pub const IFA_LOCAL: u16 = 2;
+pub const IFA_LABEL: u16 = 3;
+pub const IFA_BROADCAST: u16 = 4;
+pub const IFA_ANYCAST: u16 = 5;
+pub const IFA_CACHEINFO: u16 = 6;
+pub const IFA_MULTICAST: u16 = 7;
+pub const IFA_FLAGS: u16 = 8;
+
+// Interface address flags, for ifa_flags and IFA_FLAGS
+pub const IFA_F_SECONDARY: u32 = 0x01;
+pub const IFA_F_NODAD: u32 = 0x02;
+pub const IFA_F_OPTIMISTIC: u32 = 0x04;
+pub const IFA_F_DADFAILED: u32 = 0x08;
+pub const IFA_F_HOMEADDRESS: u32 = 0x10;
+pub const IFA_F_DEPRECATED: u32 = 0x20;
+pub const IFA_F_TENTATIVE: u32 = 0x40;
+pub const IFA_F_PERMANENT: u32 = 0x80;
+pub const IFA_F_NOPREFIXROUTE: u32 = 0x200;
+
+// Route attributes
+pub const RTA_UNSPEC: u16 = 0;
+pub const RTA_DST: u16 = 1;
+pub const RTA_SRC: u16 = 2;
+pub const RTA_IIF: u16 = 3;
+pub const RTA_OIF: u16 = 4;
+pub const RTA_GATEWAY: u16 = 5;
+pub const RTA_PRIORITY: u16 = 6;
+pub const RTA_PREFSRC: u16 = 7;
+pub const RTA_METRICS: u16 = 8;
+pub const RTA_MULTIPATH: u16 = 9;
+pub const RTA_FLOW: u16 = 11;
+pub const RTA_TABLE: u16 = 15;
+pub const RTA_MARK: u16 = 16;
+pub const RTA_PREF: u16 = 20;
+
+// Route types, for rtm_type
+pub const RTN_UNSPEC: uchar = 0;
+pub const RTN_UNICAST: uchar = 1;
+pub const RTN_LOCAL: uchar = 2;
+pub const RTN_BROADCAST: uchar = 3;
+pub const RTN_ANYCAST: uchar = 4;
+pub const RTN_MULTICAST: uchar = 5;
+pub const RTN_BLACKHOLE: uchar = 6;
+pub const RTN_UNREACHABLE: uchar = 7;
+pub const RTN_PROHIBIT: uchar = 8;
+
+// Route origins, for rtm_protocol
+pub const RTPROT_UNSPEC: uchar = 0;
+pub const RTPROT_REDIRECT: uchar = 1;
+pub const RTPROT_KERNEL: uchar = 2;
+pub const RTPROT_BOOT: uchar = 3;
+pub const RTPROT_STATIC: uchar = 4;
+
+// Route and address scopes, for rtm_scope and ifa_scope
+pub const RT_SCOPE_UNIVERSE: uchar = 0;
+pub const RT_SCOPE_SITE: uchar = 200;
+pub const RT_SCOPE_LINK: uchar = 253;
+pub const RT_SCOPE_HOST: uchar = 254;
+pub const RT_SCOPE_NOWHERE: uchar = 255;
+
+// Routing tables, for rtm_table and RTA_TABLE
+pub const RT_TABLE_UNSPEC: uchar = 0;
+pub const RT_TABLE_DEFAULT: uchar = 253;
+pub const RT_TABLE_MAIN: uchar = 254;
+pub const RT_TABLE_LOCAL: uchar = 255;
+
 /// Used for time in seconds.
 pub type time_t = long;
 
@@ -245,6 +816,149 @@ pub struct timespec {
     pub tv_nsec: long,
 }
 
+/// Representation of time as separate seconds and nanoseconds, with 64-bit
+/// fields on all platforms.
+///
+/// This has the same layout as [`timespec`] on 64-bit platforms. System calls
+/// added after the kernel's transition to 64-bit time use this type even on
+/// 32-bit platforms.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct kernel_timespec {
+    pub tv_sec: i64,
+    pub tv_nsec: longlong,
+}
+
+// Memory protection flags for mmap and mprotect.
+pub const PROT_NONE: int = 0x0;
+pub const PROT_READ: int = 0x1;
+pub const PROT_WRITE: int = 0x2;
+pub const PROT_EXEC: int = 0x4;
+pub const PROT_GROWSDOWN: int = 0x01000000;
+pub const PROT_GROWSUP: int = 0x02000000;
+
+// Flags for mmap.
+pub const MAP_SHARED: int = 0x01;
+pub const MAP_PRIVATE: int = 0x02;
+pub const MAP_SHARED_VALIDATE: int = 0x03;
+pub const MAP_FIXED: int = 0x10;
+pub const MAP_ANONYMOUS: int = 0x20;
+pub const MAP_ANON: int = MAP_ANONYMOUS;
+pub const MAP_GROWSDOWN: int = 0x0100;
+pub const MAP_DENYWRITE: int = 0x0800;
+pub const MAP_EXECUTABLE: int = 0x1000;
+pub const MAP_LOCKED: int = 0x2000;
+pub const MAP_NORESERVE: int = 0x4000;
+pub const MAP_POPULATE: int = 0x8000;
+pub const MAP_NONBLOCK: int = 0x10000;
+pub const MAP_STACK: int = 0x20000;
+pub const MAP_HUGETLB: int = 0x40000;
+pub const MAP_SYNC: int = 0x80000;
+pub const MAP_FIXED_NOREPLACE: int = 0x100000;
+pub const MAP_UNINITIALIZED: int = 0x4000000;
+
+// With MAP_HUGETLB, the page size to use is encoded as the base-2 logarithm
+// of the size shifted left by MAP_HUGE_SHIFT.
+pub const MAP_HUGE_SHIFT: int = 26;
+pub const MAP_HUGE_MASK: int = 0x3f;
+pub const MAP_HUGE_64KB: int = 16 << MAP_HUGE_SHIFT;
+pub const MAP_HUGE_2MB: int = 21 << MAP_HUGE_SHIFT;
+pub const MAP_HUGE_32MB: int = 25 << MAP_HUGE_SHIFT;
+pub const MAP_HUGE_1GB: int = 30 << MAP_HUGE_SHIFT;
+
+// Flags for mremap.
+pub const MREMAP_MAYMOVE: int = 1;
+pub const MREMAP_FIXED: int = 2;
+pub const MREMAP_DONTUNMAP: int = 4;
+
+// Advice values for madvise.
+pub const MADV_NORMAL: int = 0;
+pub const MADV_RANDOM: int = 1;
+pub const MADV_SEQUENTIAL: int = 2;
+pub const MADV_WILLNEED: int = 3;
+pub const MADV_DONTNEED: int = 4;
+pub const MADV_FREE: int = 8;
+pub const MADV_REMOVE: int = 9;
+pub const MADV_DONTFORK: int = 10;
+pub const MADV_DOFORK: int = 11;
+pub const MADV_MERGEABLE: int = 12;
+pub const MADV_UNMERGEABLE: int = 13;
+pub const MADV_HUGEPAGE: int = 14;
+pub const MADV_NOHUGEPAGE: int = 15;
+pub const MADV_DONTDUMP: int = 16;
+pub const MADV_DODUMP: int = 17;
+pub const MADV_WIPEONFORK: int = 18;
+pub const MADV_KEEPONFORK: int = 19;
+pub const MADV_COLD: int = 20;
+pub const MADV_PAGEOUT: int = 21;
+pub const MADV_POPULATE_READ: int = 22;
+pub const MADV_POPULATE_WRITE: int = 23;
+pub const MADV_DONTNEED_LOCKED: int = 24;
+pub const MADV_COLLAPSE: int = 25;
+pub const MADV_HWPOISON: int = 100;
+pub const MADV_SOFT_OFFLINE: int = 101;
+
+// Flags for msync.
+pub const MS_ASYNC: int = 1;
+pub const MS_INVALIDATE: int = 2;
+pub const MS_SYNC: int = 4;
+
+// Flags for mlock2 and mlockall.
+pub const MLOCK_ONFAULT: uint = 1;
+pub const MCL_CURRENT: int = 1;
+pub const MCL_FUTURE: int = 2;
+pub const MCL_ONFAULT: int = 4;
+
+// Flags for memfd_create.
+pub const MFD_CLOEXEC: uint = 0x0001;
+pub const MFD_ALLOW_SEALING: uint = 0x0002;
+pub const MFD_HUGETLB: uint = 0x0004;
+pub const MFD_NOEXEC_SEAL: uint = 0x0008;
+pub const MFD_EXEC: uint = 0x0010;
+
+/// Identifies a clock for use with functions like [`crate::clock_gettime`].
+pub type clockid_t = int;
+
+pub const CLOCK_REALTIME: clockid_t = 0;
+pub const CLOCK_MONOTONIC: clockid_t = 1;
+pub const CLOCK_PROCESS_CPUTIME_ID: clockid_t = 2;
+pub const CLOCK_THREAD_CPUTIME_ID: clockid_t = 3;
+pub const CLOCK_MONOTONIC_RAW: clockid_t = 4;
+pub const CLOCK_REALTIME_COARSE: clockid_t = 5;
+pub const CLOCK_MONOTONIC_COARSE: clockid_t = 6;
+pub const CLOCK_BOOTTIME: clockid_t = 7;
+pub const CLOCK_REALTIME_ALARM: clockid_t = 8;
+pub const CLOCK_BOOTTIME_ALARM: clockid_t = 9;
+pub const CLOCK_TAI: clockid_t = 11;
+
+/// The low bits of a [`clockid_t`] that represents a dynamic clock.
+pub const CLOCKFD: clockid_t = 3;
+
+/// Returns the dynamic clock id for an open posix clock device, such as a
+/// PTP hardware clock at `/dev/ptp0`.
+#[inline(always)]
+pub const fn fd_to_clockid(fd: int) -> clockid_t {
+    (!fd << 3) | CLOCKFD
+}
+
+/// Flag for [`crate::clock_nanosleep`] and timer functions to treat the given
+/// time as absolute rather than relative.
+pub const TIMER_ABSTIME: int = 1;
+
+/// A timer setting, consisting of an initial expiration time and an interval
+/// for subsequent expirations, used with [`crate::timerfd_settime`].
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct itimerspec {
+    pub it_interval: timespec,
+    pub it_value: timespec,
+}
+
+pub const TFD_TIMER_ABSTIME: int = 1 << 0;
+pub const TFD_TIMER_CANCEL_ON_SET: int = 1 << 1;
+pub const TFD_CLOEXEC: int = O_CLOEXEC;
+pub const TFD_NONBLOCK: int = O_NONBLOCK;
+
 /// Representation of time as separate seconds and microseconds.
 #[derive(Clone, Copy, Debug)]
 #[repr(C)]
@@ -253,6 +967,14 @@ pub struct timeval {
     pub tv_usec: suseconds_t,
 }
 
+/// Obsolete timezone information used with [`crate::gettimeofday`].
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct timezone {
+    pub tz_minuteswest: int,
+    pub tz_dsttime: int,
+}
+
 /// Used for [`crate::getdents`].
 #[derive(Debug)]
 #[repr(C)]
@@ -315,15 +1037,29 @@ pub struct statx {
     pub stx_mnt_id: u64,
     pub stx_dio_mem_align: u32,
     pub stx_dio_offset_align: u32,
+    pub stx_subvol: u64,
+    pub stx_atomic_write_unit_min: u32,
+    pub stx_atomic_write_unit_max: u32,
+    pub stx_atomic_write_segments_max: u32,
+    pub stx_dio_read_offset_align: u32,
+
+    // The kernel always writes the full 256-byte structure, so this
+    // reserves space for fields added in future kernel versions.
+    __spare3: [u64; 9],
 }
 
+static_assertions::const_assert_eq!(core::mem::size_of::<statx>(), 256);
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug)]
 pub struct statx_timestamp {
     pub tv_sec: i64,
     pub tv_nsec: u32,
+    pub __reserved: i32,
 }
 
+static_assertions::assert_eq_size!(statx_timestamp, [u8; 16]);
+
 pub const STATX_TYPE: u32 = 1;
 pub const STATX_MODE: u32 = 2;
 pub const STATX_NLINK: u32 = 4;
@@ -338,6 +1074,12 @@ pub const STATX_BLOCKS: u32 = 0x400;
 pub const STATX_BASIC_STATS: u32 = 0x7ff;
 pub const STATX_BTIME: u32 = 0x800;
 pub const STATX_ALL: u32 = 0xfff;
+pub const STATX_MNT_ID: u32 = 0x1000;
+pub const STATX_DIOALIGN: u32 = 0x2000;
+pub const STATX_MNT_ID_UNIQUE: u32 = 0x4000;
+pub const STATX_SUBVOL: u32 = 0x8000;
+pub const STATX_WRITE_ATOMIC: u32 = 0x10000;
+pub const STATX_DIO_READ_ALIGN: u32 = 0x20000;
 pub const STATX_ATTR_COMPRESSED: u64 = 0x4;
 pub const STATX_ATTR_IMMUTABLE: u64 = 0x10;
 pub const STATX_ATTR_APPEND: u64 = 0x20;
@@ -347,6 +1089,7 @@ pub const STATX_ATTR_AUTOMOUNT: u64 = 0x1000;
 pub const STATX_ATTR_MOUNT_ROOT: u64 = 0x2000;
 pub const STATX_ATTR_VERITY: u64 = 0x100000;
 pub const STATX_ATTR_DAX: u64 = 0x200000;
+pub const STATX_ATTR_WRITE_ATOMIC: u64 = 0x400000;
 
 /// Used for [`crate::io_uring_setup`].
 #[derive(Clone, Copy, Debug)]
@@ -392,7 +1135,814 @@ pub struct io_cqring_offsets {
     pub resv: [u32; 3],
 }
 
-pub use crate::sigset::sigset_t;
+/// A submission queue entry for an io_uring instance.
+///
+/// The kernel's definition uses unions for several of these fields, whose
+/// meaning depends on `opcode`. This definition names each such field after
+/// its most common use, and the comments list the others.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C)]
+pub struct io_uring_sqe {
+    pub opcode: u8,
+    pub flags: u8,
+    pub ioprio: u16,
+    pub fd: i32,
+    /// Also `addr2` and `cmd_op`.
+    pub off: u64,
+    /// Also `splice_off_in`.
+    pub addr: u64,
+    pub len: u32,
+    /// Also `rw_flags`, `fsync_flags`, `poll32_events`, `timeout_flags`,
+    /// `accept_flags`, `open_flags`, `statx_flags`, `msg_flags`, etc.
+    pub op_flags: u32,
+    pub user_data: u64,
+    /// Also `buf_group`.
+    pub buf_index: u16,
+    pub personality: u16,
+    /// Also `file_index` and `addr_len`.
+    pub splice_fd_in: i32,
+    pub addr3: u64,
+    pub __pad2: [u64; 1],
+}
+
+static_assertions::assert_eq_size!(io_uring_sqe, [u8; 64]);
+
+/// A completion queue entry for an io_uring instance.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C)]
+pub struct io_uring_cqe {
+    pub user_data: u64,
+    pub res: i32,
+    pub flags: u32,
+}
+
+/// Used with [`crate::io_uring_register`] and [`IORING_REGISTER_FILES_UPDATE`].
+#[derive(Clone, Copy, Debug)]
+#[repr(C, align(8))]
+pub struct io_uring_files_update {
+    pub offset: u32,
+    pub resv: u32,
+    pub fds: u64,
+}
+
+// io_uring_setup flags
+pub const IORING_SETUP_IOPOLL: u32 = 1 << 0;
+pub const IORING_SETUP_SQPOLL: u32 = 1 << 1;
+pub const IORING_SETUP_SQ_AFF: u32 = 1 << 2;
+pub const IORING_SETUP_CQSIZE: u32 = 1 << 3;
+pub const IORING_SETUP_CLAMP: u32 = 1 << 4;
+pub const IORING_SETUP_ATTACH_WQ: u32 = 1 << 5;
+pub const IORING_SETUP_R_DISABLED: u32 = 1 << 6;
+pub const IORING_SETUP_SUBMIT_ALL: u32 = 1 << 7;
+pub const IORING_SETUP_COOP_TASKRUN: u32 = 1 << 8;
+pub const IORING_SETUP_TASKRUN_FLAG: u32 = 1 << 9;
+pub const IORING_SETUP_SQE128: u32 = 1 << 10;
+pub const IORING_SETUP_CQE32: u32 = 1 << 11;
+pub const IORING_SETUP_SINGLE_ISSUER: u32 = 1 << 12;
+pub const IORING_SETUP_DEFER_TASKRUN: u32 = 1 << 13;
+pub const IORING_SETUP_NO_MMAP: u32 = 1 << 14;
+pub const IORING_SETUP_REGISTERED_FD_ONLY: u32 = 1 << 15;
+pub const IORING_SETUP_NO_SQARRAY: u32 = 1 << 16;
+
+// io_uring_params features
+pub const IORING_FEAT_SINGLE_MMAP: u32 = 1 << 0;
+pub const IORING_FEAT_NODROP: u32 = 1 << 1;
+pub const IORING_FEAT_SUBMIT_STABLE: u32 = 1 << 2;
+pub const IORING_FEAT_RW_CUR_POS: u32 = 1 << 3;
+pub const IORING_FEAT_CUR_PERSONALITY: u32 = 1 << 4;
+pub const IORING_FEAT_FAST_POLL: u32 = 1 << 5;
+pub const IORING_FEAT_POLL_32BITS: u32 = 1 << 6;
+pub const IORING_FEAT_SQPOLL_NONFIXED: u32 = 1 << 7;
+pub const IORING_FEAT_EXT_ARG: u32 = 1 << 8;
+pub const IORING_FEAT_NATIVE_WORKERS: u32 = 1 << 9;
+pub const IORING_FEAT_RSRC_TAGS: u32 = 1 << 10;
+pub const IORING_FEAT_CQE_SKIP: u32 = 1 << 11;
+pub const IORING_FEAT_LINKED_FILE: u32 = 1 << 12;
+
+// mmap offsets for the io_uring rings
+pub const IORING_OFF_SQ_RING: off_t = 0;
+pub const IORING_OFF_CQ_RING: off_t = 0x8000000;
+pub const IORING_OFF_SQES: off_t = 0x10000000;
+
+// io_uring_sqe opcodes
+pub const IORING_OP_NOP: u8 = 0;
+pub const IORING_OP_READV: u8 = 1;
+pub const IORING_OP_WRITEV: u8 = 2;
+pub const IORING_OP_FSYNC: u8 = 3;
+pub const IORING_OP_READ_FIXED: u8 = 4;
+pub const IORING_OP_WRITE_FIXED: u8 = 5;
+pub const IORING_OP_POLL_ADD: u8 = 6;
+pub const IORING_OP_POLL_REMOVE: u8 = 7;
+pub const IORING_OP_SYNC_FILE_RANGE: u8 = 8;
+pub const IORING_OP_SENDMSG: u8 = 9;
+pub const IORING_OP_RECVMSG: u8 = 10;
+pub const IORING_OP_TIMEOUT: u8 = 11;
+pub const IORING_OP_TIMEOUT_REMOVE: u8 = 12;
+pub const IORING_OP_ACCEPT: u8 = 13;
+pub const IORING_OP_ASYNC_CANCEL: u8 = 14;
+pub const IORING_OP_LINK_TIMEOUT: u8 = 15;
+pub const IORING_OP_CONNECT: u8 = 16;
+pub const IORING_OP_FALLOCATE: u8 = 17;
+pub const IORING_OP_OPENAT: u8 = 18;
+pub const IORING_OP_CLOSE: u8 = 19;
+pub const IORING_OP_FILES_UPDATE: u8 = 20;
+pub const IORING_OP_STATX: u8 = 21;
+pub const IORING_OP_READ: u8 = 22;
+pub const IORING_OP_WRITE: u8 = 23;
+pub const IORING_OP_FADVISE: u8 = 24;
+pub const IORING_OP_MADVISE: u8 = 25;
+pub const IORING_OP_SEND: u8 = 26;
+pub const IORING_OP_RECV: u8 = 27;
+pub const IORING_OP_OPENAT2: u8 = 28;
+pub const IORING_OP_EPOLL_CTL: u8 = 29;
+pub const IORING_OP_SPLICE: u8 = 30;
+pub const IORING_OP_PROVIDE_BUFFERS: u8 = 31;
+pub const IORING_OP_REMOVE_BUFFERS: u8 = 32;
+pub const IORING_OP_TEE: u8 = 33;
+pub const IORING_OP_SHUTDOWN: u8 = 34;
+pub const IORING_OP_RENAMEAT: u8 = 35;
+pub const IORING_OP_UNLINKAT: u8 = 36;
+pub const IORING_OP_MKDIRAT: u8 = 37;
+pub const IORING_OP_SYMLINKAT: u8 = 38;
+pub const IORING_OP_LINKAT: u8 = 39;
+pub const IORING_OP_MSG_RING: u8 = 40;
+pub const IORING_OP_FSETXATTR: u8 = 41;
+pub const IORING_OP_SETXATTR: u8 = 42;
+pub const IORING_OP_FGETXATTR: u8 = 43;
+pub const IORING_OP_GETXATTR: u8 = 44;
+pub const IORING_OP_SOCKET: u8 = 45;
+pub const IORING_OP_URING_CMD: u8 = 46;
+pub const IORING_OP_SEND_ZC: u8 = 47;
+pub const IORING_OP_SENDMSG_ZC: u8 = 48;
+
+// io_uring_sqe flags
+pub const IOSQE_FIXED_FILE: u8 = 1 << 0;
+pub const IOSQE_IO_DRAIN: u8 = 1 << 1;
+pub const IOSQE_IO_LINK: u8 = 1 << 2;
+pub const IOSQE_IO_HARDLINK: u8 = 1 << 3;
+pub const IOSQE_ASYNC: u8 = 1 << 4;
+pub const IOSQE_BUFFER_SELECT: u8 = 1 << 5;
+pub const IOSQE_CQE_SKIP_SUCCESS: u8 = 1 << 6;
+
+// io_uring_sqe op_flags for IORING_OP_FSYNC
+pub const IORING_FSYNC_DATASYNC: u32 = 1 << 0;
+
+// io_uring_sqe op_flags for IORING_OP_TIMEOUT
+pub const IORING_TIMEOUT_ABS: u32 = 1 << 0;
+pub const IORING_TIMEOUT_UPDATE: u32 = 1 << 1;
+pub const IORING_TIMEOUT_BOOTTIME: u32 = 1 << 2;
+pub const IORING_TIMEOUT_REALTIME: u32 = 1 << 3;
+pub const IORING_LINK_TIMEOUT_UPDATE: u32 = 1 << 4;
+pub const IORING_TIMEOUT_ETIME_SUCCESS: u32 = 1 << 5;
+pub const IORING_TIMEOUT_MULTISHOT: u32 = 1 << 6;
+
+// Used in io_uring_sqe splice_fd_in to ask the kernel to choose a free
+// slot in the registered file table.
+pub const IORING_FILE_INDEX_ALLOC: u32 = !0;
+
+// io_uring_cqe flags
+pub const IORING_CQE_F_BUFFER: u32 = 1 << 0;
+pub const IORING_CQE_F_MORE: u32 = 1 << 1;
+pub const IORING_CQE_F_SOCK_NONEMPTY: u32 = 1 << 2;
+pub const IORING_CQE_F_NOTIF: u32 = 1 << 3;
+
+// io_sqring_offsets flags
+pub const IORING_SQ_NEED_WAKEUP: u32 = 1 << 0;
+pub const IORING_SQ_CQ_OVERFLOW: u32 = 1 << 1;
+pub const IORING_SQ_TASKRUN: u32 = 1 << 2;
+
+// io_cqring_offsets flags
+pub const IORING_CQ_EVENTFD_DISABLED: u32 = 1 << 0;
+
+// io_uring_enter flags
+pub const IORING_ENTER_GETEVENTS: uint = 1 << 0;
+pub const IORING_ENTER_SQ_WAKEUP: uint = 1 << 1;
+pub const IORING_ENTER_SQ_WAIT: uint = 1 << 2;
+pub const IORING_ENTER_EXT_ARG: uint = 1 << 3;
+pub const IORING_ENTER_REGISTERED_RING: uint = 1 << 4;
+
+// io_uring_register opcodes
+pub const IORING_REGISTER_BUFFERS: uint = 0;
+pub const IORING_UNREGISTER_BUFFERS: uint = 1;
+pub const IORING_REGISTER_FILES: uint = 2;
+pub const IORING_UNREGISTER_FILES: uint = 3;
+pub const IORING_REGISTER_EVENTFD: uint = 4;
+pub const IORING_UNREGISTER_EVENTFD: uint = 5;
+pub const IORING_REGISTER_FILES_UPDATE: uint = 6;
+pub const IORING_REGISTER_EVENTFD_ASYNC: uint = 7;
+pub const IORING_REGISTER_PROBE: uint = 8;
+pub const IORING_REGISTER_PERSONALITY: uint = 9;
+pub const IORING_UNREGISTER_PERSONALITY: uint = 10;
+pub const IORING_REGISTER_RESTRICTIONS: uint = 11;
+pub const IORING_REGISTER_ENABLE_RINGS: uint = 12;
+
+/// Used for [`crate::clone3`].
+#[derive(Clone, Copy, Debug)]
+#[repr(C, align(8))]
+pub struct clone_args {
+    pub flags: u64,
+    pub pidfd: u64,
+    pub child_tid: u64,
+    pub parent_tid: u64,
+    pub exit_signal: u64,
+    pub stack: u64,
+    pub stack_size: u64,
+    pub tls: u64,
+    pub set_tid: u64,
+    pub set_tid_size: u64,
+    pub cgroup: u64,
+}
+
+pub const CLONE_VM: u64 = 0x00000100;
+pub const CLONE_FS: u64 = 0x00000200;
+pub const CLONE_FILES: u64 = 0x00000400;
+pub const CLONE_SIGHAND: u64 = 0x00000800;
+pub const CLONE_PIDFD: u64 = 0x00001000;
+pub const CLONE_PTRACE: u64 = 0x00002000;
+pub const CLONE_VFORK: u64 = 0x00004000;
+pub const CLONE_PARENT: u64 = 0x00008000;
+pub const CLONE_THREAD: u64 = 0x00010000;
+pub const CLONE_NEWNS: u64 = 0x00020000;
+pub const CLONE_SYSVSEM: u64 = 0x00040000;
+pub const CLONE_SETTLS: u64 = 0x00080000;
+pub const CLONE_PARENT_SETTID: u64 = 0x00100000;
+pub const CLONE_CHILD_CLEARTID: u64 = 0x00200000;
+pub const CLONE_DETACHED: u64 = 0x00400000;
+pub const CLONE_UNTRACED: u64 = 0x00800000;
+pub const CLONE_CHILD_SETTID: u64 = 0x01000000;
+pub const CLONE_NEWCGROUP: u64 = 0x02000000;
+pub const CLONE_NEWUTS: u64 = 0x04000000;
+pub const CLONE_NEWIPC: u64 = 0x08000000;
+pub const CLONE_NEWUSER: u64 = 0x10000000;
+pub const CLONE_NEWPID: u64 = 0x20000000;
+pub const CLONE_NEWNET: u64 = 0x40000000;
+pub const CLONE_IO: u64 = 0x80000000;
+pub const CLONE_NEWTIME: u64 = 0x00000080;
+pub const CLONE_CLEAR_SIGHAND: u64 = 0x100000000;
+pub const CLONE_INTO_CGROUP: u64 = 0x200000000;
+
+/// The type used to represent an identifier whose meaning depends on an
+/// accompanying [`idtype_t`], as used with [`crate::waitid`].
+pub type id_t = uint;
+
+/// The type used to specify how [`crate::waitid`] should interpret its `id`
+/// argument.
+pub type idtype_t = int;
+
+pub const P_ALL: idtype_t = 0;
+pub const P_PID: idtype_t = 1;
+pub const P_PGID: idtype_t = 2;
+pub const P_PIDFD: idtype_t = 3;
+
+pub const WNOHANG: int = 0x00000001;
+pub const WUNTRACED: int = 0x00000002;
+pub const WSTOPPED: int = WUNTRACED;
+pub const WEXITED: int = 0x00000004;
+pub const WCONTINUED: int = 0x00000008;
+pub const WNOWAIT: int = 0x01000000;
+pub const __WNOTHREAD: int = 0x20000000;
+pub const __WALL: int = 0x40000000;
+pub const __WCLONE: int = 0x80000000_u32 as int;
+
+// Values of `si_code` for `SIGCHLD` and for `waitid` results.
+pub const CLD_EXITED: int = 1;
+pub const CLD_KILLED: int = 2;
+pub const CLD_DUMPED: int = 3;
+pub const CLD_TRAPPED: int = 4;
+pub const CLD_STOPPED: int = 5;
+pub const CLD_CONTINUED: int = 6;
+
+/// Used for time measured in clock ticks.
+pub type clock_t = long;
+
+/// Information about a signal, or about a child process state change when
+/// used with [`crate::waitid`].
+///
+/// The content of [`Self::fields`] depends on the signal number and code, so
+/// callers must use `si_signo` and `si_code` to decide which of its union
+/// fields is active.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct siginfo_t {
+    pub si_signo: int,
+    pub si_errno: int,
+    pub si_code: int,
+    pub fields: siginfo_fields,
+}
+
+impl core::fmt::Debug for siginfo_t {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("siginfo_t")
+            .field("si_signo", &self.si_signo)
+            .field("si_errno", &self.si_errno)
+            .field("si_code", &self.si_code)
+            .finish_non_exhaustive()
+    }
+}
+
+// The kernel always uses 128 bytes for siginfo_t, but the size of the
+// "preamble" before the union depends on the platform's pointer alignment.
+const SI_MAX_SIZE: usize = 128;
+const SI_PREAMBLE_SIZE: usize = if core::mem::size_of::<ulong>() == 8 {
+    4 * core::mem::size_of::<int>()
+} else {
+    3 * core::mem::size_of::<int>()
+};
+const SI_PAD_SIZE: usize = (SI_MAX_SIZE - SI_PREAMBLE_SIZE) / core::mem::size_of::<int>();
+
+static_assertions::assert_eq_size!(siginfo_t, [u8; SI_MAX_SIZE]);
+
+/// The signal-specific part of [`siginfo_t`].
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub union siginfo_fields {
+    pub kill: siginfo_kill,
+    pub timer: siginfo_timer,
+    pub rt: siginfo_rt,
+    pub sigchld: siginfo_sigchld,
+    pub sigfault: siginfo_sigfault,
+    pub sigpoll: siginfo_sigpoll,
+    pub sigsys: siginfo_sigsys,
+    _pad: [int; SI_PAD_SIZE],
+}
+
+/// Fields of [`siginfo_t`] for signals sent by [`crate::kill`].
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct siginfo_kill {
+    pub si_pid: pid_t,
+    pub si_uid: uid_t,
+}
+
+/// Fields of [`siginfo_t`] for signals sent by POSIX timers.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct siginfo_timer {
+    pub si_tid: int,
+    pub si_overrun: int,
+    pub si_value: sigval_t,
+}
+
+/// Fields of [`siginfo_t`] for POSIX real-time signals.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct siginfo_rt {
+    pub si_pid: pid_t,
+    pub si_uid: uid_t,
+    pub si_value: sigval_t,
+}
+
+/// Fields of [`siginfo_t`] for `SIGCHLD` and for [`crate::waitid`] results.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct siginfo_sigchld {
+    pub si_pid: pid_t,
+    pub si_uid: uid_t,
+    pub si_status: int,
+    pub si_utime: clock_t,
+    pub si_stime: clock_t,
+}
+
+/// Fields of [`siginfo_t`] for `SIGILL`, `SIGFPE`, `SIGSEGV`, `SIGBUS`,
+/// and `SIGTRAP`.
+///
+/// Only the faulting address is included here, because the remaining
+/// fields vary depending on `si_code`.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct siginfo_sigfault {
+    pub si_addr: *mut void,
+}
+
+/// Fields of [`siginfo_t`] for `SIGPOLL`.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct siginfo_sigpoll {
+    pub si_band: long,
+    pub si_fd: int,
+}
+
+/// Fields of [`siginfo_t`] for `SIGSYS`.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct siginfo_sigsys {
+    pub si_call_addr: *mut void,
+    pub si_syscall: int,
+    pub si_arch: uint,
+}
+
+/// A value sent along with a signal.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub union sigval_t {
+    pub sival_int: int,
+    pub sival_ptr: *mut void,
+}
+
+impl core::fmt::Debug for sigval_t {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("sigval_t").finish_non_exhaustive()
+    }
+}
+
+/// Resource usage information, used with [`crate::wait4`] and
+/// [`crate::waitid`].
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct rusage {
+    pub ru_utime: timeval,
+    pub ru_stime: timeval,
+    pub ru_maxrss: long,
+    pub ru_ixrss: long,
+    pub ru_idrss: long,
+    pub ru_isrss: long,
+    pub ru_minflt: long,
+    pub ru_majflt: long,
+    pub ru_nswap: long,
+    pub ru_inblock: long,
+    pub ru_oublock: long,
+    pub ru_msgsnd: long,
+    pub ru_msgrcv: long,
+    pub ru_nsignals: long,
+    pub ru_nvcsw: long,
+    pub ru_nivcsw: long,
+}
+
+/// The type of the handler field of [`sigaction`].
+///
+/// This is either a pointer to a handler function or one of the special
+/// values [`SIG_DFL`] and [`SIG_IGN`]. Handler functions have the signature
+/// `extern "C" fn(int)`, or `extern "C" fn(int, *mut siginfo_t, *mut void)`
+/// if the action flags include [`SA_SIGINFO`].
+pub type sighandler_t = size_t;
+
+/// Use the default action for a signal.
+pub const SIG_DFL: sighandler_t = 0;
+/// Ignore a signal.
+pub const SIG_IGN: sighandler_t = 1;
+
+/// The kernel's representation of the action to take when a signal is
+/// delivered, for use with [`crate::rt_sigaction`].
+///
+/// The layout of this type varies by architecture, because only some
+/// architectures support [`SA_RESTORER`].
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct sigaction {
+    pub sa_handler: sighandler_t,
+    pub sa_flags: ulong,
+    #[cfg(any(
+        target_arch = "x86_64",
+        target_arch = "x86",
+        target_arch = "arm",
+        target_arch = "aarch64"
+    ))]
+    pub sa_restorer: Option<unsafe extern "C" fn() -> !>,
+    pub sa_mask: sigset_t,
+}
+
+impl sigaction {
+    /// Prepare a signal action with the given handler, flags, and mask.
+    ///
+    /// On architectures that support [`SA_RESTORER`] this also populates
+    /// `sa_restorer` with a trampoline that calls `rt_sigreturn`, which
+    /// is mandatory on x86_64 and harmless elsewhere.
+    pub const fn new(handler: sighandler_t, flags: ulong, mask: sigset_t) -> Self {
+        #[cfg(any(
+            target_arch = "x86_64",
+            target_arch = "x86",
+            target_arch = "arm",
+            target_arch = "aarch64"
+        ))]
+        {
+            Self {
+                sa_handler: handler,
+                sa_flags: flags | SA_RESTORER,
+                sa_restorer: Some(crate::raw::restore_rt),
+                sa_mask: mask,
+            }
+        }
+        #[cfg(not(any(
+            target_arch = "x86_64",
+            target_arch = "x86",
+            target_arch = "arm",
+            target_arch = "aarch64"
+        )))]
+        {
+            Self {
+                sa_handler: handler,
+                sa_flags: flags,
+                sa_mask: mask,
+            }
+        }
+    }
+}
+
+pub const SA_NOCLDSTOP: ulong = 0x00000001;
+pub const SA_NOCLDWAIT: ulong = 0x00000002;
+pub const SA_SIGINFO: ulong = 0x00000004;
+pub const SA_ONSTACK: ulong = 0x08000000;
+pub const SA_RESTART: ulong = 0x10000000;
+pub const SA_NODEFER: ulong = 0x40000000;
+pub const SA_RESETHAND: ulong = 0x80000000;
+pub const SA_NOMASK: ulong = SA_NODEFER;
+pub const SA_ONESHOT: ulong = SA_RESETHAND;
+#[cfg(any(
+    target_arch = "x86_64",
+    target_arch = "x86",
+    target_arch = "arm",
+    target_arch = "aarch64"
+))]
+pub const SA_RESTORER: ulong = 0x04000000;
+
+// Values for the "how" argument of rt_sigprocmask.
+pub const SIG_BLOCK: int = 0;
+pub const SIG_UNBLOCK: int = 1;
+pub const SIG_SETMASK: int = 2;
+
+/// Describes an alternate signal stack, for use with [`crate::sigaltstack`].
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct stack_t {
+    pub ss_sp: *mut void,
+    pub ss_flags: int,
+    pub ss_size: size_t,
+}
+
+pub const SS_ONSTACK: int = 1;
+pub const SS_DISABLE: int = 2;
+pub const SS_AUTODISARM: int = (1_u32 << 31) as int;
+
+#[cfg(not(target_arch = "aarch64"))]
+pub const MINSIGSTKSZ: size_t = 2048;
+#[cfg(not(target_arch = "aarch64"))]
+pub const SIGSTKSZ: size_t = 8192;
+#[cfg(target_arch = "aarch64")]
+pub const MINSIGSTKSZ: size_t = 5120;
+#[cfg(target_arch = "aarch64")]
+pub const SIGSTKSZ: size_t = 16384;
+
+/// The layout of the records read from a signalfd file descriptor.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct signalfd_siginfo {
+    pub ssi_signo: u32,
+    pub ssi_errno: i32,
+    pub ssi_code: i32,
+    pub ssi_pid: u32,
+    pub ssi_uid: u32,
+    pub ssi_fd: i32,
+    pub ssi_tid: u32,
+    pub ssi_band: u32,
+    pub ssi_overrun: u32,
+    pub ssi_trapno: u32,
+    pub ssi_status: i32,
+    pub ssi_int: i32,
+    pub ssi_ptr: u64,
+    pub ssi_utime: u64,
+    pub ssi_stime: u64,
+    pub ssi_addr: u64,
+    pub ssi_addr_lsb: u16,
+    pub __pad2: u16,
+    pub ssi_syscall: i32,
+    pub ssi_call_addr: u64,
+    pub ssi_arch: u32,
+    pub __pad: [u8; 28],
+}
+
+static_assertions::assert_eq_size!(signalfd_siginfo, [u8; 128]);
+
+pub const SFD_CLOEXEC: int = O_CLOEXEC;
+pub const SFD_NONBLOCK: int = O_NONBLOCK;
+
+pub const EFD_SEMAPHORE: int = 1;
+pub const EFD_CLOEXEC: int = O_CLOEXEC;
+pub const EFD_NONBLOCK: int = O_NONBLOCK;
+
+/// The fixed-size header of an event read from an inotify file descriptor.
+///
+/// In the kernel's buffer this is followed by `len` bytes containing a
+/// null-terminated filename and padding.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct inotify_event {
+    pub wd: int,
+    pub mask: u32,
+    pub cookie: u32,
+    pub len: u32,
+}
+
+pub const IN_CLOEXEC: int = O_CLOEXEC;
+pub const IN_NONBLOCK: int = O_NONBLOCK;
+
+// Event types and flags for inotify watch masks.
+pub const IN_ACCESS: u32 = 0x00000001;
+pub const IN_MODIFY: u32 = 0x00000002;
+pub const IN_ATTRIB: u32 = 0x00000004;
+pub const IN_CLOSE_WRITE: u32 = 0x00000008;
+pub const IN_CLOSE_NOWRITE: u32 = 0x00000010;
+pub const IN_OPEN: u32 = 0x00000020;
+pub const IN_MOVED_FROM: u32 = 0x00000040;
+pub const IN_MOVED_TO: u32 = 0x00000080;
+pub const IN_CREATE: u32 = 0x00000100;
+pub const IN_DELETE: u32 = 0x00000200;
+pub const IN_DELETE_SELF: u32 = 0x00000400;
+pub const IN_MOVE_SELF: u32 = 0x00000800;
+pub const IN_UNMOUNT: u32 = 0x00002000;
+pub const IN_Q_OVERFLOW: u32 = 0x00004000;
+pub const IN_IGNORED: u32 = 0x00008000;
+pub const IN_CLOSE: u32 = IN_CLOSE_WRITE | IN_CLOSE_NOWRITE;
+pub const IN_MOVE: u32 = IN_MOVED_FROM | IN_MOVED_TO;
+pub const IN_ALL_EVENTS: u32 = 0x00000fff;
+pub const IN_ONLYDIR: u32 = 0x01000000;
+pub const IN_DONT_FOLLOW: u32 = 0x02000000;
+pub const IN_EXCL_UNLINK: u32 = 0x04000000;
+pub const IN_MASK_CREATE: u32 = 0x10000000;
+pub const IN_MASK_ADD: u32 = 0x20000000;
+pub const IN_ISDIR: u32 = 0x40000000;
+pub const IN_ONESHOT: u32 = 0x80000000;
+
+/// The fixed-size header of an event read from a fanotify file descriptor.
+///
+/// In the kernel's buffer this is followed by zero or more information
+/// records, each beginning with a [`fanotify_event_info_header`], up to a
+/// total size of `event_len` bytes.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct fanotify_event_metadata {
+    pub event_len: u32,
+    pub vers: u8,
+    pub reserved: u8,
+    pub metadata_len: u16,
+    pub mask: u64,
+    pub fd: int,
+    pub pid: int,
+}
+
+static_assertions::assert_eq_size!(fanotify_event_metadata, [u8; 24]);
+
+/// The header of each information record that follows a
+/// [`fanotify_event_metadata`].
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct fanotify_event_info_header {
+    pub info_type: u8,
+    pub pad: u8,
+    pub len: u16,
+}
+
+/// The fixed-size part of a fanotify information record describing a file
+/// identifier.
+///
+/// This is followed by a variable-length `struct file_handle`, and then by a
+/// null-terminated filename for the record types that include a name.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct fanotify_event_info_fid {
+    pub hdr: fanotify_event_info_header,
+    pub fsid: [int; 2],
+}
+
+/// A fanotify information record carrying a pidfd for the process that
+/// caused an event.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct fanotify_event_info_pidfd {
+    pub hdr: fanotify_event_info_header,
+    pub pidfd: int,
+}
+
+/// A response to a fanotify permission event, written to the fanotify file
+/// descriptor.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct fanotify_response {
+    pub fd: int,
+    pub response: u32,
+}
+
+pub const FANOTIFY_METADATA_VERSION: u8 = 3;
+
+// Flags for `fanotify_init`.
+pub const FAN_CLOEXEC: uint = 0x00000001;
+pub const FAN_NONBLOCK: uint = 0x00000002;
+pub const FAN_CLASS_NOTIF: uint = 0x00000000;
+pub const FAN_CLASS_CONTENT: uint = 0x00000004;
+pub const FAN_CLASS_PRE_CONTENT: uint = 0x00000008;
+pub const FAN_UNLIMITED_QUEUE: uint = 0x00000010;
+pub const FAN_UNLIMITED_MARKS: uint = 0x00000020;
+pub const FAN_ENABLE_AUDIT: uint = 0x00000040;
+pub const FAN_REPORT_PIDFD: uint = 0x00000080;
+pub const FAN_REPORT_TID: uint = 0x00000100;
+pub const FAN_REPORT_FID: uint = 0x00000200;
+pub const FAN_REPORT_DIR_FID: uint = 0x00000400;
+pub const FAN_REPORT_NAME: uint = 0x00000800;
+pub const FAN_REPORT_TARGET_FID: uint = 0x00001000;
+pub const FAN_REPORT_DFID_NAME: uint = FAN_REPORT_DIR_FID | FAN_REPORT_NAME;
+pub const FAN_REPORT_DFID_NAME_TARGET: uint =
+    FAN_REPORT_DFID_NAME | FAN_REPORT_FID | FAN_REPORT_TARGET_FID;
+
+// Flags for `fanotify_mark`.
+pub const FAN_MARK_ADD: uint = 0x00000001;
+pub const FAN_MARK_REMOVE: uint = 0x00000002;
+pub const FAN_MARK_DONT_FOLLOW: uint = 0x00000004;
+pub const FAN_MARK_ONLYDIR: uint = 0x00000008;
+pub const FAN_MARK_IGNORED_MASK: uint = 0x00000020;
+pub const FAN_MARK_IGNORED_SURV_MODIFY: uint = 0x00000040;
+pub const FAN_MARK_FLUSH: uint = 0x00000080;
+pub const FAN_MARK_EVICTABLE: uint = 0x00000200;
+pub const FAN_MARK_IGNORE: uint = 0x00000400;
+pub const FAN_MARK_INODE: uint = 0x00000000;
+pub const FAN_MARK_MOUNT: uint = 0x00000010;
+pub const FAN_MARK_FILESYSTEM: uint = 0x00000100;
+
+// Event types and flags for fanotify masks.
+pub const FAN_ACCESS: u64 = 0x00000001;
+pub const FAN_MODIFY: u64 = 0x00000002;
+pub const FAN_ATTRIB: u64 = 0x00000004;
+pub const FAN_CLOSE_WRITE: u64 = 0x00000008;
+pub const FAN_CLOSE_NOWRITE: u64 = 0x00000010;
+pub const FAN_OPEN: u64 = 0x00000020;
+pub const FAN_MOVED_FROM: u64 = 0x00000040;
+pub const FAN_MOVED_TO: u64 = 0x00000080;
+pub const FAN_CREATE: u64 = 0x00000100;
+pub const FAN_DELETE: u64 = 0x00000200;
+pub const FAN_DELETE_SELF: u64 = 0x00000400;
+pub const FAN_MOVE_SELF: u64 = 0x00000800;
+pub const FAN_OPEN_EXEC: u64 = 0x00001000;
+pub const FAN_Q_OVERFLOW: u64 = 0x00004000;
+pub const FAN_FS_ERROR: u64 = 0x00008000;
+pub const FAN_OPEN_PERM: u64 = 0x00010000;
+pub const FAN_ACCESS_PERM: u64 = 0x00020000;
+pub const FAN_OPEN_EXEC_PERM: u64 = 0x00040000;
+pub const FAN_EVENT_ON_CHILD: u64 = 0x08000000;
+pub const FAN_RENAME: u64 = 0x10000000;
+pub const FAN_ONDIR: u64 = 0x40000000;
+pub const FAN_CLOSE: u64 = FAN_CLOSE_WRITE | FAN_CLOSE_NOWRITE;
+pub const FAN_MOVE: u64 = FAN_MOVED_FROM | FAN_MOVED_TO;
+
+// Types of the information records that follow fanotify event metadata.
+pub const FAN_EVENT_INFO_TYPE_FID: u8 = 1;
+pub const FAN_EVENT_INFO_TYPE_DFID_NAME: u8 = 2;
+pub const FAN_EVENT_INFO_TYPE_DFID: u8 = 3;
+pub const FAN_EVENT_INFO_TYPE_PIDFD: u8 = 4;
+pub const FAN_EVENT_INFO_TYPE_ERROR: u8 = 5;
+pub const FAN_EVENT_INFO_TYPE_OLD_DFID_NAME: u8 = 10;
+pub const FAN_EVENT_INFO_TYPE_NEW_DFID_NAME: u8 = 12;
+
+// Responses to fanotify permission events.
+pub const FAN_ALLOW: u32 = 0x01;
+pub const FAN_DENY: u32 = 0x02;
+pub const FAN_AUDIT: u32 = 0x10;
+
+pub const FAN_NOFD: int = -1;
+pub const FAN_NOPIDFD: int = FAN_NOFD;
+pub const FAN_EPIDFD: int = -2;
+
+/// An entry in the auxiliary vector that the kernel passes to a new program
+/// alongside its arguments and environment.
+///
+/// Use [`crate::startup::StartupInfo`] to find the auxiliary vector.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct auxv_t {
+    pub a_type: ulong,
+    pub a_val: ulong,
+}
+
+// Types of entry in the auxiliary vector.
+pub const AT_NULL: ulong = 0;
+pub const AT_IGNORE: ulong = 1;
+pub const AT_EXECFD: ulong = 2;
+pub const AT_PHDR: ulong = 3;
+pub const AT_PHENT: ulong = 4;
+pub const AT_PHNUM: ulong = 5;
+pub const AT_PAGESZ: ulong = 6;
+pub const AT_BASE: ulong = 7;
+pub const AT_FLAGS: ulong = 8;
+pub const AT_ENTRY: ulong = 9;
+pub const AT_NOTELF: ulong = 10;
+pub const AT_UID: ulong = 11;
+pub const AT_EUID: ulong = 12;
+pub const AT_GID: ulong = 13;
+pub const AT_EGID: ulong = 14;
+pub const AT_PLATFORM: ulong = 15;
+pub const AT_HWCAP: ulong = 16;
+pub const AT_CLKTCK: ulong = 17;
+pub const AT_SECURE: ulong = 23;
+pub const AT_BASE_PLATFORM: ulong = 24;
+pub const AT_RANDOM: ulong = 25;
+pub const AT_HWCAP2: ulong = 26;
+pub const AT_RSEQ_FEATURE_SIZE: ulong = 27;
+pub const AT_RSEQ_ALIGN: ulong = 28;
+pub const AT_HWCAP3: ulong = 29;
+pub const AT_HWCAP4: ulong = 30;
+pub const AT_EXECFN: ulong = 31;
+pub const AT_SYSINFO_EHDR: ulong = 33;
+pub const AT_MINSIGSTKSZ: ulong = 51;
+
+pub use crate::raw::signal::*;
+pub use crate::sigset::{sigset_t, SigSetIter};
 
 // Also include architecture-specific types.
 #[allow(unused_imports)]
@@ -0,0 +1,507 @@
+//! Access to the virtual dynamic shared object ("vDSO") that the kernel maps
+//! into every process.
+//!
+//! The vDSO exports functions that can answer some common questions, such as
+//! the current time, without entering the kernel at all. The top-level
+//! functions in this module use the vDSO when it exports a suitable function
+//! on the current platform, and fall back to the equivalent system call
+//! otherwise.
+//!
+//! The vDSO is located automatically on first use by reading the
+//! `AT_SYSINFO_EHDR` entry from `/proc/self/auxv`. Programs that already know
+//! the address of the vDSO, or that run without `/proc` mounted, can call
+//! [`init`] to provide the address explicitly before calling any other
+//! function in this module. A `no_std` program can find the address using
+//! [`crate::startup::StartupInfo::vdso_ehdr`].
+
+use core::ffi::CStr;
+use core::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
+
+use crate::raw::vdso as names;
+use crate::result::{Error, Result};
+use crate::{clockid_t, int, time_t, timespec, timeval, timezone, uint, void};
+
+type ClockGettimeFn = unsafe extern "C" fn(clockid_t, *mut timespec) -> int;
+type GettimeofdayFn = unsafe extern "C" fn(*mut timeval, *mut timezone) -> int;
+type TimeFn = unsafe extern "C" fn(*mut time_t) -> time_t;
+type GetcpuFn = unsafe extern "C" fn(*mut uint, *mut uint, *mut void) -> int;
+
+static INITIALIZED: AtomicBool = AtomicBool::new(false);
+static CLOCK_GETTIME: AtomicPtr<void> = AtomicPtr::new(core::ptr::null_mut());
+static GETTIMEOFDAY: AtomicPtr<void> = AtomicPtr::new(core::ptr::null_mut());
+static TIME: AtomicPtr<void> = AtomicPtr::new(core::ptr::null_mut());
+static GETCPU: AtomicPtr<void> = AtomicPtr::new(core::ptr::null_mut());
+
+/// Retrieve the time of the specified clock, using the vDSO if possible.
+///
+/// This has the same meaning as [`crate::clock_gettime`].
+#[inline]
+pub unsafe fn clock_gettime(clockid: clockid_t, tp: *mut timespec) -> Result<int> {
+    let f = symbol(&CLOCK_GETTIME);
+    if f.is_null() {
+        return crate::clock_gettime(clockid, tp);
+    }
+    let f: ClockGettimeFn = core::mem::transmute(f);
+    int_result(f(clockid, tp))
+}
+
+/// Get the current wall-clock time as seconds and microseconds since the
+/// Unix epoch, using the vDSO if possible.
+///
+/// This has the same meaning as [`crate::gettimeofday`].
+#[inline]
+pub unsafe fn gettimeofday(tv: *mut timeval, tz: *mut timezone) -> Result<int> {
+    let f = symbol(&GETTIMEOFDAY);
+    if f.is_null() {
+        return crate::gettimeofday(tv, tz);
+    }
+    let f: GettimeofdayFn = core::mem::transmute(f);
+    int_result(f(tv, tz))
+}
+
+/// Get the current wall-clock time in seconds since the Unix epoch, using the
+/// vDSO if possible.
+///
+/// This has the same meaning as [`crate::time`], but is available on all
+/// platforms: where there is neither a vDSO function nor a system call for
+/// this, it's implemented in terms of [`clock_gettime`] instead.
+#[inline]
+pub unsafe fn time(tloc: *mut time_t) -> Result<time_t> {
+    let f = symbol(&TIME);
+    if f.is_null() {
+        return time_fallback(tloc);
+    }
+    let f: TimeFn = core::mem::transmute(f);
+    let ret = f(tloc);
+    if (-4095..0).contains(&ret) {
+        return Err(Error::new(-ret as i32));
+    }
+    Ok(ret)
+}
+
+/// Determine CPU and NUMA node on which the calling thread is running, using
+/// the vDSO if possible.
+///
+/// This has the same meaning as [`crate::getcpu`].
+#[inline]
+pub unsafe fn getcpu(cpu: *mut uint, node: *mut uint) -> Result<int> {
+    let f = symbol(&GETCPU);
+    if f.is_null() {
+        return crate::getcpu(cpu, node);
+    }
+    let f: GetcpuFn = core::mem::transmute(f);
+    int_result(f(cpu, node, core::ptr::null_mut()))
+}
+
+/// Explicitly provide the address of the vDSO's ELF header, as given by the
+/// `AT_SYSINFO_EHDR` entry in the auxiliary vector.
+///
+/// This is needed only if `/proc/self/auxv` is unavailable. Set `ehdr` to
+/// null to disable use of the vDSO, so that all of the functions in this
+/// module will make system calls instead.
+///
+/// Safety: `ehdr` must either be null or point to the vDSO that the kernel
+/// mapped into the current process.
+pub unsafe fn init(ehdr: *const void) {
+    let vdso = Vdso::from_ehdr(ehdr);
+    let lookup = |name: Option<&[u8]>| -> *mut void {
+        match (&vdso, name) {
+            (Some(vdso), Some(name)) => vdso
+                .lookup(name, names::VERSION)
+                .map_or(core::ptr::null_mut(), |p| p as *mut void),
+            _ => core::ptr::null_mut(),
+        }
+    };
+    CLOCK_GETTIME.store(lookup(names::CLOCK_GETTIME), Ordering::Relaxed);
+    GETTIMEOFDAY.store(lookup(names::GETTIMEOFDAY), Ordering::Relaxed);
+    TIME.store(lookup(names::TIME), Ordering::Relaxed);
+    GETCPU.store(lookup(names::GETCPU), Ordering::Relaxed);
+    INITIALIZED.store(true, Ordering::Release);
+}
+
+#[inline(always)]
+unsafe fn symbol(slot: &AtomicPtr<void>) -> *mut void {
+    if !INITIALIZED.load(Ordering::Acquire) {
+        // If two threads race to get here then they will both find the
+        // same results, so it's harmless for them to both store them.
+        init(find_ehdr());
+    }
+    slot.load(Ordering::Relaxed)
+}
+
+#[inline(always)]
+fn int_result(ret: int) -> Result<int> {
+    // The vDSO functions use the same error convention as the raw
+    // system calls, returning a negated errno value on failure.
+    if ret < 0 {
+        Err(Error::new(-ret))
+    } else {
+        Ok(ret)
+    }
+}
+
+#[cfg(have_syscall = "time")]
+#[inline(always)]
+unsafe fn time_fallback(tloc: *mut time_t) -> Result<time_t> {
+    crate::time(tloc)
+}
+
+#[cfg(not(have_syscall = "time"))]
+#[inline(always)]
+unsafe fn time_fallback(tloc: *mut time_t) -> Result<time_t> {
+    let mut ts = timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    clock_gettime(crate::CLOCK_REALTIME, &mut ts)?;
+    if !tloc.is_null() {
+        *tloc = ts.tv_sec;
+    }
+    Ok(ts.tv_sec)
+}
+
+/// Find the address of the vDSO by searching the auxiliary vector as exposed
+/// in `/proc/self/auxv`, or return null if it isn't available.
+pub(crate) unsafe fn find_ehdr() -> *const void {
+    const PATH: &CStr = c"/proc/self/auxv";
+    let fd = match crate::openat(
+        crate::AT_FDCWD,
+        PATH.as_ptr(),
+        crate::O_RDONLY | crate::O_CLOEXEC,
+        0,
+    ) {
+        Ok(fd) => fd,
+        Err(_) => return core::ptr::null(),
+    };
+
+    // The auxiliary vector is a sequence of (type, value) pairs terminated
+    // by an entry whose type is zero. We read it in chunks that are always
+    // a whole number of pairs, because procfs returns as much as fits.
+    let mut buf = [0 as crate::ulong; 64];
+    let mut ret: *const void = core::ptr::null();
+    'read: loop {
+        let len = match crate::read(
+            fd,
+            buf.as_mut_ptr() as *mut void,
+            core::mem::size_of_val(&buf),
+        ) {
+            Ok(len) if len > 0 => len as usize,
+            _ => break,
+        };
+        let entries = len / core::mem::size_of::<crate::ulong>();
+        for pair in buf[..entries].chunks_exact(2) {
+            match pair[0] {
+                0 => break 'read,
+                crate::AT_SYSINFO_EHDR => {
+                    ret = pair[1] as *const void;
+                    break 'read;
+                }
+                _ => {}
+            }
+        }
+    }
+    let _ = crate::close(fd);
+    ret
+}
+
+/// A parsed vDSO image, used to look up the functions it exports.
+///
+/// Most callers should use the top-level functions in this module instead,
+/// which find and parse the vDSO automatically.
+#[derive(Clone, Copy, Debug)]
+pub struct Vdso {
+    load_offset: usize,
+    symtab: *const elf::Sym,
+    strtab: *const u8,
+    bucket: *const u32,
+    chain: *const u32,
+    nbucket: u32,
+    versym: *const u16,
+    verdef: *const elf::Verdef,
+}
+
+impl Vdso {
+    /// Parse the vDSO image whose ELF header is at the given address.
+    ///
+    /// Returns `None` if `ehdr` is null or if the image lacks the
+    /// information needed to look up symbols.
+    ///
+    /// Safety: `ehdr` must either be null or point to the vDSO that the
+    /// kernel mapped into the current process.
+    pub unsafe fn from_ehdr(ehdr: *const void) -> Option<Self> {
+        if ehdr.is_null() {
+            return None;
+        }
+        let base = ehdr as usize;
+        let hdr = &*(ehdr as *const elf::Ehdr);
+        if hdr.e_ident[..4] != *b"\x7fELF" || hdr.e_ident[4] != elf::CLASS {
+            return None;
+        }
+
+        let phdrs = core::slice::from_raw_parts(
+            (base + hdr.e_phoff as usize) as *const elf::Phdr,
+            hdr.e_phnum as usize,
+        );
+        let mut load_offset: Option<usize> = None;
+        let mut dyns: *const elf::Dyn = core::ptr::null();
+        for phdr in phdrs {
+            match phdr.p_type {
+                elf::PT_LOAD if load_offset.is_none() => {
+                    load_offset = Some(
+                        base.wrapping_add(phdr.p_offset as usize)
+                            .wrapping_sub(phdr.p_vaddr as usize),
+                    );
+                }
+                elf::PT_DYNAMIC => {
+                    dyns = (base + phdr.p_offset as usize) as *const elf::Dyn;
+                }
+                _ => {}
+            }
+        }
+        let load_offset = load_offset?;
+        if dyns.is_null() {
+            return None;
+        }
+
+        let mut symtab: *const elf::Sym = core::ptr::null();
+        let mut strtab: *const u8 = core::ptr::null();
+        let mut hash: *const u32 = core::ptr::null();
+        let mut versym: *const u16 = core::ptr::null();
+        let mut verdef: *const elf::Verdef = core::ptr::null();
+        let mut dyn_ptr = dyns;
+        loop {
+            let entry = &*dyn_ptr;
+            let addr = load_offset.wrapping_add(entry.d_val as usize);
+            match entry.d_tag {
+                elf::DT_NULL => break,
+                elf::DT_STRTAB => strtab = addr as *const u8,
+                elf::DT_SYMTAB => symtab = addr as *const elf::Sym,
+                elf::DT_HASH => hash = addr as *const u32,
+                elf::DT_VERSYM => versym = addr as *const u16,
+                elf::DT_VERDEF => verdef = addr as *const elf::Verdef,
+                _ => {}
+            }
+            dyn_ptr = dyn_ptr.add(1);
+        }
+        // All of the currently-supported platforms have a vDSO with a
+        // traditional SysV hash table, so we don't support DT_GNU_HASH.
+        if symtab.is_null() || strtab.is_null() || hash.is_null() {
+            return None;
+        }
+        if versym.is_null() || verdef.is_null() {
+            versym = core::ptr::null();
+            verdef = core::ptr::null();
+        }
+
+        let nbucket = *hash;
+        Some(Self {
+            load_offset,
+            symtab,
+            strtab,
+            bucket: hash.add(2),
+            chain: hash.add(2 + nbucket as usize),
+            nbucket,
+            versym,
+            verdef,
+        })
+    }
+
+    /// Find the address of the exported function with the given name and
+    /// symbol version, or `None` if there is no such function.
+    ///
+    /// The name and version must not include a null terminator.
+    pub fn lookup(&self, name: &[u8], version: &[u8]) -> Option<*const void> {
+        if self.nbucket == 0 {
+            return None;
+        }
+        let mut idx = unsafe { *self.bucket.add((elf_hash(name) % self.nbucket) as usize) };
+        while idx != 0 {
+            let sym = unsafe { &*self.symtab.add(idx as usize) };
+            if self.symbol_matches(sym, idx, name, version) {
+                return Some(self.load_offset.wrapping_add(sym.st_value as usize) as *const void);
+            }
+            idx = unsafe { *self.chain.add(idx as usize) };
+        }
+        None
+    }
+
+    fn symbol_matches(&self, sym: &elf::Sym, idx: u32, name: &[u8], version: &[u8]) -> bool {
+        let typ = sym.st_info & 0xf;
+        let bind = sym.st_info >> 4;
+        if typ != elf::STT_FUNC && typ != elf::STT_NOTYPE {
+            return false;
+        }
+        if bind != elf::STB_GLOBAL && bind != elf::STB_WEAK {
+            return false;
+        }
+        if sym.st_shndx == elf::SHN_UNDEF {
+            return false;
+        }
+        if unsafe { self.string(sym.st_name) } != name {
+            return false;
+        }
+        if self.versym.is_null() {
+            return true;
+        }
+        let ver = unsafe { *self.versym.add(idx as usize) } & 0x7fff;
+        unsafe { self.version_matches(ver, version) }
+    }
+
+    unsafe fn version_matches(&self, ver: u16, version: &[u8]) -> bool {
+        let mut def = self.verdef;
+        loop {
+            let d = &*def;
+            if (d.vd_flags & elf::VER_FLG_BASE) == 0 && (d.vd_ndx & 0x7fff) == ver {
+                let aux = &*((def as usize + d.vd_aux as usize) as *const elf::Verdaux);
+                return self.string(aux.vda_name) == version;
+            }
+            if d.vd_next == 0 {
+                return false;
+            }
+            def = (def as usize + d.vd_next as usize) as *const elf::Verdef;
+        }
+    }
+
+    unsafe fn string(&self, offset: u32) -> &[u8] {
+        CStr::from_ptr(self.strtab.add(offset as usize) as *const crate::char).to_bytes()
+    }
+}
+
+/// The hash function used for the SysV-style ELF symbol hash table.
+fn elf_hash(name: &[u8]) -> u32 {
+    let mut h: u32 = 0;
+    for &c in name {
+        h = (h << 4).wrapping_add(c as u32);
+        let g = h & 0xf0000000;
+        if g != 0 {
+            h ^= g >> 24;
+        }
+        h &= !g;
+    }
+    h
+}
+
+/// The subset of the ELF format needed to look up symbols in the vDSO,
+/// which always uses the native word size of the platform.
+#[allow(dead_code)]
+mod elf {
+    pub(super) const PT_LOAD: u32 = 1;
+    pub(super) const PT_DYNAMIC: u32 = 2;
+
+    pub(super) const DT_NULL: Sword = 0;
+    pub(super) const DT_HASH: Sword = 4;
+    pub(super) const DT_STRTAB: Sword = 5;
+    pub(super) const DT_SYMTAB: Sword = 6;
+    pub(super) const DT_VERSYM: Sword = 0x6ffffff0;
+    pub(super) const DT_VERDEF: Sword = 0x6ffffffc;
+
+    pub(super) const STT_NOTYPE: u8 = 0;
+    pub(super) const STT_FUNC: u8 = 2;
+    pub(super) const STB_GLOBAL: u8 = 1;
+    pub(super) const STB_WEAK: u8 = 2;
+    pub(super) const SHN_UNDEF: u16 = 0;
+    pub(super) const VER_FLG_BASE: u16 = 1;
+
+    #[cfg(target_pointer_width = "64")]
+    pub(super) const CLASS: u8 = 2;
+    #[cfg(target_pointer_width = "64")]
+    pub(super) type Addr = u64;
+    #[cfg(target_pointer_width = "64")]
+    pub(super) type Sword = i64;
+
+    #[cfg(target_pointer_width = "32")]
+    pub(super) const CLASS: u8 = 1;
+    #[cfg(target_pointer_width = "32")]
+    pub(super) type Addr = u32;
+    #[cfg(target_pointer_width = "32")]
+    pub(super) type Sword = i32;
+
+    #[repr(C)]
+    pub(super) struct Ehdr {
+        pub e_ident: [u8; 16],
+        pub e_type: u16,
+        pub e_machine: u16,
+        pub e_version: u32,
+        pub e_entry: Addr,
+        pub e_phoff: Addr,
+        pub e_shoff: Addr,
+        pub e_flags: u32,
+        pub e_ehsize: u16,
+        pub e_phentsize: u16,
+        pub e_phnum: u16,
+        pub e_shentsize: u16,
+        pub e_shnum: u16,
+        pub e_shstrndx: u16,
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[repr(C)]
+    pub(super) struct Phdr {
+        pub p_type: u32,
+        pub p_flags: u32,
+        pub p_offset: u64,
+        pub p_vaddr: u64,
+        pub p_paddr: u64,
+        pub p_filesz: u64,
+        pub p_memsz: u64,
+        pub p_align: u64,
+    }
+
+    #[cfg(target_pointer_width = "32")]
+    #[repr(C)]
+    pub(super) struct Phdr {
+        pub p_type: u32,
+        pub p_offset: u32,
+        pub p_vaddr: u32,
+        pub p_paddr: u32,
+        pub p_filesz: u32,
+        pub p_memsz: u32,
+        pub p_flags: u32,
+        pub p_align: u32,
+    }
+
+    #[repr(C)]
+    pub(super) struct Dyn {
+        pub d_tag: Sword,
+        pub d_val: Addr,
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[repr(C)]
+    pub(super) struct Sym {
+        pub st_name: u32,
+        pub st_info: u8,
+        pub st_other: u8,
+        pub st_shndx: u16,
+        pub st_value: u64,
+        pub st_size: u64,
+    }
+
+    #[cfg(target_pointer_width = "32")]
+    #[repr(C)]
+    pub(super) struct Sym {
+        pub st_name: u32,
+        pub st_value: u32,
+        pub st_size: u32,
+        pub st_info: u8,
+        pub st_other: u8,
+        pub st_shndx: u16,
+    }
+
+    #[repr(C)]
+    pub(super) struct Verdef {
+        pub vd_version: u16,
+        pub vd_flags: u16,
+        pub vd_ndx: u16,
+        pub vd_cnt: u16,
+        pub vd_hash: u32,
+        pub vd_aux: u32,
+        pub vd_next: u32,
+    }
+
+    #[repr(C)]
+    pub(super) struct Verdaux {
+        pub vda_name: u32,
+        pub vda_next: u32,
+    }
+}